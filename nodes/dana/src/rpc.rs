@@ -61,7 +61,9 @@ where
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
     C::Api: pallet_mmr_rpc::MmrRuntimeApi<Block, <Block as sp_runtime::traits::Block>::Hash>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
-    C::Api: parami_swap_rpc::SwapRuntimeApi<Block, AssetId, Balance>,
+    C::Api: parami_ad_rpc::AdRuntimeApi<Block, AssetId, Balance, DecentralizedId, Hash, BlockNumber>,
+    C::Api: parami_nft_rpc::NftRuntimeApi<Block, AssetId, Balance, DecentralizedId, BlockNumber>,
+    C::Api: parami_swap_rpc::SwapRuntimeApi<Block, AccountId, AssetId, Balance, BlockNumber>,
     C::Api: BlockBuilder<Block>,
     P: TransactionPool + 'static,
     B: sc_client_api::Backend<Block> + Send + Sync + 'static,
@@ -69,7 +71,9 @@ where
 {
     use pallet_mmr_rpc::{Mmr, MmrApiServer};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
+    use parami_ad_rpc::{AdApiServer, AdRpcHandler};
     use parami_did_rpc::{DidApiServer, DidRpcHandler};
+    use parami_nft_rpc::{NftApiServer, NftRpcHandler};
     use parami_swap_rpc::{SwapApiServer, SwapsRpcHandler};
     use substrate_frame_rpc_system::{System, SystemApiServer};
 
@@ -114,6 +118,8 @@ where
     {
         io.merge(did_rpc)?;
     }
+    io.merge(AdRpcHandler::new(client.clone()).into_rpc())?;
+    io.merge(NftRpcHandler::new(client.clone()).into_rpc())?;
     io.merge(SwapsRpcHandler::new(client.clone()).into_rpc())?;
 
     Ok(io)
@@ -0,0 +1,111 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use parami_primitives::BalanceWrapper;
+use sp_runtime::traits::{MaybeDisplay, MaybeFromStr};
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+pub type ApiResult<T> = Result<T, DispatchError>;
+
+sp_api::decl_runtime_apis! {
+    pub trait AdRuntimeApi<AssetId, Balance, DecentralizedId, Hash, Height>
+    where
+        AssetId: Codec,
+        Balance: Codec + MaybeDisplay + MaybeFromStr,
+        DecentralizedId: Codec,
+        Hash: Codec,
+        Height: Codec,
+    {
+        /// Get current slots of a NFT
+        ///
+        /// # Arguments
+        ///
+        /// * `nft_id` - The NFT ID
+        ///
+        /// # Results
+        ///
+        /// list of tuple of (ad_id, deadline)
+        ///
+        /// * `ad_id` - The advertisement currently in the slot
+        /// * `deadline` - The block number at which the slot expires
+        fn slots_of(
+            nft_id: AssetId,
+        ) -> ApiResult<Vec<(Hash, Height)>>;
+
+        /// Get remaining budget in the pot of a slot
+        ///
+        /// # Arguments
+        ///
+        /// * `nft_id` - The NFT ID
+        /// * `ad_id` - The advertisement in the slot
+        ///
+        /// # Results
+        ///
+        /// tuple of (fractions, fungibles)
+        ///
+        /// * `fractions` - The amount of fractions remaining
+        /// * `fungibles` - The amount of fungibles remaining, zero if none was bid
+        fn slot_budget(
+            nft_id: AssetId,
+            ad_id: Hash,
+        ) -> ApiResult<(BalanceWrapper<Balance>, BalanceWrapper<Balance>)>;
+
+        /// Get all advertisements of an advertiser
+        ///
+        /// # Arguments
+        ///
+        /// * `did` - The DID of the advertiser
+        ///
+        /// # Results
+        ///
+        /// The IDs of the advertisements
+        fn ads_of(
+            did: DecentralizedId,
+        ) -> ApiResult<Vec<Hash>>;
+
+        /// Check if a visitor has been paid for an advertisement
+        ///
+        /// # Arguments
+        ///
+        /// * `ad_id` - The advertisement ID
+        /// * `did` - The DID of the visitor
+        ///
+        /// # Results
+        ///
+        /// Whether the visitor has been paid
+        fn is_paid(
+            ad_id: Hash,
+            did: DecentralizedId,
+        ) -> ApiResult<bool>;
+
+        /// Dry-run a payout of an advertisement to a visitor
+        ///
+        /// # Arguments
+        ///
+        /// * `ad_id` - The advertisement ID
+        /// * `nft_id` - The NFT ID of the slot
+        /// * `visitor` - The DID of the visitor
+        /// * `referrer` - The DID of the referrer, if any
+        ///
+        /// # Results
+        ///
+        /// tuple of (score, reward, award, fungibles)
+        ///
+        /// * `score` - The score of the visitor
+        /// * `reward` - The amount of fractions to be paid to the visitor
+        /// * `award` - The amount of fractions to be paid to the referrer
+        /// * `fungibles` - The amount of fungibles to be paid to the visitor
+        fn dryly_pay(
+            ad_id: Hash,
+            nft_id: AssetId,
+            visitor: DecentralizedId,
+            referrer: Option<DecentralizedId>,
+        ) -> ApiResult<(
+            u32,
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+        )>;
+    }
+}
@@ -0,0 +1,284 @@
+use codec::Codec;
+use jsonrpsee::{
+    core::{async_trait, Error, RpcResult},
+    proc_macros::rpc,
+    types::error::{CallError, ErrorObject, INTERNAL_ERROR_CODE},
+};
+pub use parami_ad_rpc_runtime_api::{AdRuntimeApi, ApiResult};
+use parami_primitives::BalanceWrapper;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+    generic::BlockId,
+    traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
+};
+use std::sync::Arc;
+
+#[rpc(client, server)]
+pub trait AdApi<BlockHash, AssetId, Balance, DecentralizedId, Hash, Height>
+where
+    Balance: MaybeDisplay + MaybeFromStr,
+{
+    /// Get current slots of a NFT
+    ///
+    /// # Arguments
+    ///
+    /// * `nft_id` - The NFT ID
+    ///
+    /// # Results
+    ///
+    /// list of tuple of (ad_id, deadline)
+    ///
+    /// * `ad_id` - The advertisement currently in the slot
+    /// * `deadline` - The block number at which the slot expires
+    #[method(name = "ad_getSlotsOf")]
+    fn slots_of(&self, nft_id: AssetId, at: Option<BlockHash>)
+        -> RpcResult<Vec<(Hash, Height)>>;
+
+    /// Get remaining budget in the pot of a slot
+    ///
+    /// # Arguments
+    ///
+    /// * `nft_id` - The NFT ID
+    /// * `ad_id` - The advertisement in the slot
+    ///
+    /// # Results
+    ///
+    /// tuple of (fractions, fungibles)
+    ///
+    /// * `fractions` - The amount of fractions remaining
+    /// * `fungibles` - The amount of fungibles remaining, zero if none was bid
+    #[method(name = "ad_getSlotBudget")]
+    fn slot_budget(
+        &self,
+        nft_id: AssetId,
+        ad_id: Hash,
+        at: Option<BlockHash>,
+    ) -> RpcResult<(BalanceWrapper<Balance>, BalanceWrapper<Balance>)>;
+
+    /// Get all advertisements of an advertiser
+    ///
+    /// # Arguments
+    ///
+    /// * `did` - The DID of the advertiser
+    ///
+    /// # Results
+    ///
+    /// The IDs of the advertisements
+    #[method(name = "ad_getAdsOf")]
+    fn ads_of(&self, did: DecentralizedId, at: Option<BlockHash>) -> RpcResult<Vec<Hash>>;
+
+    /// Check if a visitor has been paid for an advertisement
+    ///
+    /// # Arguments
+    ///
+    /// * `ad_id` - The advertisement ID
+    /// * `did` - The DID of the visitor
+    ///
+    /// # Results
+    ///
+    /// Whether the visitor has been paid
+    #[method(name = "ad_isPaid")]
+    fn is_paid(&self, ad_id: Hash, did: DecentralizedId, at: Option<BlockHash>)
+        -> RpcResult<bool>;
+
+    /// Dry-run a payout of an advertisement to a visitor
+    ///
+    /// # Arguments
+    ///
+    /// * `ad_id` - The advertisement ID
+    /// * `nft_id` - The NFT ID of the slot
+    /// * `visitor` - The DID of the visitor
+    /// * `referrer` - The DID of the referrer, if any
+    ///
+    /// # Results
+    ///
+    /// tuple of (score, reward, award, fungibles)
+    ///
+    /// * `score` - The score of the visitor
+    /// * `reward` - The amount of fractions to be paid to the visitor
+    /// * `award` - The amount of fractions to be paid to the referrer
+    /// * `fungibles` - The amount of fungibles to be paid to the visitor
+    #[method(name = "ad_drylyPay")]
+    fn dryly_pay(
+        &self,
+        ad_id: Hash,
+        nft_id: AssetId,
+        visitor: DecentralizedId,
+        referrer: Option<DecentralizedId>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<(
+        u32,
+        BalanceWrapper<Balance>,
+        BalanceWrapper<Balance>,
+        BalanceWrapper<Balance>,
+    )>;
+}
+
+pub struct AdRpcHandler<C, Block, AssetId, Balance, DecentralizedId, Hash, Height> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<(Block, AssetId, Balance, DecentralizedId, Hash, Height)>,
+}
+
+impl<C, Block, AssetId, Balance, DecentralizedId, Hash, Height>
+    AdRpcHandler<C, Block, AssetId, Balance, DecentralizedId, Hash, Height>
+{
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl<C, Block, AssetId, Balance, DecentralizedId, Hash, Height>
+    AdApiServer<<Block as BlockT>::Hash, AssetId, Balance, DecentralizedId, Hash, Height>
+    for AdRpcHandler<C, Block, AssetId, Balance, DecentralizedId, Hash, Height>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: AdRuntimeApi<Block, AssetId, Balance, DecentralizedId, Hash, Height>,
+    AssetId: Codec + Send + Sync + 'static,
+    Balance: Codec + MaybeDisplay + MaybeFromStr + Send + Sync + 'static,
+    DecentralizedId: Codec + Send + Sync + 'static,
+    Hash: Codec + Send + Sync + 'static,
+    Height: Codec + Send + Sync + 'static,
+{
+    fn slots_of(
+        &self,
+        nft_id: AssetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(Hash, Height)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api.slots_of(&at, nft_id).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get slots.",
+                Some(format!("{:?}", e)),
+            )))
+        })?;
+
+        res.map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get slots.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    fn slot_budget(
+        &self,
+        nft_id: AssetId,
+        ad_id: Hash,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<(BalanceWrapper<Balance>, BalanceWrapper<Balance>)> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api.slot_budget(&at, nft_id, ad_id).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get slot budget.",
+                Some(format!("{:?}", e)),
+            )))
+        })?;
+
+        res.map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get slot budget.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    fn ads_of(
+        &self,
+        did: DecentralizedId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<Hash>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api.ads_of(&at, did).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get ads.",
+                Some(format!("{:?}", e)),
+            )))
+        })?;
+
+        res.map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get ads.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    fn is_paid(
+        &self,
+        ad_id: Hash,
+        did: DecentralizedId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<bool> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api.is_paid(&at, ad_id, did).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to check payout.",
+                Some(format!("{:?}", e)),
+            )))
+        })?;
+
+        res.map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to check payout.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    fn dryly_pay(
+        &self,
+        ad_id: Hash,
+        nft_id: AssetId,
+        visitor: DecentralizedId,
+        referrer: Option<DecentralizedId>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<(
+        u32,
+        BalanceWrapper<Balance>,
+        BalanceWrapper<Balance>,
+        BalanceWrapper<Balance>,
+    )> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api
+            .dryly_pay(&at, ad_id, nft_id, visitor, referrer)
+            .map_err(|e| {
+                Error::Call(CallError::Custom(ErrorObject::owned(
+                    INTERNAL_ERROR_CODE,
+                    "Unable to dry-run pay.",
+                    Some(format!("{:?}", e)),
+                )))
+            })?;
+
+        res.map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to dry-run pay.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+}
@@ -13,9 +13,21 @@ use parami_did::Pallet as Did;
 use parami_nft::Pallet as Nft;
 use parami_primitives::constants::DOLLARS;
 use parami_tag::Pallet as Tag;
+use parami_traits::types::Network;
 use sp_runtime::traits::{Bounded, Saturating};
 
 fn prepare_nft<T>(caller: &T::AccountId) -> T::AssetId
+where
+    T: parami_advertiser::Config,
+    T: parami_did::Config,
+    T: parami_nft::Config,
+    T: parami_tag::Config,
+    T: crate::Config,
+{
+    prepare_kol::<T>(caller, 1)
+}
+
+fn prepare_kol<T>(caller: &T::AccountId, index: u32) -> T::AssetId
 where
     T: parami_advertiser::Config,
     T: parami_did::Config,
@@ -28,19 +40,38 @@ where
         .map_err(|e| "balance conversion")
         .unwrap();
 
-    let nft_id = Zero::zero();
-
-    let kol: T::AccountId = account("kol", 1, 1);
+    let kol: T::AccountId = account("kol", index, index);
     Did::<T>::register(RawOrigin::Signed(kol.clone()).into(), None);
+    let did = Did::<T>::did_of(&kol).unwrap();
+
+    Nft::<T>::kick(RawOrigin::Signed(kol.clone()).into(), None, None);
+    let nft_id = Nft::<T>::preferred(did).unwrap();
 
-    Nft::<T>::kick(RawOrigin::Signed(kol.clone()).into());
     Nft::<T>::back(RawOrigin::Signed(caller.clone()).into(), nft_id, balance);
+
+    // symbols of fragments are unique, suffix them with the index in letters
+    let mut symbol = b"XT".to_vec();
+    let mut n = index;
+    loop {
+        symbol.push(b'A' + (n % 26) as u8);
+        n /= 26;
+        if n == 0 {
+            break;
+        }
+    }
+
     Nft::<T>::mint(
         RawOrigin::Signed(kol).into(),
         nft_id,
         b"Test Token".to_vec(),
-        b"XTT".to_vec(),
+        symbol,
     );
+
+    let vested = frame_system::Pallet::<T>::block_number()
+        .saturating_add(<T as parami_nft::Config>::InitialMintingVestingCliff::get())
+        .saturating_add(<T as parami_nft::Config>::InitialMintingVestingPeriod::get());
+    frame_system::Pallet::<T>::set_block_number(vested);
+
     Nft::<T>::claim(RawOrigin::Signed(caller.clone()).into(), nft_id);
 
     nft_id
@@ -79,6 +110,8 @@ where
         payout_base,
         payout_min,
         payout_max,
+        Zero::zero(),
+        1,
     )
     .unwrap();
 
@@ -87,6 +120,40 @@ where
     (caller, ad)
 }
 
+fn prepare_referrers<T>(
+    caller: &T::AccountId,
+    ad: <T as frame_system::Config>::Hash,
+    depth: u32,
+) -> Option<T::DecentralizedId>
+where
+    T: parami_advertiser::Config,
+    T: parami_did::Config,
+    T: parami_nft::Config,
+    T: parami_tag::Config,
+    T: crate::Config,
+{
+    if depth == 0 {
+        return None;
+    }
+
+    Ad::<T>::update_referral(
+        RawOrigin::Signed(caller.clone()).into(),
+        ad,
+        vec![Percent::from_percent(1); depth as usize],
+    )
+    .unwrap();
+
+    // an inviter chain of the given depth
+    let mut referrer = None;
+    for i in 0..depth {
+        let inviter: T::AccountId = account("referrer", i, i);
+        Did::<T>::register(RawOrigin::Signed(inviter.clone()).into(), referrer).unwrap();
+        referrer = Did::<T>::did_of(&inviter);
+    }
+
+    referrer
+}
+
 benchmarks! {
     where_clause {
         where
@@ -117,7 +184,7 @@ benchmarks! {
         Advertiser::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), balance)?;
         Tag::<T>::force_create(RawOrigin::Root.into(), vec![1u8; 6]);
 
-    }: _(RawOrigin::Signed(caller), vec![vec![1u8; 6]], vec![0u8; 500], 1, HeightOf::<T>::max_value(), payout_base, payout_min, payout_max)
+    }: _(RawOrigin::Signed(caller), vec![vec![1u8; 6]], vec![0u8; 500], 1, HeightOf::<T>::max_value(), payout_base, payout_min, payout_max, Zero::zero(), 1)
     verify {
         assert_ne!(<Metadata<T>>::iter_values().next(), None);
     }
@@ -130,6 +197,14 @@ benchmarks! {
         assert_eq!(ad.reward_rate, 100);
     }
 
+    update_frequency {
+        let (caller, ad) = prepare_ad::<T>();
+    }: _(RawOrigin::Signed(caller), ad, 100u32.into(), 10)
+    verify {
+        let ad = <Metadata<T>>::get(&ad).unwrap();
+        assert_eq!(ad.max_payouts, 10);
+    }
+
     update_tags {
         let (caller, ad) = prepare_ad::<T>();
 
@@ -138,13 +213,27 @@ benchmarks! {
         assert_eq!(Tag::<T>::tags_of(&ad).len(), 1);
     }
 
+    update_targeting {
+        let (caller, ad) = prepare_ad::<T>();
+
+        let targeting = types::Targeting {
+            min_scores: vec![(vec![1u8; 6], 5)],
+            excluded: vec![vec![1u8; 6]],
+            networks: vec![Network::Twitter],
+        };
+    }: _(RawOrigin::Signed(caller), ad, targeting.clone())
+    verify {
+        let ad = <Metadata<T>>::get(&ad).unwrap();
+        assert_eq!(ad.targeting, targeting);
+    }
+
     bid_with_fraction {
         let (caller, ad) = prepare_ad::<T>();
         let nft_id = prepare_nft::<T>(&caller);
 
     }: _(RawOrigin::Signed(caller.clone()), ad, nft_id, 1000u32.into(), None, None)
     verify {
-        assert_ne!(<SlotOf<T>>::get(T::AssetId::zero()), None);
+        assert_ne!(<SlotOf<T>>::get(nft_id, &ad), None);
     }
 
     add_budget {
@@ -157,18 +246,123 @@ benchmarks! {
         Ad::<T>::bid_with_fraction(RawOrigin::Signed(caller.clone()).into(), ad, nft_id, initial_bid, None, None)?;
     }: _(RawOrigin::Signed(caller.clone()), ad, nft_id, budget, None, None)
     verify {
-        let nft = <SlotOf<T>>::get(nft_id).unwrap();
+        let nft = <SlotOf<T>>::get(nft_id, &ad).unwrap();
         assert_eq!(<T as parami_nft::Config>::Assets::balance(nft.fraction_id, &nft.budget_pot), initial_bid.saturating_add(budget));
     }
 
+    extend {
+        let n in 1 .. T::MaxSlotsPerAd::get();
+
+        let (caller, ad) = prepare_ad::<T>();
+
+        for i in 0 .. n {
+            let nft_id = prepare_kol::<T>(&caller, i + 1);
+            Ad::<T>::bid_with_fraction(RawOrigin::Signed(caller.clone()).into(), ad, nft_id, 1000u32.into(), None, None)?;
+        }
+
+        let endtime: HeightOf<T> = <EndtimeOf<T>>::get(&ad).unwrap().saturating_sub(1u32.into());
+        <EndtimeOf<T>>::insert(&ad, endtime);
+    }: _(RawOrigin::Signed(caller.clone()), ad, HeightOf::<T>::max_value())
+    verify {
+        assert_eq!(<EndtimeOf<T>>::get(&ad), Some(HeightOf::<T>::max_value()));
+    }
+
+    cancel {
+        let n in 1 .. T::MaxSlotsPerAd::get();
+
+        let (caller, ad) = prepare_ad::<T>();
+
+        let mut nfts = vec![];
+        for i in 0 .. n {
+            let nft_id = prepare_kol::<T>(&caller, i + 1);
+            Ad::<T>::bid_with_fraction(RawOrigin::Signed(caller.clone()).into(), ad, nft_id, 1000u32.into(), None, None)?;
+
+            nfts.push(nft_id);
+        }
+    }: _(RawOrigin::Signed(caller.clone()), ad)
+    verify {
+        for nft_id in nfts {
+            assert_eq!(<SlotOf<T>>::get(nft_id, &ad), None);
+        }
+        assert_eq!(<Metadata<T>>::get(&ad), None);
+        assert_eq!(<SlotsOf<T>>::get(&ad).len(), 0);
+    }
+
+    withdraw_budget {
+        let (caller, ad) = prepare_ad::<T>();
+        let nft_id = prepare_nft::<T>(&caller);
+
+        Ad::<T>::bid_with_fraction(RawOrigin::Signed(caller.clone()).into(), ad, nft_id, 1000u32.into(), None, None)?;
+    }: _(RawOrigin::Signed(caller.clone()), ad, nft_id)
+    verify {
+        assert_ne!(<SlotOf<T>>::get(nft_id, &ad), None);
+    }
+
+    start_auction {
+        let (caller, ad) = prepare_ad::<T>();
+        let nft_id = prepare_nft::<T>(&caller);
+
+        let kol: T::AccountId = account("kol", 1, 1);
+    }: _(RawOrigin::Signed(kol), nft_id)
+    verify {
+        assert_ne!(<AuctionOf<T>>::get(nft_id), None);
+    }
+
+    commit_bid {
+        let (caller, ad) = prepare_ad::<T>();
+        let nft_id = prepare_nft::<T>(&caller);
+
+        let kol: T::AccountId = account("kol", 1, 1);
+        Ad::<T>::start_auction(RawOrigin::Signed(kol).into(), nft_id)?;
+
+        let value: BalanceOf<T> = 1000u32.into();
+        let sealed = T::Hashing::hash_of(&(ad, value, vec![0u8; 32]));
+    }: _(RawOrigin::Signed(caller.clone()), ad, nft_id, sealed, value)
+    verify {
+        assert_ne!(<SealedBidOf<T>>::get(nft_id, &ad), None);
+    }
+
+    reveal_bid {
+        let (caller, ad) = prepare_ad::<T>();
+        let nft_id = prepare_nft::<T>(&caller);
+
+        let kol: T::AccountId = account("kol", 1, 1);
+        Ad::<T>::start_auction(RawOrigin::Signed(kol).into(), nft_id)?;
+
+        let value: BalanceOf<T> = 1000u32.into();
+        let sealed = T::Hashing::hash_of(&(ad, value, vec![0u8; 32]));
+        Ad::<T>::commit_bid(RawOrigin::Signed(caller.clone()).into(), ad, nft_id, sealed, value)?;
+
+        let auction = <AuctionOf<T>>::get(nft_id).unwrap();
+        frame_system::Pallet::<T>::set_block_number(auction.commit_end);
+    }: _(RawOrigin::Signed(caller.clone()), ad, nft_id, value, vec![0u8; 32])
+    verify {
+        let bid = <SealedBidOf<T>>::get(nft_id, &ad).unwrap();
+        assert_eq!(bid.revealed, Some(value));
+    }
+
+    update_referral {
+        let (caller, ad) = prepare_ad::<T>();
+
+        let referral = vec![Percent::from_percent(1); T::MaxReferralDepth::get() as usize];
+    }: _(RawOrigin::Signed(caller), ad, referral)
+    verify {
+        let ad = <Metadata<T>>::get(&ad).unwrap();
+        assert_eq!(ad.referral.len() as u32, T::MaxReferralDepth::get());
+    }
+
     pay {
+        let r in 0 .. 5;
+
         let (caller, ad) = prepare_ad::<T>();
         let nft_id = prepare_nft::<T>(&caller);
 
         Ad::<T>::bid_with_fraction(RawOrigin::Signed(caller.clone()).into(), ad, Zero::zero(), 1000u32.into(), None, None);
 
+        let referrer = prepare_referrers::<T>(&caller, ad, r);
+
         let visitor: T::AccountId = account("visitor", 2, 2);
-        Did::<T>::register(RawOrigin::Signed(visitor.clone()).into(), None);
+        Did::<T>::register(RawOrigin::Signed(visitor.clone()).into(), referrer);
         let did = Did::<T>::did_of(&visitor).unwrap();
 
     }: _(RawOrigin::Signed(caller.clone()), ad, Zero::zero(), did, vec![(vec![1u8; 6], 5)], None)
@@ -176,5 +370,94 @@ benchmarks! {
         assert_ne!(<T as parami_nft::Config>::Assets::balance(T::AssetId::zero(), &visitor), Zero::zero());
     }
 
+    pay_batch {
+        let n in 1 .. T::MaxPayBatch::get();
+        let r in 0 .. 5;
+
+        let (caller, ad) = prepare_ad::<T>();
+        let nft_id = prepare_nft::<T>(&caller);
+
+        let budget: BalanceOf<T> = DOLLARS
+            .try_into()
+            .map_err(|e| "balance conversion")
+            .unwrap();
+
+        Ad::<T>::bid_with_fraction(RawOrigin::Signed(caller.clone()).into(), ad, Zero::zero(), budget, None, None);
+
+        let referrer = prepare_referrers::<T>(&caller, ad, r);
+
+        let mut visitors = vec![];
+        let mut batch = vec![];
+        for i in 0 .. n {
+            let visitor: T::AccountId = account("visitor", i, i);
+            Did::<T>::register(RawOrigin::Signed(visitor.clone()).into(), referrer);
+            let did = Did::<T>::did_of(&visitor).unwrap();
+
+            visitors.push(visitor);
+            batch.push((did, vec![(vec![1u8; 6], 5)], None));
+        }
+
+    }: _(RawOrigin::Signed(caller.clone()), ad, Zero::zero(), batch)
+    verify {
+        for visitor in visitors {
+            assert_ne!(<T as parami_nft::Config>::Assets::balance(T::AssetId::zero(), &visitor), Zero::zero());
+        }
+    }
+
+    on_initialize {
+        let n in 1 .. 100;
+
+        let (caller, ad) = prepare_ad::<T>();
+
+        let mut nfts = vec![];
+        for i in 0 .. n {
+            let nft_id = prepare_kol::<T>(&caller, i + 1);
+            Ad::<T>::bid_with_fraction(RawOrigin::Signed(caller.clone()).into(), ad, nft_id, 1000u32.into(), None, None)?;
+
+            nfts.push(nft_id);
+        }
+
+        let now = <DeadlineOf<T>>::get(nfts[0], &ad).unwrap();
+        <SweepCursor<T>>::put(now);
+    }: {
+        Ad::<T>::on_initialize(now);
+    }
+    verify {
+        for nft_id in nfts.into_iter().take(T::MaxExpiriesPerBlock::get() as usize) {
+            assert_eq!(<SlotOf<T>>::get(nft_id, &ad), None);
+        }
+    }
+
+    migrate_account {
+        let n in 1 .. 100;
+
+        let (caller, ad) = prepare_ad::<T>();
+        let dest: T::AccountId = account("dest", 0, 0);
+
+        let value: BalanceOf<T> = 1000u32.into();
+        let sealed = T::Hashing::hash_of(&(ad, value, vec![0u8; 32]));
+
+        let mut nfts = vec![];
+        for i in 0 .. n {
+            let nft_id = prepare_kol::<T>(&caller, i + 1);
+
+            let kol: T::AccountId = account("kol", i + 1, i + 1);
+            Ad::<T>::start_auction(RawOrigin::Signed(kol).into(), nft_id)?;
+
+            Ad::<T>::commit_bid(RawOrigin::Signed(caller.clone()).into(), ad, nft_id, sealed, value)?;
+
+            nfts.push(nft_id);
+        }
+
+        let items = <BidsOf<T>>::iter_prefix(&caller).count() as u32;
+    }: {
+        Ad::<T>::migrate(&caller, &dest, items)?;
+    }
+    verify {
+        for nft_id in nfts {
+            assert_eq!(<SealedBidOf<T>>::get(nft_id, &ad).unwrap().bidder, dest);
+        }
+    }
+
     impl_benchmark_test_suite!(Ad, crate::mock::new_test_ext(), crate::mock::Test);
 }
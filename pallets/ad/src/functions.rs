@@ -0,0 +1,169 @@
+use crate::{AccountOf, BalanceOf, Config, DidOf, Error, MetaOf, Pallet, SlotMetaOf, TagOf};
+
+use frame_support::{ensure, traits::tokens::fungibles::Inspect as FungInspect};
+use parami_nft::Pallet as Nft;
+use parami_traits::{Royalties, Tags};
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    DispatchError, Permill,
+};
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+
+impl<T: Config> Pallet<T> {
+    /// Calculate the score of a visitor
+    ///
+    /// personas matching tags of the advertisement are weighted by 10,
+    /// the result is normalized by the number of tags and floored at zero
+    pub(super) fn calculate_score(
+        tags: &BTreeMap<TagOf, bool>,
+        personas: &BTreeMap<TagOf, i32>,
+    ) -> u32 {
+        let mut scoring = 5i32;
+
+        for (tag, score) in personas {
+            let delta = if tags.contains_key(tag) {
+                score.saturating_mul(10)
+            } else {
+                *score
+            };
+            scoring.saturating_accrue(delta);
+        }
+
+        scoring /= tags.len().saturating_mul(10).saturating_add(1) as i32;
+
+        if scoring < 0 {
+            scoring = 0;
+        }
+
+        scoring as u32
+    }
+
+    /// Calculate the amount to be paid for a score,
+    /// clamped to the payout range of the advertisement
+    pub(super) fn calculate_amount(meta: &MetaOf<T>, scoring: u32) -> BalanceOf<T> {
+        let mut amount = meta.payout_base.saturating_mul(scoring.into());
+        if amount < meta.payout_min {
+            amount = meta.payout_min;
+        }
+        if amount > meta.payout_max {
+            amount = meta.payout_max;
+        }
+
+        amount
+    }
+
+    /// Calculate the awards of referrers out of the amount to be paid
+    ///
+    /// a given referrer is awarded by the reward rate,
+    /// the inviter chain is awarded level by level following the referral schedule
+    pub(super) fn calculate_awards(
+        meta: &MetaOf<T>,
+        amount: BalanceOf<T>,
+        referrers: &[DidOf<T>],
+    ) -> Vec<(DidOf<T>, BalanceOf<T>)> {
+        if meta.referral.is_empty() {
+            let rate = meta.reward_rate.into();
+            return referrers
+                .iter()
+                .take(1)
+                .map(|referrer| (*referrer, amount.saturating_mul(rate) / 100u32.into()))
+                .collect();
+        }
+
+        referrers
+            .iter()
+            .zip(meta.referral.iter())
+            .map(|(referrer, percent)| (*referrer, percent.mul_floor(amount)))
+            .collect()
+    }
+
+    /// Calculate the royalty of the NFT owner out of the reward of a visitor
+    pub(super) fn calculate_royalty(
+        slot: &SlotMetaOf<T>,
+        reward: BalanceOf<T>,
+    ) -> Option<(AccountOf<T>, BalanceOf<T>)> {
+        let (owner, royalty) = Nft::<T>::royalty_of(slot.nft_id)?;
+
+        let royalty = Permill::from_parts(royalty.saturating_mul(100)).mul_floor(reward);
+
+        if royalty.is_zero() {
+            return None;
+        }
+
+        Some((owner, royalty))
+    }
+
+    /// Deduct the royalty of the NFT owner from the reward of a visitor
+    ///
+    /// # Results
+    ///
+    /// tuple of (reward, royalty)
+    pub(super) fn deduct_royalty(
+        slot: &SlotMetaOf<T>,
+        reward: BalanceOf<T>,
+    ) -> (BalanceOf<T>, Option<(AccountOf<T>, BalanceOf<T>)>) {
+        let royalty = Self::calculate_royalty(slot, reward);
+
+        let reward = match &royalty {
+            Some((_, royalty)) => reward.saturating_sub(*royalty),
+            None => reward,
+        };
+
+        (reward, royalty)
+    }
+
+    /// Calculate the payout of a visitor against a slot
+    ///
+    /// # Results
+    ///
+    /// tuple of (score, reward, awards, fungibles)
+    pub(super) fn calculate_payout(
+        meta: &MetaOf<T>,
+        slot: &SlotMetaOf<T>,
+        visitor: &DidOf<T>,
+        referrers: &[DidOf<T>],
+    ) -> Result<
+        (
+            u32,
+            BalanceOf<T>,
+            Vec<(DidOf<T>, BalanceOf<T>)>,
+            BalanceOf<T>,
+        ),
+        DispatchError,
+    > {
+        let tags = T::Tags::tags_of(&meta.id);
+        let personas = T::Tags::personas_of(visitor);
+
+        let scoring = Self::calculate_score(&tags, &personas);
+        let amount = Self::calculate_amount(meta, scoring);
+
+        ensure!(
+            Self::slot_current_fraction_balance(slot) >= amount,
+            Error::<T>::InsufficientFractions
+        );
+
+        let fungibles = if let Some(fungible_id) = slot.fungible_id {
+            let fungibles = amount.clone();
+            let fungibles_balance = T::Assets::balance(fungible_id, &slot.budget_pot);
+            ensure!(
+                fungibles_balance >= fungibles,
+                Error::<T>::InsufficientFungibles
+            );
+            fungibles
+        } else {
+            Zero::zero()
+        };
+
+        let awards = Self::calculate_awards(meta, amount, referrers);
+
+        let award = awards
+            .iter()
+            .fold(Zero::zero(), |total: BalanceOf<T>, (_, award)| {
+                total.saturating_add(*award)
+            });
+
+        let reward = amount.saturating_sub(award);
+
+        Ok((scoring, reward, awards, fungibles))
+    }
+}
@@ -14,46 +14,51 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+mod functions;
 mod migrations;
 mod types;
 
 use frame_support::pallet_prelude::*;
 use frame_support::traits::Hooks;
 use frame_support::{
-    dispatch::DispatchResult,
+    dispatch::{DispatchResult, DispatchResultWithPostInfo},
     ensure,
     traits::{
         tokens::fungibles::{Inspect as FungInspect, Transfer as FungTransfer},
         Currency, StorageVersion,
     },
+    transactional,
     weights::Weight,
     Blake2_256, PalletId, StorageHasher,
 };
 
 use frame_system::pallet_prelude::BlockNumberFor;
 use frame_system::pallet_prelude::*;
-use parami_did::Pallet as Did;
+use parami_did::{EnsureDid, Pallet as Did};
 use parami_nft::Pallet as Nft;
-use parami_traits::Tags;
+use parami_traits::{AccountMigration, Holders, Links, Migrated, Tags};
 use sp_runtime::{
-    traits::{AccountIdConversion, Hash, Saturating, Zero},
-    DispatchError,
+    traits::{AccountIdConversion, Hash, SaturatedConversion, Saturating, Zero},
+    DispatchError, Percent,
 };
 use sp_std::prelude::*;
 use weights::WeightInfo;
 
 type AccountOf<T> = <T as frame_system::Config>::AccountId;
 type AssetsOf<T> = <T as parami_nft::Config>::AssetId;
+type AuctionMetaOf<T> = types::Auction<HeightOf<T>>;
 type BalanceOf<T> = <<T as parami_did::Config>::Currency as Currency<AccountOf<T>>>::Balance;
 type DidOf<T> = <T as parami_did::Config>::DecentralizedId;
 type HashOf<T> = <<T as frame_system::Config>::Hashing as Hash>::Output;
 type HeightOf<T> = <T as frame_system::Config>::BlockNumber;
 type MetaOf<T> = types::Metadata<BalanceOf<T>, DidOf<T>, HashOf<T>, HeightOf<T>>;
 type NftOf<T> = <T as parami_nft::Config>::AssetId;
+type PayoutMetaOf<T> = types::Payout<HeightOf<T>>;
+type SealedBidMetaOf<T> = types::SealedBid<AccountOf<T>, BalanceOf<T>, HashOf<T>>;
 type SlotMetaOf<T> = types::Slot<HashOf<T>, HeightOf<T>, NftOf<T>, AssetsOf<T>, AccountOf<T>>;
 type TagOf = <Blake2_256 as StorageHasher>::Output;
 
-const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(7);
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -80,9 +85,52 @@ pub mod pallet {
         #[pallet::constant]
         type SlotLifetime: Get<HeightOf<Self>>;
 
+        /// The maximum number of concurrent slots of a NFT
+        #[pallet::constant]
+        type MaxSlotsPerNft: Get<u32>;
+
+        /// The maximum number of concurrent slots of an advertisement
+        #[pallet::constant]
+        type MaxSlotsPerAd: Get<u32>;
+
+        /// The increment over the lowest budget required to take over a slot
+        #[pallet::constant]
+        type OutbidIncrement: Get<Percent>;
+
+        /// The lifetime of the commit window of a sealed-bid auction
+        #[pallet::constant]
+        type AuctionCommitPeriod: Get<HeightOf<Self>>;
+
+        /// The lifetime of the reveal window of a sealed-bid auction
+        #[pallet::constant]
+        type AuctionRevealPeriod: Get<HeightOf<Self>>;
+
+        /// The maximum number of sealed bids of an auction
+        #[pallet::constant]
+        type MaxSealedBids: Get<u32>;
+
+        /// The maximum depth of the inviter chain awarded by an advertisement
+        #[pallet::constant]
+        type MaxReferralDepth: Get<u32>;
+
+        /// The maximum number of visitors paid in a batch
+        #[pallet::constant]
+        type MaxPayBatch: Get<u32>;
+
+        /// The maximum number of expired slots and advertisements swept in a block
+        #[pallet::constant]
+        type MaxExpiriesPerBlock: Get<u32>;
+
+        /// The maximum number of payouts of ended advertisements removed in a block
+        #[pallet::constant]
+        type MaxPurgesPerBlock: Get<u32>;
+
         /// The means of storing the tags and tags of advertisement
         type Tags: Tags<TagOf, HashOf<Self>, DidOf<Self>>;
 
+        /// The links trait
+        type Links: Links<DidOf<Self>>;
+
         /// The origin which may do calls
         type CallOrigin: EnsureOrigin<Self::Origin, Success = (DidOf<Self>, AccountOf<Self>)>;
 
@@ -126,10 +174,22 @@ pub mod pallet {
         HeightOf<T>,
     >;
 
-    /// Slot of a NFT
+    /// Slots of a NFT
     #[pallet::storage]
     #[pallet::getter(fn slot_of)]
-    pub(super) type SlotOf<T: Config> = StorageMap<_, Twox64Concat, NftOf<T>, SlotMetaOf<T>>;
+    pub(super) type SlotOf<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        NftOf<T>, // KOL NFT ID
+        Identity,
+        HashOf<T>,
+        SlotMetaOf<T>,
+    >;
+
+    /// KOL NFTs an advertisement occupies slots of
+    #[pallet::storage]
+    pub(super) type SlotsOf<T: Config> =
+        StorageMap<_, Identity, HashOf<T>, BoundedVec<NftOf<T>, T::MaxSlotsPerAd>, ValueQuery>;
 
     /// Payouts of an advertisement
     #[pallet::storage]
@@ -140,9 +200,66 @@ pub mod pallet {
         HashOf<T>,
         Identity,
         DidOf<T>, //
+        PayoutMetaOf<T>,
+    >;
+
+    /// Slots expiring at a block
+    #[pallet::storage]
+    pub(super) type DeadlineQueue<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
         HeightOf<T>,
+        Twox64Concat,
+        (NftOf<T>, HashOf<T>), // KOL NFT ID, Advertisement ID
+        (),
+    >;
+
+    /// Advertisements ending at a block
+    #[pallet::storage]
+    pub(super) type EndtimeQueue<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, HeightOf<T>, Identity, HashOf<T>, ()>;
+
+    /// Sealed-bid auction of a NFT
+    #[pallet::storage]
+    #[pallet::getter(fn auction_of)]
+    pub(super) type AuctionOf<T: Config> = StorageMap<_, Twox64Concat, NftOf<T>, AuctionMetaOf<T>>;
+
+    /// Sealed bids of an auction
+    #[pallet::storage]
+    #[pallet::getter(fn sealed_bid_of)]
+    pub(super) type SealedBidOf<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        NftOf<T>, // KOL NFT ID
+        Identity,
+        HashOf<T>,
+        SealedBidMetaOf<T>,
+    >;
+
+    /// Sealed bids of a bidder
+    #[pallet::storage]
+    pub(super) type BidsOf<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        AccountOf<T>,
+        Twox64Concat,
+        (NftOf<T>, HashOf<T>), // KOL NFT ID, Advertisement ID
+        (),
     >;
 
+    /// Auctions closing at a block
+    #[pallet::storage]
+    pub(super) type AuctionQueue<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, HeightOf<T>, Twox64Concat, NftOf<T>, ()>;
+
+    /// Ended advertisements with payouts left to remove
+    #[pallet::storage]
+    pub(super) type PurgeQueue<T: Config> = StorageMap<_, Identity, HashOf<T>, ()>;
+
+    /// The earliest block of which expiries are not fully swept
+    #[pallet::storage]
+    pub(super) type SweepCursor<T: Config> = StorageValue<_, HeightOf<T>, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -156,17 +273,37 @@ pub mod pallet {
         Bid(NftOf<T>, HashOf<T>, BalanceOf<T>),
         /// Advertisement (in slot) deadline reached \[kol, id, value\]
         End(NftOf<T>, HashOf<T>, BalanceOf<T>),
-        /// Advertisement payout \[id, nft, visitor, value, referrer, value\]
+        /// Advertisement payout \[id, nft, visitor, value, referrers and values, count\]
         Paid(
             HashOf<T>,
             NftOf<T>,
             DidOf<T>,
             BalanceOf<T>,
-            Option<DidOf<T>>,
-            BalanceOf<T>,
+            Vec<(DidOf<T>, BalanceOf<T>)>,
+            u32,
         ),
         /// Swap Triggered \[id, kol, remain\]
         SwapTriggered(HashOf<T>, NftOf<T>, BalanceOf<T>),
+        /// Advertisement payout failed in a batch \[id, nft, visitor, error\]
+        PayFailed(HashOf<T>, NftOf<T>, DidOf<T>, DispatchError),
+        /// Batch of payouts processed \[id, nft, paid, processed\]
+        BatchPaid(HashOf<T>, NftOf<T>, u32, u32),
+        /// Royalty paid to the NFT owner out of a payout \[id, nft, account, value\]
+        RoyaltyPaid(HashOf<T>, NftOf<T>, AccountOf<T>, BalanceOf<T>),
+        /// Advertisement end time extended \[id, endtime\]
+        Extended(HashOf<T>, HeightOf<T>),
+        /// Advertisement cancelled \[id\]
+        Cancelled(HashOf<T>),
+        /// Budget withdrawn from a slot \[kol, id, value\]
+        Withdrawn(NftOf<T>, HashOf<T>, BalanceOf<T>),
+        /// Sealed-bid auction started \[kol, commit_end, reveal_end\]
+        AuctionStarted(NftOf<T>, HeightOf<T>, HeightOf<T>),
+        /// Sealed bid committed \[kol, id, deposit\]
+        BidCommitted(NftOf<T>, HashOf<T>, BalanceOf<T>),
+        /// Sealed bid revealed \[kol, id, value\]
+        BidRevealed(NftOf<T>, HashOf<T>, BalanceOf<T>),
+        /// Sealed-bid auction closed \[kol, winner, value\]
+        AuctionClosed(NftOf<T>, Option<HashOf<T>>, BalanceOf<T>),
     }
 
     #[pallet::hooks]
@@ -180,12 +317,12 @@ pub mod pallet {
 
         #[cfg(feature = "try-runtime")]
         fn pre_upgrade() -> Result<(), &'static str> {
-            Ok(())
+            migrations::pre_upgrade::<T>()
         }
 
         #[cfg(feature = "try-runtime")]
         fn post_upgrade() -> Result<(), &'static str> {
-            Ok(())
+            migrations::post_upgrade::<T>()
         }
 
         fn on_runtime_upgrade() -> Weight {
@@ -209,9 +346,26 @@ pub mod pallet {
         Underbid,
         FungiblesNotEqualToFractions,
         WrongPayoutSetting,
+        WrongFrequencySetting,
+        InCooldown,
+        NotTargeted,
+        WrongReferralSetting,
         DrawbackFailedForDidNotExists,
         SlotNotExists,
         FungibleNotForSlot,
+        AlreadyInSlot,
+        AlreadyCommitted,
+        AuctionExists,
+        AuctionNotExists,
+        InAuction,
+        InvalidReveal,
+        NotCommitPeriod,
+        NotRevealPeriod,
+        TooManyBids,
+        TooManySlots,
+        Frozen,
+        SlotsRemaining,
+        TooManyPayouts,
     }
 
     #[pallet::call]
@@ -226,12 +380,15 @@ pub mod pallet {
             payout_base: BalanceOf<T>,
             payout_min: BalanceOf<T>,
             payout_max: BalanceOf<T>,
+            cooldown: HeightOf<T>,
+            max_payouts: u32,
         ) -> DispatchResult {
             let created = <frame_system::Pallet<T>>::block_number();
 
             ensure!(deadline > created, Error::<T>::Deadline);
             //TODO: ensure!(payout_base > xxx)
             ensure!(payout_min < payout_max, Error::<T>::WrongPayoutSetting);
+            ensure!(max_payouts > 0, Error::<T>::WrongFrequencySetting);
             let (creator, who) = T::CallOrigin::ensure_origin(origin)?;
 
             for tag in &tags {
@@ -261,10 +418,14 @@ pub mod pallet {
                     payout_base,
                     payout_min,
                     payout_max,
+                    cooldown,
+                    max_payouts,
+                    targeting: Default::default(),
+                    referral: Default::default(),
                 },
             );
 
-            <EndtimeOf<T>>::insert(&id, deadline);
+            Self::set_endtime(id, deadline);
 
             <AdsOf<T>>::mutate(&creator, |maybe| {
                 if let Some(ads) = maybe {
@@ -307,6 +468,34 @@ pub mod pallet {
             Ok(())
         }
 
+        #[pallet::weight(<T as Config>::WeightInfo::update_frequency())]
+        pub fn update_frequency(
+            origin: OriginFor<T>,
+            id: HashOf<T>,
+            cooldown: HeightOf<T>,
+            max_payouts: u32,
+        ) -> DispatchResult {
+            let (did, _) = T::CallOrigin::ensure_origin(origin)?;
+
+            ensure!(max_payouts > 0, Error::<T>::WrongFrequencySetting);
+
+            let height = <frame_system::Pallet<T>>::block_number();
+
+            let endtime = <EndtimeOf<T>>::get(&id).ok_or(Error::<T>::NotExists)?;
+            ensure!(endtime > height, Error::<T>::Deadline);
+
+            let mut meta = Self::ensure_owned(did, id)?;
+
+            meta.cooldown = cooldown;
+            meta.max_payouts = max_payouts;
+
+            <Metadata<T>>::insert(&id, meta);
+
+            Self::deposit_event(Event::Updated(id));
+
+            Ok(())
+        }
+
         #[pallet::weight(<T as Config>::WeightInfo::update_tags())]
         pub fn update_tags(
             origin: OriginFor<T>,
@@ -336,6 +525,71 @@ pub mod pallet {
             Ok(())
         }
 
+        #[pallet::weight(<T as Config>::WeightInfo::update_targeting())]
+        pub fn update_targeting(
+            origin: OriginFor<T>,
+            id: HashOf<T>,
+            targeting: types::Targeting,
+        ) -> DispatchResult {
+            let (did, _) = T::CallOrigin::ensure_origin(origin)?;
+
+            let height = <frame_system::Pallet<T>>::block_number();
+
+            let endtime = <EndtimeOf<T>>::get(&id).ok_or(Error::<T>::NotExists)?;
+            ensure!(endtime > height, Error::<T>::Deadline);
+
+            let mut meta = Self::ensure_owned(did, id)?;
+
+            for (tag, _) in &targeting.min_scores {
+                ensure!(T::Tags::exists(tag), Error::<T>::TagNotExists);
+            }
+            for tag in &targeting.excluded {
+                ensure!(T::Tags::exists(tag), Error::<T>::TagNotExists);
+            }
+
+            meta.targeting = targeting;
+
+            <Metadata<T>>::insert(&id, meta);
+
+            Self::deposit_event(Event::Updated(id));
+
+            Ok(())
+        }
+
+        #[pallet::weight(<T as Config>::WeightInfo::update_referral())]
+        pub fn update_referral(
+            origin: OriginFor<T>,
+            id: HashOf<T>,
+            referral: Vec<Percent>,
+        ) -> DispatchResult {
+            let (did, _) = T::CallOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                referral.len() as u32 <= T::MaxReferralDepth::get(),
+                Error::<T>::WrongReferralSetting
+            );
+
+            let total = referral
+                .iter()
+                .fold(0u32, |total, percent| total + percent.deconstruct() as u32);
+            ensure!(total <= 100, Error::<T>::WrongReferralSetting);
+
+            let height = <frame_system::Pallet<T>>::block_number();
+
+            let endtime = <EndtimeOf<T>>::get(&id).ok_or(Error::<T>::NotExists)?;
+            ensure!(endtime > height, Error::<T>::Deadline);
+
+            let mut meta = Self::ensure_owned(did, id)?;
+
+            meta.referral = referral;
+
+            <Metadata<T>>::insert(&id, meta);
+
+            Self::deposit_event(Event::Updated(id));
+
+            Ok(())
+        }
+
         #[pallet::weight(<T as Config>::WeightInfo::bid())]
         pub fn bid_with_fraction(
             origin: OriginFor<T>,
@@ -367,6 +621,7 @@ pub mod pallet {
 
             let nft_meta = Nft::<T>::meta(nft_id).ok_or(Error::<T>::NotMinted)?;
             ensure!(nft_meta.minted, Error::<T>::NotMinted);
+            ensure!(!Nft::<T>::is_frozen(nft_id), Error::<T>::Frozen);
 
             let created = <frame_system::Pallet<T>>::block_number();
 
@@ -378,27 +633,23 @@ pub mod pallet {
                 Error::<T>::InsufficientFractions
             );
 
-            // 1. check slot of kol
-            let slot = <SlotOf<T>>::get(nft_id);
-
-            // 2. if slot is used
-            // require a 20% increase of current budget
-            // and drawback current ad
+            // 1. check slots of kol
+            ensure!(!<AuctionOf<T>>::contains_key(nft_id), Error::<T>::InAuction);
 
-            if let Some(slot) = slot {
-                let locked_fractions = Self::slot_current_fraction_balance(&slot);
+            ensure!(
+                !<SlotOf<T>>::contains_key(nft_id, &ad_id),
+                Error::<T>::AlreadyInSlot
+            );
+            Self::ensure_slots_left(ad_id)?;
 
-                ensure!(
-                    fraction_value.saturating_mul(100u32.into())
-                        > locked_fractions.saturating_mul(120u32.into()),
-                    Error::<T>::Underbid
-                );
+            // 2. if all slots are used
+            // require an increase of the lowest budget
+            // and drawback the lowest ad
 
-                Self::drawback(&slot)?;
-            }
+            Self::ensure_vacancy(nft_id, fraction_value)?;
 
             // 3. deposit fractions and fungibles
-            let pot = Self::generate_slot_pot(nft_id);
+            let pot = Self::generate_slot_pot(nft_id, ad_id);
             T::Assets::transfer(nft_meta.token_asset_id, &who, &pot, fraction_value, false)?;
 
             if let Some(fungible_id) = fungible_id {
@@ -407,25 +658,16 @@ pub mod pallet {
 
             // 4. update slot
 
-            let lifetime = T::SlotLifetime::get();
-            let slotlife = created.saturating_add(lifetime);
-            let deadline = if slotlife > endtime {
-                endtime
-            } else {
-                slotlife
-            };
-
-            let slot = types::Slot {
+            Self::occupy(
                 ad_id,
                 nft_id,
-                fraction_id: nft_meta.token_asset_id,
-                budget_pot: pot,
+                nft_meta.token_asset_id,
+                pot,
                 fungible_id,
                 created,
-            };
+                endtime,
+            )?;
 
-            <SlotOf<T>>::insert(nft_id, &slot);
-            <DeadlineOf<T>>::insert(nft_id, &ad_id, deadline);
             <Metadata<T>>::insert(&ad_id, &ad_meta);
 
             Self::deposit_event(Event::Bid(nft_id, ad_id, fraction_value));
@@ -448,8 +690,7 @@ pub mod pallet {
             let endtime = <EndtimeOf<T>>::get(&ad_id).ok_or(Error::<T>::NotExists)?;
             ensure!(endtime > height, Error::<T>::Deadline);
 
-            let slot = <SlotOf<T>>::get(nft_id).ok_or(Error::<T>::SlotNotExists)?;
-            ensure!(slot.ad_id == ad_id, Error::<T>::NotOwned);
+            let slot = <SlotOf<T>>::get(nft_id, &ad_id).ok_or(Error::<T>::SlotNotExists)?;
 
             ensure!(
                 T::Assets::balance(slot.fraction_id, &who) >= fraction_value,
@@ -482,218 +723,477 @@ pub mod pallet {
             Ok(())
         }
 
-        #[pallet::weight(<T as Config>::WeightInfo::pay())]
-        pub fn pay(
-            origin: OriginFor<T>,
-            ad_id: HashOf<T>,
-            nft_id: NftOf<T>,
-            visitor: DidOf<T>,
-            scores: Vec<(Vec<u8>, i8)>,
-            referrer: Option<DidOf<T>>,
-        ) -> DispatchResult {
-            ensure!(!scores.is_empty(), Error::<T>::EmptyTags);
-
-            let (did, _who) = T::CallOrigin::ensure_origin(origin)?;
+        #[pallet::weight(<T as Config>::WeightInfo::extend(T::MaxSlotsPerAd::get()))]
+        pub fn extend(origin: OriginFor<T>, id: HashOf<T>, endtime: HeightOf<T>) -> DispatchResult {
+            let (did, _) = T::CallOrigin::ensure_origin(origin)?;
 
             let height = <frame_system::Pallet<T>>::block_number();
 
-            let endtime = <EndtimeOf<T>>::get(&ad_id).ok_or(Error::<T>::NotExists)?;
-            ensure!(endtime > height, Error::<T>::Deadline);
+            let current = <EndtimeOf<T>>::get(&id).ok_or(Error::<T>::NotExists)?;
+            ensure!(current > height, Error::<T>::Deadline);
+            ensure!(endtime > current, Error::<T>::Deadline);
 
-            let ad_meta = Self::ensure_owned(did, ad_id)?;
-            let nft_meta = Nft::<T>::meta(nft_id).ok_or(Error::<T>::NotMinted)?;
-            ensure!(nft_meta.minted, Error::<T>::NotMinted);
+            Self::ensure_owned(did, id)?;
 
-            let deadline = <DeadlineOf<T>>::get(nft_id, &ad_id).ok_or(Error::<T>::NotExists)?;
-            ensure!(deadline > height, Error::<T>::Deadline);
+            Self::set_endtime(id, endtime);
 
-            ensure!(
-                !<Payout<T>>::contains_key(&ad_id, &visitor),
-                Error::<T>::Paid
-            );
+            // slots capped by the previous end time live on until their own lifetime
 
-            // 1. get slot, check current ad
-            let slot = <SlotOf<T>>::get(nft_id).ok_or(Error::<T>::NotExists)?;
-            ensure!(slot.ad_id == ad_id, Error::<T>::Underbid);
-
-            // 2. scoring visitor
-            let mut scoring = 5i32;
-
-            let tags = T::Tags::tags_of(&ad_id);
-            let personas = T::Tags::personas_of(&visitor);
-            let length = tags.len();
-            for (tag, score) in personas {
-                let delta = if tags.contains_key(&tag) {
-                    score.saturating_mul(10)
-                } else {
-                    score
-                };
-                scoring.saturating_accrue(delta);
+            let lifetime = T::SlotLifetime::get();
+            for nft_id in Self::slots_of(id) {
+                if let Some(slot) = <SlotOf<T>>::get(nft_id, &id) {
+                    let slotlife = slot.created.saturating_add(lifetime);
+                    let deadline = if slotlife > endtime {
+                        endtime
+                    } else {
+                        slotlife
+                    };
+
+                    Self::set_deadline(nft_id, id, deadline);
+                }
             }
 
-            scoring /= length.saturating_mul(10).saturating_add(1) as i32;
+            Self::deposit_event(Event::Extended(id, endtime));
 
-            if scoring < 0 {
-                scoring = 0;
-            }
+            Ok(())
+        }
 
-            let scoring = scoring as u32;
+        #[pallet::weight(<T as Config>::WeightInfo::cancel(T::MaxSlotsPerAd::get())
+            .saturating_add(T::DbWeight::get().writes(T::MaxPurgesPerBlock::get() as Weight)))]
+        pub fn cancel(origin: OriginFor<T>, id: HashOf<T>) -> DispatchResult {
+            let (did, _) = T::CallOrigin::ensure_origin(origin)?;
 
-            let mut amount = ad_meta.payout_base.saturating_mul(scoring.into());
-            if amount < ad_meta.payout_min {
-                amount = ad_meta.payout_min;
-            }
-            if amount > ad_meta.payout_max {
-                amount = ad_meta.payout_max;
-            }
+            let height = <frame_system::Pallet<T>>::block_number();
 
-            ensure!(
-                Self::slot_current_fraction_balance(&slot) >= amount,
-                Error::<T>::InsufficientFractions
-            );
+            let endtime = <EndtimeOf<T>>::get(&id).ok_or(Error::<T>::NotExists)?;
+            ensure!(endtime > height, Error::<T>::Deadline);
 
-            let fungibles = if let Some(fungible_id) = slot.fungible_id {
-                let fungibles = amount.clone();
-                let fungibles_balance = T::Assets::balance(fungible_id, &slot.budget_pot);
-                ensure!(
-                    fungibles_balance >= fungibles,
-                    Error::<T>::InsufficientFungibles
-                );
-                fungibles
-            } else {
-                Zero::zero()
-            };
+            Self::ensure_owned(did, id)?;
 
-            // 3. influence visitor
-            for (tag, score) in scores {
-                ensure!(T::Tags::has_tag(&ad_id, &tag), Error::<T>::TagNotExists);
-                ensure!(score >= -5 && score <= 5, Error::<T>::ScoreOutOfRange);
+            Self::drawback_all(id)?;
 
-                T::Tags::influence(&visitor, &tag, score as i32)?;
-            }
+            Self::destroy(id, T::MaxPurgesPerBlock::get())?;
 
-            // 4. payout assets
+            Self::deposit_event(Event::Cancelled(id));
 
-            let account =
-                Did::<T>::lookup_did(visitor).ok_or(parami_did::Error::<T>::DidNotExists)?;
+            Ok(())
+        }
 
-            let award = if let Some(referrer) = referrer {
-                let rate = ad_meta.reward_rate.into();
-                let award = amount.saturating_mul(rate) / 100u32.into();
+        #[pallet::weight(<T as Config>::WeightInfo::withdraw_budget())]
+        pub fn withdraw_budget(
+            origin: OriginFor<T>,
+            ad_id: HashOf<T>,
+            nft_id: NftOf<T>,
+        ) -> DispatchResult {
+            let (did, _) = T::CallOrigin::ensure_origin(origin)?;
 
-                let referrer =
-                    Did::<T>::lookup_did(referrer).ok_or(parami_did::Error::<T>::DidNotExists)?;
+            Self::ensure_owned(did, ad_id)?;
 
-                T::Assets::transfer(slot.nft_id, &slot.budget_pot, &referrer, award, false)?;
+            ensure!(
+                <SlotOf<T>>::contains_key(nft_id, &ad_id),
+                Error::<T>::SlotNotExists
+            );
 
-                award
-            } else {
-                Zero::zero()
-            };
+            let slot = Self::ensure_slot(ad_id, nft_id)?;
 
-            let reward = amount.saturating_sub(award);
+            // the slot is kept until its deadline, unspent budget goes back to the advertiser
+            let value = Self::refund(&slot)?;
 
-            T::Assets::transfer(slot.nft_id, &slot.budget_pot, &account, reward, false)?;
+            Self::deposit_event(Event::Withdrawn(nft_id, ad_id, value));
 
-            if let Some(fungible_id) = slot.fungible_id {
-                T::Assets::transfer(fungible_id, &slot.budget_pot, &account, fungibles, false)?;
-            }
+            Ok(())
+        }
 
-            <SlotOf<T>>::insert(nft_id, &slot);
+        #[pallet::weight(<T as Config>::WeightInfo::start_auction())]
+        pub fn start_auction(origin: OriginFor<T>, nft_id: NftOf<T>) -> DispatchResult {
+            let (did, _) = EnsureDid::<T>::ensure_origin(origin)?;
 
-            <Payout<T>>::insert(&ad_id, &visitor, height);
+            let nft_meta = Nft::<T>::meta(nft_id).ok_or(Error::<T>::NotMinted)?;
+            ensure!(nft_meta.minted, Error::<T>::NotMinted);
+            ensure!(nft_meta.owner == did, Error::<T>::NotOwned);
+            ensure!(!Nft::<T>::is_frozen(nft_id), Error::<T>::Frozen);
 
-            Self::deposit_event(Event::Paid(
-                ad_id,
-                slot.nft_id,
-                visitor,
-                reward,
-                referrer,
-                award,
-            ));
+            ensure!(
+                !<AuctionOf<T>>::contains_key(nft_id),
+                Error::<T>::AuctionExists
+            );
 
-            // 5. drawback if advertiser does not have enough fees
+            let height = <frame_system::Pallet<T>>::block_number();
 
-            if Self::slot_current_fraction_balance(&slot) < T::MinimumFeeBalance::get() {
-                Self::drawback(&slot)?;
-            }
+            let commit_end = height.saturating_add(T::AuctionCommitPeriod::get());
+            let reveal_end = commit_end.saturating_add(T::AuctionRevealPeriod::get());
 
-            Ok(())
-        }
-    }
+            <AuctionOf<T>>::insert(
+                nft_id,
+                types::Auction {
+                    commit_end,
+                    reveal_end,
+                    bids: 0,
+                },
+            );
+            <AuctionQueue<T>>::insert(reveal_end, nft_id, ());
 
-    #[pallet::genesis_config]
-    pub struct GenesisConfig {}
+            Self::deposit_event(Event::AuctionStarted(nft_id, commit_end, reveal_end));
 
-    #[cfg(feature = "std")]
-    impl Default for GenesisConfig {
-        fn default() -> Self {
-            Self {}
+            Ok(())
         }
-    }
-
-    #[pallet::genesis_build]
-    impl<T: Config> GenesisBuild<T> for GenesisConfig {
-        fn build(&self) {}
-    }
-}
 
-impl<T: Config> Pallet<T> {
-    fn begin_block(now: HeightOf<T>) -> Result<Weight, DispatchError> {
-        let mut read = 0;
-        let mut write = 0;
+        #[pallet::weight(<T as Config>::WeightInfo::commit_bid())]
+        pub fn commit_bid(
+            origin: OriginFor<T>,
+            ad_id: HashOf<T>,
+            nft_id: NftOf<T>,
+            sealed: HashOf<T>,
+            #[pallet::compact] deposit: BalanceOf<T>,
+        ) -> DispatchResult {
+            let (did, who) = T::CallOrigin::ensure_origin(origin)?;
 
-        let mut amount = 0;
+            let height = <frame_system::Pallet<T>>::block_number();
 
-        for (nft_id, ad_id, deadline) in <DeadlineOf<T>>::iter() {
-            read += 1;
+            let endtime = <EndtimeOf<T>>::get(&ad_id).ok_or(Error::<T>::NotExists)?;
+            ensure!(endtime > height, Error::<T>::Deadline);
 
-            if amount >= 100 {
-                break;
-            }
+            Self::ensure_owned(did, ad_id)?;
 
-            if deadline > now {
-                continue;
-            }
+            let auction = <AuctionOf<T>>::get(nft_id).ok_or(Error::<T>::AuctionNotExists)?;
+            ensure!(height < auction.commit_end, Error::<T>::NotCommitPeriod);
 
-            read += 1;
-            let slot = <SlotOf<T>>::get(nft_id);
-            if let Some(slot) = slot {
-                if slot.ad_id != ad_id {
-                    continue;
-                }
+            ensure!(
+                !<SealedBidOf<T>>::contains_key(nft_id, &ad_id),
+                Error::<T>::AlreadyCommitted
+            );
+            ensure!(
+                !<SlotOf<T>>::contains_key(nft_id, &ad_id),
+                Error::<T>::AlreadyInSlot
+            );
+            Self::ensure_slots_left(ad_id)?;
+            ensure!(
+                auction.bids < T::MaxSealedBids::get(),
+                Error::<T>::TooManyBids
+            );
 
-                read += 2;
-                write += 4;
-                Self::drawback(&slot)?;
+            let nft_meta = Nft::<T>::meta(nft_id).ok_or(Error::<T>::NotMinted)?;
+            ensure!(!Nft::<T>::is_frozen(nft_id), Error::<T>::Frozen);
 
-                amount += 1;
-            }
-        }
+            // lock deposit in the pot, so that the winner takes the slot right away,
+            // the deposit is public and bounds the sealed value,
+            // so bidders keeping their bid private deposit more than they bid
+            let pot = Self::generate_slot_pot(nft_id, ad_id);
+            T::Assets::transfer(nft_meta.token_asset_id, &who, &pot, deposit, false)?;
+
+            <BidsOf<T>>::insert(&who, (nft_id, ad_id), ());
+            <SealedBidOf<T>>::insert(
+                nft_id,
+                &ad_id,
+                types::SealedBid {
+                    bidder: who,
+                    sealed,
+                    deposit,
+                    revealed: None,
+                },
+            );
+
+            <AuctionOf<T>>::insert(
+                nft_id,
+                types::Auction {
+                    bids: auction.bids + 1,
+                    ..auction
+                },
+            );
+
+            Self::deposit_event(Event::BidCommitted(nft_id, ad_id, deposit));
+
+            Ok(())
+        }
+
+        #[pallet::weight(<T as Config>::WeightInfo::reveal_bid())]
+        pub fn reveal_bid(
+            origin: OriginFor<T>,
+            ad_id: HashOf<T>,
+            nft_id: NftOf<T>,
+            #[pallet::compact] value: BalanceOf<T>,
+            salt: Vec<u8>,
+        ) -> DispatchResult {
+            let (did, _) = T::CallOrigin::ensure_origin(origin)?;
+
+            Self::ensure_owned(did, ad_id)?;
+
+            let height = <frame_system::Pallet<T>>::block_number();
+
+            let auction = <AuctionOf<T>>::get(nft_id).ok_or(Error::<T>::AuctionNotExists)?;
+            ensure!(
+                height >= auction.commit_end && height < auction.reveal_end,
+                Error::<T>::NotRevealPeriod
+            );
+
+            let mut bid = <SealedBidOf<T>>::get(nft_id, &ad_id).ok_or(Error::<T>::NotExists)?;
+
+            let hash = <T as frame_system::Config>::Hashing::hash_of(&(ad_id, value, salt));
+            ensure!(
+                hash == bid.sealed && value <= bid.deposit,
+                Error::<T>::InvalidReveal
+            );
+
+            bid.revealed = Some(value);
+
+            <SealedBidOf<T>>::insert(nft_id, &ad_id, bid);
+
+            Self::deposit_event(Event::BidRevealed(nft_id, ad_id, value));
+
+            Ok(())
+        }
+
+        #[pallet::weight(<T as Config>::WeightInfo::pay(T::MaxReferralDepth::get()))]
+        pub fn pay(
+            origin: OriginFor<T>,
+            ad_id: HashOf<T>,
+            nft_id: NftOf<T>,
+            visitor: DidOf<T>,
+            scores: Vec<(Vec<u8>, i8)>,
+            referrer: Option<DidOf<T>>,
+        ) -> DispatchResult {
+            ensure!(!scores.is_empty(), Error::<T>::EmptyTags);
+
+            let (did, _who) = T::CallOrigin::ensure_origin(origin)?;
+
+            // 1. get slot, check current ad
+            let slot = Self::ensure_slot(ad_id, nft_id)?;
+
+            let ad_meta = Self::ensure_owned(did, ad_id)?;
+
+            // 2. score, influence and pay visitor
+            Self::pay_visitor(&ad_meta, &slot, visitor, scores, referrer)?;
+
+            // 3. drawback if advertiser does not have enough fees
+
+            if Self::slot_current_fraction_balance(&slot) < T::MinimumFeeBalance::get() {
+                Self::drawback(&slot)?;
+            }
+
+            Ok(())
+        }
+
+        #[pallet::weight(<T as Config>::WeightInfo::pay_batch(batch.len() as u32, T::MaxReferralDepth::get()))]
+        pub fn pay_batch(
+            origin: OriginFor<T>,
+            ad_id: HashOf<T>,
+            nft_id: NftOf<T>,
+            batch: Vec<(DidOf<T>, Vec<(Vec<u8>, i8)>, Option<DidOf<T>>)>,
+        ) -> DispatchResultWithPostInfo {
+            let (did, _who) = T::CallOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                batch.len() as u32 <= T::MaxPayBatch::get(),
+                Error::<T>::TooManyPayouts
+            );
+
+            // 1. get slot, check current ad, only once for the whole batch
+            let slot = Self::ensure_slot(ad_id, nft_id)?;
+
+            let ad_meta = Self::ensure_owned(did, ad_id)?;
+
+            // 2. pay visitors one by one, a failed entry does not revert the others
+
+            let mut paid = 0u32;
+            let mut processed = 0u32;
+
+            for (visitor, scores, referrer) in batch {
+                // stop once advertiser does not have enough fees
+                if Self::slot_current_fraction_balance(&slot) < T::MinimumFeeBalance::get() {
+                    break;
+                }
+
+                processed += 1;
+
+                match Self::pay_visitor(&ad_meta, &slot, visitor, scores, referrer) {
+                    Ok(()) => paid += 1,
+                    Err(e) => {
+                        Self::deposit_event(Event::PayFailed(ad_id, nft_id, visitor, e));
+                    }
+                }
+            }
+
+            // 3. drawback if advertiser does not have enough fees
+
+            if Self::slot_current_fraction_balance(&slot) < T::MinimumFeeBalance::get() {
+                Self::drawback(&slot)?;
+            }
+
+            Self::deposit_event(Event::BatchPaid(ad_id, nft_id, paid, processed));
+
+            Ok(Some(<T as Config>::WeightInfo::pay_batch(
+                processed,
+                T::MaxReferralDepth::get(),
+            ))
+            .into())
+        }
+    }
+
+    #[pallet::genesis_config]
+    pub struct GenesisConfig {}
+
+    #[cfg(feature = "std")]
+    impl Default for GenesisConfig {
+        fn default() -> Self {
+            Self {}
+        }
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> GenesisBuild<T> for GenesisConfig {
+        fn build(&self) {}
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    fn begin_block(now: HeightOf<T>) -> Result<Weight, DispatchError> {
+        let limit = T::MaxExpiriesPerBlock::get();
 
         let mut amount = 0;
+        let mut visited = 0;
+        let mut weight: Weight = 0;
+
+        let purge_limit = T::MaxPurgesPerBlock::get();
+
+        // payouts of ended advertisements left over by earlier blocks
+        let mut purged = 0;
+        let ads: Vec<HashOf<T>> = <PurgeQueue<T>>::iter_keys()
+            .take(purge_limit as usize)
+            .collect();
+        let mut purges = ads.len();
+        for ad_id in ads {
+            purged += Self::purge(ad_id, purge_limit - purged);
+        }
+
+        let mut cursor = <SweepCursor<T>>::get();
+        let mut idle = 0;
+
+        // sweep from the earliest unswept block, so that no expiry starves,
+        // slots of a block are swept before advertisements ending in the same block,
+        // blocks with nothing to sweep count against the limit as well
+        while cursor <= now && amount + idle < limit {
+            visited += 1;
 
-        for (ad_id, endtime) in <EndtimeOf<T>>::iter() {
-            read += 1;
+            let swept = amount;
 
-            if amount >= 100 {
+            // failed entries are retried in the following block
+            let next = cursor.saturating_add(1u32.into());
+
+            let auctions: Vec<NftOf<T>> = <AuctionQueue<T>>::iter_key_prefix(cursor)
+                .take((limit - amount - idle) as usize)
+                .collect();
+
+            for nft_id in auctions {
+                <AuctionQueue<T>>::remove(cursor, nft_id);
+
+                amount += 1;
+
+                match Self::close_auction(nft_id) {
+                    Ok(bids) => {
+                        weight.saturating_accrue(<T as Config>::WeightInfo::close_auction(bids));
+                    }
+                    Err(e) => {
+                        sp_runtime::print(e);
+                        <AuctionQueue<T>>::insert(next, nft_id, ());
+                    }
+                }
+            }
+
+            if amount + idle >= limit {
                 break;
             }
 
-            if endtime > now {
-                continue;
+            let slots: Vec<(NftOf<T>, HashOf<T>)> = <DeadlineQueue<T>>::iter_key_prefix(cursor)
+                .take((limit - amount - idle) as usize)
+                .collect();
+
+            for (nft_id, ad_id) in slots {
+                <DeadlineQueue<T>>::remove(cursor, (nft_id, ad_id));
+
+                amount += 1;
+
+                // outdated by an extension or drawback
+                match <DeadlineOf<T>>::get(nft_id, &ad_id) {
+                    Some(deadline) if deadline <= cursor => {}
+                    _ => continue,
+                }
+
+                let res = match <SlotOf<T>>::get(nft_id, &ad_id) {
+                    Some(slot) => Self::drawback(&slot).map(|_| ()),
+                    None => {
+                        <DeadlineOf<T>>::remove(nft_id, &ad_id);
+                        Self::remove_slot(nft_id, ad_id);
+                        Ok(())
+                    }
+                };
+
+                if let Err(e) = res {
+                    sp_runtime::print(e);
+                    <DeadlineQueue<T>>::insert(next, (nft_id, ad_id), ());
+                }
             }
 
-            write += 1;
-            <EndtimeOf<T>>::remove(ad_id);
+            if amount + idle >= limit {
+                break;
+            }
+
+            let ads: Vec<HashOf<T>> = <EndtimeQueue<T>>::iter_key_prefix(cursor)
+                .take((limit - amount - idle) as usize)
+                .collect();
+
+            for ad_id in ads {
+                <EndtimeQueue<T>>::remove(cursor, ad_id);
 
-            amount += 1;
+                amount += 1;
+
+                // outdated by an extension or cancellation
+                match <EndtimeOf<T>>::get(&ad_id) {
+                    Some(endtime) if endtime <= cursor => {}
+                    _ => continue,
+                }
+
+                purges += 1;
+
+                match Self::destroy(ad_id, purge_limit - purged) {
+                    Ok(removed) => purged += removed,
+                    Err(e) => {
+                        sp_runtime::print(e);
+                        <EndtimeQueue<T>>::insert(next, ad_id, ());
+                    }
+                }
+            }
+
+            if amount == swept {
+                idle += 1;
+            } else if amount + idle >= limit {
+                break;
+            }
+
+            cursor = next;
         }
 
-        Ok(T::DbWeight::get().reads_writes(read as Weight, write as Weight))
+        <SweepCursor<T>>::put(cursor);
+
+        Ok(<T as Config>::WeightInfo::on_initialize(amount)
+            .saturating_add(weight)
+            .saturating_add(T::DbWeight::get().reads_writes(3 * visited as Weight, 1))
+            .saturating_add(T::DbWeight::get().reads_writes(2 * purges as Weight, purges as Weight))
+            .saturating_add(T::DbWeight::get().writes(purged as Weight)))
     }
 
-    fn drawback(slot: &SlotMetaOf<T>) -> Result<(), DispatchError> {
+    fn drawback(slot: &SlotMetaOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+        let locking_fractions = Self::refund(slot)?;
+
+        Self::remove_slot(slot.nft_id, slot.ad_id);
+
+        Self::remove_deadline(slot.nft_id, slot.ad_id);
+
+        Self::deposit_event(Event::End(slot.nft_id, slot.ad_id, locking_fractions));
+
+        Ok(locking_fractions)
+    }
+
+    fn refund(slot: &SlotMetaOf<T>) -> Result<BalanceOf<T>, DispatchError> {
         let meta = <Metadata<T>>::get(slot.ad_id).ok_or(Error::<T>::NotExists)?;
 
         let owner_account =
@@ -719,16 +1219,223 @@ impl<T: Config> Pallet<T> {
             locking_fractions,
             false,
         )?;
+        Nft::<T>::note_holder(slot.nft_id, &owner_account);
 
-        <SlotOf<T>>::remove(slot.nft_id);
+        Ok(locking_fractions)
+    }
 
-        <DeadlineOf<T>>::remove(slot.nft_id, slot.ad_id);
+    fn ensure_vacancy(nft_id: NftOf<T>, fraction_value: BalanceOf<T>) -> DispatchResult {
+        let slots: Vec<SlotMetaOf<T>> = <SlotOf<T>>::iter_prefix_values(nft_id).collect();
 
-        Self::deposit_event(Event::End(slot.nft_id, slot.ad_id, locking_fractions));
+        if (slots.len() as u32) < T::MaxSlotsPerNft::get() {
+            return Ok(());
+        }
+
+        let lowest = slots
+            .into_iter()
+            .map(|slot| (Self::slot_current_fraction_balance(&slot), slot))
+            .min_by(|(a, _), (b, _)| a.cmp(b));
+
+        if let Some((locked_fractions, slot)) = lowest {
+            let increment = T::OutbidIncrement::get().mul_floor(locked_fractions);
+
+            ensure!(
+                fraction_value > locked_fractions.saturating_add(increment),
+                Error::<T>::Underbid
+            );
+
+            Self::drawback(&slot)?;
+        }
+
+        Ok(())
+    }
+
+    fn occupy(
+        ad_id: HashOf<T>,
+        nft_id: NftOf<T>,
+        fraction_id: AssetsOf<T>,
+        pot: AccountOf<T>,
+        fungible_id: Option<AssetsOf<T>>,
+        created: HeightOf<T>,
+        endtime: HeightOf<T>,
+    ) -> DispatchResult {
+        <SlotsOf<T>>::try_mutate(&ad_id, |slots| slots.try_push(nft_id))
+            .map_err(|_| Error::<T>::TooManySlots)?;
+
+        let lifetime = T::SlotLifetime::get();
+        let slotlife = created.saturating_add(lifetime);
+        let deadline = if slotlife > endtime {
+            endtime
+        } else {
+            slotlife
+        };
+
+        let slot = types::Slot {
+            ad_id,
+            nft_id,
+            fraction_id,
+            budget_pot: pot,
+            fungible_id,
+            created,
+        };
+
+        <SlotOf<T>>::insert(nft_id, &ad_id, &slot);
+        Self::set_deadline(nft_id, ad_id, deadline);
 
         Ok(())
     }
 
+    fn ensure_slots_left(ad_id: HashOf<T>) -> DispatchResult {
+        let slots = <SlotsOf<T>>::decode_len(&ad_id).unwrap_or_default() as u32;
+        ensure!(slots < T::MaxSlotsPerAd::get(), Error::<T>::TooManySlots);
+
+        Ok(())
+    }
+
+    fn close_auction(nft_id: NftOf<T>) -> Result<u32, DispatchError> {
+        let nft_meta = Nft::<T>::meta(nft_id).ok_or(Error::<T>::NotMinted)?;
+
+        <AuctionOf<T>>::remove(nft_id);
+
+        let bids: Vec<(HashOf<T>, SealedBidMetaOf<T>)> =
+            <SealedBidOf<T>>::drain_prefix(nft_id).collect();
+
+        for (ad_id, bid) in &bids {
+            <BidsOf<T>>::remove(&bid.bidder, (nft_id, *ad_id));
+        }
+
+        // highest revealed bid wins, the earlier one on a tie,
+        // the next highest takes over if the winner can no longer occupy the slot
+        let mut revealed: Vec<(HashOf<T>, BalanceOf<T>)> = bids
+            .iter()
+            .filter_map(|(ad_id, bid)| bid.revealed.map(|value| (*ad_id, value)))
+            .collect();
+        revealed.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        let winner = revealed.into_iter().find(|(ad_id, value)| {
+            Self::settle_auction(nft_id, *ad_id, *value, nft_meta.token_asset_id).is_ok()
+        });
+
+        // refund losing bids, and the deposit above the winning value
+        for (ad_id, bid) in &bids {
+            let refund = match winner {
+                Some((winner, value)) if winner == *ad_id => bid.deposit.saturating_sub(value),
+                _ => bid.deposit,
+            };
+
+            if refund.is_zero() {
+                continue;
+            }
+
+            let pot = Self::generate_slot_pot(nft_id, *ad_id);
+            let res =
+                T::Assets::transfer(nft_meta.token_asset_id, &pot, &bid.bidder, refund, false);
+
+            if let Err(e) = res {
+                sp_runtime::print(e);
+            }
+        }
+
+        let (winner, value) = match winner {
+            Some((ad_id, value)) => (Some(ad_id), value),
+            None => (None, Zero::zero()),
+        };
+
+        Self::deposit_event(Event::AuctionClosed(nft_id, winner, value));
+
+        Ok(bids.len() as u32)
+    }
+
+    #[transactional]
+    fn settle_auction(
+        nft_id: NftOf<T>,
+        ad_id: HashOf<T>,
+        value: BalanceOf<T>,
+        fraction_id: AssetsOf<T>,
+    ) -> DispatchResult {
+        let height = <frame_system::Pallet<T>>::block_number();
+
+        let endtime = <EndtimeOf<T>>::get(&ad_id).ok_or(Error::<T>::NotExists)?;
+        ensure!(endtime > height, Error::<T>::Deadline);
+
+        Self::ensure_vacancy(nft_id, value)?;
+
+        let pot = Self::generate_slot_pot(nft_id, ad_id);
+
+        Self::occupy(ad_id, nft_id, fraction_id, pot, None, height, endtime)?;
+
+        Self::deposit_event(Event::Bid(nft_id, ad_id, value));
+
+        Ok(())
+    }
+
+    fn drawback_all(ad_id: HashOf<T>) -> Result<u32, DispatchError> {
+        let mut amount = 0;
+
+        for nft_id in Self::slots_of(ad_id) {
+            match <SlotOf<T>>::get(nft_id, &ad_id) {
+                Some(slot) => {
+                    Self::drawback(&slot)?;
+
+                    amount += 1;
+                }
+                None => {
+                    Self::remove_deadline(nft_id, ad_id);
+                    Self::remove_slot(nft_id, ad_id);
+                }
+            }
+        }
+
+        Ok(amount)
+    }
+
+    fn destroy(ad_id: HashOf<T>, purge_limit: u32) -> Result<u32, DispatchError> {
+        // budgets locked in slots would be lost with the metadata
+        ensure!(
+            !<SlotsOf<T>>::contains_key(&ad_id),
+            Error::<T>::SlotsRemaining
+        );
+
+        T::Tags::clr_tag(&ad_id)?;
+
+        Self::remove_endtime(ad_id);
+
+        let purged = Self::purge(ad_id, purge_limit);
+
+        if let Some(meta) = <Metadata<T>>::take(&ad_id) {
+            <AdsOf<T>>::mutate_exists(&meta.creator, |maybe| {
+                if let Some(ads) = maybe {
+                    ads.retain(|id| *id != ad_id);
+
+                    if ads.is_empty() {
+                        *maybe = None;
+                    }
+                }
+            });
+        }
+
+        Ok(purged)
+    }
+
+    fn purge(ad_id: HashOf<T>, limit: u32) -> u32 {
+        let dids: Vec<DidOf<T>> = <Payout<T>>::iter_key_prefix(&ad_id)
+            .take(limit as usize)
+            .collect();
+
+        for did in &dids {
+            <Payout<T>>::remove(&ad_id, did);
+        }
+
+        // the rest is left to the following blocks
+        if <Payout<T>>::iter_key_prefix(&ad_id).next().is_some() {
+            <PurgeQueue<T>>::insert(&ad_id, ());
+        } else {
+            <PurgeQueue<T>>::remove(&ad_id);
+        }
+
+        dids.len() as u32
+    }
+
     fn ensure_owned(did: DidOf<T>, id: HashOf<T>) -> Result<MetaOf<T>, DispatchError> {
         let meta = <Metadata<T>>::get(&id).ok_or(Error::<T>::NotExists)?;
         ensure!(meta.creator == did, Error::<T>::NotOwned);
@@ -736,13 +1443,342 @@ impl<T: Config> Pallet<T> {
         Ok(meta)
     }
 
+    fn set_deadline(nft_id: NftOf<T>, ad_id: HashOf<T>, deadline: HeightOf<T>) {
+        if let Some(previous) = <DeadlineOf<T>>::get(nft_id, &ad_id) {
+            <DeadlineQueue<T>>::remove(previous, (nft_id, ad_id));
+        }
+
+        <DeadlineOf<T>>::insert(nft_id, &ad_id, deadline);
+        <DeadlineQueue<T>>::insert(deadline, (nft_id, ad_id), ());
+    }
+
+    fn remove_deadline(nft_id: NftOf<T>, ad_id: HashOf<T>) {
+        if let Some(deadline) = <DeadlineOf<T>>::take(nft_id, &ad_id) {
+            <DeadlineQueue<T>>::remove(deadline, (nft_id, ad_id));
+        }
+    }
+
+    fn set_endtime(ad_id: HashOf<T>, endtime: HeightOf<T>) {
+        if let Some(previous) = <EndtimeOf<T>>::get(&ad_id) {
+            <EndtimeQueue<T>>::remove(previous, ad_id);
+        }
+
+        <EndtimeOf<T>>::insert(&ad_id, endtime);
+        <EndtimeQueue<T>>::insert(endtime, ad_id, ());
+    }
+
+    fn remove_endtime(ad_id: HashOf<T>) {
+        if let Some(endtime) = <EndtimeOf<T>>::take(&ad_id) {
+            <EndtimeQueue<T>>::remove(endtime, ad_id);
+        }
+    }
+
+    fn remove_slot(nft_id: NftOf<T>, ad_id: HashOf<T>) {
+        <SlotOf<T>>::remove(nft_id, &ad_id);
+        <SlotsOf<T>>::mutate_exists(&ad_id, |maybe| {
+            if let Some(slots) = maybe {
+                slots.retain(|id| *id != nft_id);
+
+                if slots.is_empty() {
+                    *maybe = None;
+                }
+            }
+        });
+    }
+
+    fn slots_of(ad_id: HashOf<T>) -> Vec<NftOf<T>> {
+        <SlotsOf<T>>::get(ad_id).into_inner()
+    }
+
+    fn ensure_slot(ad_id: HashOf<T>, nft_id: NftOf<T>) -> Result<SlotMetaOf<T>, DispatchError> {
+        let height = <frame_system::Pallet<T>>::block_number();
+
+        let endtime = <EndtimeOf<T>>::get(&ad_id).ok_or(Error::<T>::NotExists)?;
+        ensure!(endtime > height, Error::<T>::Deadline);
+
+        let nft_meta = Nft::<T>::meta(nft_id).ok_or(Error::<T>::NotMinted)?;
+        ensure!(nft_meta.minted, Error::<T>::NotMinted);
+
+        let deadline = <DeadlineOf<T>>::get(nft_id, &ad_id).ok_or(Error::<T>::NotExists)?;
+        ensure!(deadline > height, Error::<T>::Deadline);
+
+        let slot = <SlotOf<T>>::get(nft_id, &ad_id).ok_or(Error::<T>::NotExists)?;
+
+        Ok(slot)
+    }
+
+    fn ensure_payable(
+        ad_id: HashOf<T>,
+        nft_id: NftOf<T>,
+        visitor: &DidOf<T>,
+    ) -> Result<SlotMetaOf<T>, DispatchError> {
+        let slot = Self::ensure_slot(ad_id, nft_id)?;
+
+        let ad_meta = <Metadata<T>>::get(&ad_id).ok_or(Error::<T>::NotExists)?;
+
+        Self::ensure_frequency(&ad_meta, visitor)?;
+        Self::ensure_targeted(&ad_meta, visitor)?;
+
+        Ok(slot)
+    }
+
+    fn ensure_targeted(ad_meta: &MetaOf<T>, visitor: &DidOf<T>) -> DispatchResult {
+        let targeting = &ad_meta.targeting;
+
+        for (tag, min) in &targeting.min_scores {
+            ensure!(
+                T::Tags::get_score(visitor, tag) >= *min,
+                Error::<T>::NotTargeted
+            );
+        }
+
+        for tag in &targeting.excluded {
+            ensure!(
+                T::Tags::get_score(visitor, tag) >= 0,
+                Error::<T>::NotTargeted
+            );
+        }
+
+        for network in &targeting.networks {
+            ensure!(
+                !T::Links::links(visitor, *network).is_empty(),
+                Error::<T>::NotTargeted
+            );
+        }
+
+        Ok(())
+    }
+
+    /// returns the payout count of the visitor after this payout
+    fn ensure_frequency(ad_meta: &MetaOf<T>, visitor: &DidOf<T>) -> Result<u32, DispatchError> {
+        let payout = match <Payout<T>>::get(&ad_meta.id, visitor) {
+            Some(payout) => payout,
+            None => return Ok(1),
+        };
+
+        ensure!(payout.count < ad_meta.max_payouts, Error::<T>::Paid);
+
+        let height = <frame_system::Pallet<T>>::block_number();
+        ensure!(
+            height >= payout.last.saturating_add(ad_meta.cooldown),
+            Error::<T>::InCooldown
+        );
+
+        Ok(payout.count.saturating_add(1))
+    }
+
+    #[transactional]
+    fn pay_visitor(
+        ad_meta: &MetaOf<T>,
+        slot: &SlotMetaOf<T>,
+        visitor: DidOf<T>,
+        scores: Vec<(Vec<u8>, i8)>,
+        referrer: Option<DidOf<T>>,
+    ) -> DispatchResult {
+        let ad_id = ad_meta.id;
+
+        ensure!(!scores.is_empty(), Error::<T>::EmptyTags);
+
+        let count = Self::ensure_frequency(ad_meta, &visitor)?;
+        Self::ensure_targeted(ad_meta, &visitor)?;
+
+        let height = <frame_system::Pallet<T>>::block_number();
+
+        // 1. scoring visitor
+        let referrers = Self::referrers_of(ad_meta, &visitor, referrer);
+
+        let (_, reward, awards, fungibles) =
+            Self::calculate_payout(ad_meta, slot, &visitor, &referrers)?;
+
+        let (reward, royalty) = Self::deduct_royalty(slot, reward);
+
+        // 2. influence visitor
+        for (tag, score) in scores {
+            ensure!(T::Tags::has_tag(&ad_id, &tag), Error::<T>::TagNotExists);
+            ensure!(score >= -5 && score <= 5, Error::<T>::ScoreOutOfRange);
+
+            T::Tags::influence(&visitor, &tag, score as i32)?;
+        }
+
+        // 3. payout assets
+
+        let account = Did::<T>::lookup_did(visitor).ok_or(parami_did::Error::<T>::DidNotExists)?;
+
+        for (referrer, award) in &awards {
+            let referrer =
+                Did::<T>::lookup_did(*referrer).ok_or(parami_did::Error::<T>::DidNotExists)?;
+
+            T::Assets::transfer(slot.nft_id, &slot.budget_pot, &referrer, *award, false)?;
+            Nft::<T>::note_holder(slot.nft_id, &referrer);
+        }
+
+        T::Assets::transfer(slot.nft_id, &slot.budget_pot, &account, reward, false)?;
+        Nft::<T>::note_holder(slot.nft_id, &account);
+
+        if let Some((owner, royalty)) = royalty {
+            T::Assets::transfer(slot.nft_id, &slot.budget_pot, &owner, royalty, false)?;
+            Nft::<T>::note_holder(slot.nft_id, &owner);
+
+            Self::deposit_event(Event::RoyaltyPaid(ad_id, slot.nft_id, owner, royalty));
+        }
+
+        if let Some(fungible_id) = slot.fungible_id {
+            T::Assets::transfer(fungible_id, &slot.budget_pot, &account, fungibles, false)?;
+        }
+
+        <Payout<T>>::insert(
+            &ad_id,
+            &visitor,
+            types::Payout {
+                last: height,
+                count,
+            },
+        );
+
+        Self::deposit_event(Event::Paid(
+            ad_id,
+            slot.nft_id,
+            visitor,
+            reward,
+            awards,
+            count,
+        ));
+
+        Ok(())
+    }
+
+    /// the given referrer, or the inviter chain of the visitor
+    /// if the advertisement has a referral schedule
+    fn referrers_of(
+        ad_meta: &MetaOf<T>,
+        visitor: &DidOf<T>,
+        referrer: Option<DidOf<T>>,
+    ) -> Vec<DidOf<T>> {
+        if ad_meta.referral.is_empty() {
+            return referrer.into_iter().collect();
+        }
+
+        let mut referrers: Vec<DidOf<T>> = Vec::new();
+        let mut current = *visitor;
+
+        while referrers.len() < ad_meta.referral.len() {
+            match Did::<T>::referrer_of(current) {
+                Some(inviter) if inviter != *visitor && !referrers.contains(&inviter) => {
+                    referrers.push(inviter);
+                    current = inviter;
+                }
+                _ => break,
+            }
+        }
+
+        referrers
+    }
+
     fn slot_current_fraction_balance(slot: &SlotMetaOf<T>) -> BalanceOf<T> {
         T::Assets::balance(slot.fraction_id, &slot.budget_pot)
     }
 
-    fn generate_slot_pot(nft_id: NftOf<T>) -> AccountOf<T> {
-        let nft_raw = <NftOf<T>>::encode(&nft_id);
-        let hash = <T as frame_system::Config>::Hashing::hash(&nft_raw);
+    fn generate_slot_pot(nft_id: NftOf<T>, ad_id: HashOf<T>) -> AccountOf<T> {
+        let raw = (nft_id, ad_id).encode();
+        let hash = <T as frame_system::Config>::Hashing::hash(&raw);
         <T as Config>::PalletId::get().into_sub_account_truncating(hash)
     }
+
+    pub fn slots_current(nft_id: NftOf<T>) -> Vec<(HashOf<T>, HeightOf<T>)> {
+        <SlotOf<T>>::iter_key_prefix(nft_id)
+            .filter_map(|ad_id| {
+                <DeadlineOf<T>>::get(nft_id, &ad_id).map(|deadline| (ad_id, deadline))
+            })
+            .collect()
+    }
+
+    pub fn slot_budget(
+        nft_id: NftOf<T>,
+        ad_id: HashOf<T>,
+    ) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+        let slot = <SlotOf<T>>::get(nft_id, &ad_id).ok_or(Error::<T>::SlotNotExists)?;
+
+        let fractions = Self::slot_current_fraction_balance(&slot);
+        let fungibles = match slot.fungible_id {
+            Some(fungible_id) => T::Assets::balance(fungible_id, &slot.budget_pot),
+            None => Zero::zero(),
+        };
+
+        Ok((fractions, fungibles))
+    }
+
+    pub fn is_paid(ad_id: HashOf<T>, did: DidOf<T>) -> bool {
+        <Payout<T>>::contains_key(&ad_id, &did)
+    }
+
+    pub fn dryly_pay(
+        ad_id: HashOf<T>,
+        nft_id: NftOf<T>,
+        visitor: DidOf<T>,
+        referrer: Option<DidOf<T>>,
+    ) -> Result<(u32, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>), DispatchError> {
+        let slot = Self::ensure_payable(ad_id, nft_id, &visitor)?;
+
+        let ad_meta = <Metadata<T>>::get(&ad_id).ok_or(Error::<T>::NotExists)?;
+
+        Did::<T>::lookup_did(visitor).ok_or(parami_did::Error::<T>::DidNotExists)?;
+        if let Some(referrer) = referrer {
+            Did::<T>::lookup_did(referrer).ok_or(parami_did::Error::<T>::DidNotExists)?;
+        }
+
+        let referrers = Self::referrers_of(&ad_meta, &visitor, referrer);
+
+        let (scoring, reward, awards, fungibles) =
+            Self::calculate_payout(&ad_meta, &slot, &visitor, &referrers)?;
+
+        let (reward, _) = Self::deduct_royalty(&slot, reward);
+
+        let award = awards
+            .into_iter()
+            .fold(Zero::zero(), |total: BalanceOf<T>, (_, award)| {
+                total.saturating_add(award)
+            });
+
+        Ok((scoring, reward, award, fungibles))
+    }
+}
+
+impl<T: Config> AccountMigration<AccountOf<T>> for Pallet<T> {
+    fn migrate(
+        src: &AccountOf<T>,
+        dest: &AccountOf<T>,
+        items: u32,
+    ) -> Result<Vec<Migrated>, DispatchError> {
+        let bids: Vec<(NftOf<T>, HashOf<T>)> = <BidsOf<T>>::iter_key_prefix(src)
+            .take((items as usize).saturating_add(1))
+            .collect();
+        ensure!(bids.len() as u32 <= items, DispatchError::Exhausted);
+
+        let mut migrated = Vec::new();
+
+        for (nft_id, ad_id) in bids {
+            <BidsOf<T>>::remove(src, (nft_id, ad_id));
+
+            let mut bid = match <SealedBidOf<T>>::get(nft_id, &ad_id) {
+                Some(bid) => bid,
+                None => continue,
+            };
+
+            migrated.push(Migrated::SealedBid(
+                nft_id.saturated_into(),
+                bid.deposit.saturated_into(),
+            ));
+
+            bid.bidder = dest.clone();
+
+            <SealedBidOf<T>>::insert(nft_id, ad_id, bid);
+            <BidsOf<T>>::insert(dest, (nft_id, ad_id), ());
+        }
+
+        Ok(migrated)
+    }
+
+    fn migrate_weight(items: u32) -> Weight {
+        <T as Config>::WeightInfo::migrate_account(items)
+    }
 }
@@ -1,13 +1,447 @@
 use crate::StorageVersion;
 use crate::{Config, Pallet};
-use frame_support::weights::Weight;
+use frame_support::{traits::Get, weights::Weight};
+use sp_runtime::traits::Saturating;
 
 #[cfg(feature = "try-runtime")]
-use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+use frame_support::{ensure, traits::OnRuntimeUpgradeHelpersExt};
 
 pub fn migrate<T: Config>() -> Weight {
-    let _version = StorageVersion::get::<Pallet<T>>();
-    let weight: Weight = 0;
+    let version = StorageVersion::get::<Pallet<T>>();
+    let mut weight: Weight = 0;
+
+    if version < 1 {
+        weight.saturating_accrue(v1::migrate::<T>());
+        StorageVersion::new(1).put::<Pallet<T>>();
+    }
+
+    if version < 2 {
+        weight.saturating_accrue(v2::migrate::<T>());
+        StorageVersion::new(2).put::<Pallet<T>>();
+    }
+
+    if version < 3 {
+        weight.saturating_accrue(v3::migrate::<T>());
+        StorageVersion::new(3).put::<Pallet<T>>();
+    }
+
+    if version < 4 {
+        weight.saturating_accrue(v4::migrate::<T>());
+        StorageVersion::new(4).put::<Pallet<T>>();
+    }
+
+    if version < 5 {
+        weight.saturating_accrue(v5::migrate::<T>());
+        StorageVersion::new(5).put::<Pallet<T>>();
+    }
+
+    if version < 6 {
+        weight.saturating_accrue(v6::migrate::<T>());
+        StorageVersion::new(6).put::<Pallet<T>>();
+    }
+
+    if version < 7 {
+        weight.saturating_accrue(v7::migrate::<T>());
+        StorageVersion::new(7).put::<Pallet<T>>();
+    }
 
     weight
 }
+
+#[cfg(feature = "try-runtime")]
+pub fn pre_upgrade<T: Config>() -> Result<(), &'static str> {
+    use crate::{HashOf, SlotOf};
+    use sp_std::collections::btree_map::BTreeMap;
+
+    let version = StorageVersion::get::<Pallet<T>>();
+
+    let slots = if version < 2 {
+        v2::old_slots::<T>()
+    } else {
+        <SlotOf<T>>::iter_values().collect()
+    };
+
+    // slots over the limit of an advertisement are refunded when indexed
+    let mut expected = slots.len() as u32;
+    if version < 6 {
+        let mut counts: BTreeMap<HashOf<T>, u32> = BTreeMap::new();
+        for slot in slots {
+            *counts.entry(slot.ad_id).or_default() += 1;
+        }
+
+        let max = T::MaxSlotsPerAd::get();
+        for count in counts.into_values() {
+            expected -= count.saturating_sub(max);
+        }
+    }
+
+    <Pallet<T> as OnRuntimeUpgradeHelpersExt>::set_temp_storage(expected, "slots");
+
+    Ok(())
+}
+
+#[cfg(feature = "try-runtime")]
+pub fn post_upgrade<T: Config>() -> Result<(), &'static str> {
+    use crate::{BidsOf, SealedBidOf, SlotOf, SlotsOf};
+
+    let expected: u32 = <Pallet<T> as OnRuntimeUpgradeHelpersExt>::get_temp_storage("slots")
+        .ok_or("slots not counted before upgrade")?;
+
+    let mut slots = 0u32;
+    for (nft_id, ad_id, slot) in <SlotOf<T>>::iter() {
+        ensure!(
+            slot.nft_id == nft_id && slot.ad_id == ad_id,
+            "slot not keyed by NFT and advertisement"
+        );
+        ensure!(
+            <SlotsOf<T>>::get(&ad_id).contains(&nft_id),
+            "slot not indexed by advertisement"
+        );
+
+        slots += 1;
+    }
+
+    let indexed: u32 = <SlotsOf<T>>::iter_values()
+        .map(|nfts| nfts.len() as u32)
+        .sum();
+
+    ensure!(indexed == slots, "index of slots out of sync");
+    ensure!(slots == expected, "slots lost in migration");
+
+    for (nft_id, ad_id, bid) in <SealedBidOf<T>>::iter() {
+        ensure!(
+            <BidsOf<T>>::contains_key(&bid.bidder, (nft_id, ad_id)),
+            "sealed bid not indexed by bidder"
+        );
+    }
+
+    Ok(())
+}
+
+mod v1 {
+    use super::*;
+    use crate::{DeadlineOf, DeadlineQueue, EndtimeOf, EndtimeQueue, SweepCursor};
+
+    pub fn migrate<T: Config>() -> Weight {
+        let now = <frame_system::Pallet<T>>::block_number();
+
+        let mut count: Weight = 0;
+
+        // overdue entries are swept right away
+        for (nft_id, ad_id, deadline) in <DeadlineOf<T>>::iter() {
+            let height = if deadline > now { deadline } else { now };
+            <DeadlineQueue<T>>::insert(height, (nft_id, ad_id), ());
+
+            count += 1;
+        }
+
+        for (ad_id, endtime) in <EndtimeOf<T>>::iter() {
+            let height = if endtime > now { endtime } else { now };
+            <EndtimeQueue<T>>::insert(height, ad_id, ());
+
+            count += 1;
+        }
+
+        <SweepCursor<T>>::put(now);
+
+        T::DbWeight::get().reads_writes(count + 1, count + 1)
+    }
+}
+
+mod v2 {
+    use super::*;
+    use crate::{NftOf, SlotMetaOf, SlotOf};
+    use frame_support::{
+        storage::migration::{remove_storage_prefix, storage_key_iter},
+        traits::PalletInfoAccess,
+        Twox64Concat,
+    };
+    use sp_std::vec::Vec;
+
+    pub fn migrate<T: Config>() -> Weight {
+        let module = <Pallet<T>>::name().as_bytes();
+
+        // slots were keyed by NFT only, re-key them by NFT and advertisement
+        let slots: Vec<(NftOf<T>, SlotMetaOf<T>)> =
+            storage_key_iter::<NftOf<T>, SlotMetaOf<T>, Twox64Concat>(module, b"SlotOf").collect();
+
+        remove_storage_prefix(module, b"SlotOf", b"");
+
+        let count = slots.len() as Weight;
+
+        for (nft_id, slot) in slots {
+            <SlotOf<T>>::insert(nft_id, slot.ad_id, slot);
+        }
+
+        T::DbWeight::get().reads_writes(count, count * 2)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    pub fn old_slots<T: Config>() -> Vec<SlotMetaOf<T>> {
+        let module = <Pallet<T>>::name().as_bytes();
+
+        storage_key_iter::<NftOf<T>, SlotMetaOf<T>, Twox64Concat>(module, b"SlotOf")
+            .map(|(_, slot)| slot)
+            .collect()
+    }
+}
+
+mod v3 {
+    use super::*;
+    use crate::{BalanceOf, DidOf, HashOf, HeightOf};
+    use codec::{Decode, Encode};
+    use frame_support::{
+        storage::migration::{put_storage_value, storage_iter},
+        traits::PalletInfoAccess,
+    };
+    use sp_runtime::RuntimeDebug;
+    use sp_std::vec::Vec;
+
+    #[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug)]
+    pub struct OldMetadata<B, D, H, N> {
+        pub id: H,
+        pub creator: D,
+        pub metadata: Vec<u8>,
+        pub reward_rate: u16,
+        pub created: N,
+        pub payout_base: B,
+        pub payout_min: B,
+        pub payout_max: B,
+    }
+
+    #[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug)]
+    pub struct NewMetadata<B, D, H, N> {
+        pub id: H,
+        pub creator: D,
+        pub metadata: Vec<u8>,
+        pub reward_rate: u16,
+        pub created: N,
+        pub payout_base: B,
+        pub payout_min: B,
+        pub payout_max: B,
+        pub cooldown: N,
+        pub max_payouts: u32,
+    }
+
+    #[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug)]
+    pub struct NewPayout<N> {
+        pub last: N,
+        pub count: u32,
+    }
+
+    type OldMetaOf<T> = OldMetadata<BalanceOf<T>, DidOf<T>, HashOf<T>, HeightOf<T>>;
+
+    pub fn migrate<T: Config>() -> Weight {
+        let module = <Pallet<T>>::name().as_bytes();
+
+        // advertisements used to pay a visitor only once, ever
+        let ads: Vec<(Vec<u8>, OldMetaOf<T>)> = storage_iter(module, b"Metadata").collect();
+
+        let ads_count = ads.len() as Weight;
+
+        for (key, old) in ads {
+            let new = NewMetadata {
+                id: old.id,
+                creator: old.creator,
+                metadata: old.metadata,
+                reward_rate: old.reward_rate,
+                created: old.created,
+                payout_base: old.payout_base,
+                payout_min: old.payout_min,
+                payout_max: old.payout_max,
+                cooldown: HeightOf::<T>::default(),
+                max_payouts: 1,
+            };
+
+            put_storage_value(module, b"Metadata", &key, new);
+        }
+
+        let payouts: Vec<(Vec<u8>, HeightOf<T>)> = storage_iter(module, b"Payout").collect();
+
+        let count = payouts.len() as Weight;
+
+        for (key, last) in payouts {
+            put_storage_value(module, b"Payout", &key, NewPayout { last, count: 1 });
+        }
+
+        T::DbWeight::get().reads_writes(count + ads_count, count + ads_count)
+    }
+}
+
+mod v4 {
+    use super::*;
+    use crate::{BalanceOf, DidOf, HashOf, HeightOf};
+    use codec::{Decode, Encode};
+    use frame_support::{
+        storage::migration::{put_storage_value, storage_iter},
+        traits::PalletInfoAccess,
+    };
+    use parami_traits::types::Network;
+    use sp_runtime::RuntimeDebug;
+    use sp_std::vec::Vec;
+
+    use super::v3::NewMetadata as OldMetadata;
+
+    #[derive(Clone, Decode, Default, Encode, Eq, PartialEq, RuntimeDebug)]
+    pub struct Targeting {
+        pub min_scores: Vec<(Vec<u8>, i32)>,
+        pub excluded: Vec<Vec<u8>>,
+        pub networks: Vec<Network>,
+    }
+
+    #[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug)]
+    pub struct NewMetadata<B, D, H, N> {
+        pub id: H,
+        pub creator: D,
+        pub metadata: Vec<u8>,
+        pub reward_rate: u16,
+        pub created: N,
+        pub payout_base: B,
+        pub payout_min: B,
+        pub payout_max: B,
+        pub cooldown: N,
+        pub max_payouts: u32,
+        pub targeting: Targeting,
+    }
+
+    type OldMetaOf<T> = OldMetadata<BalanceOf<T>, DidOf<T>, HashOf<T>, HeightOf<T>>;
+
+    pub fn migrate<T: Config>() -> Weight {
+        let module = <Pallet<T>>::name().as_bytes();
+
+        let ads: Vec<(Vec<u8>, OldMetaOf<T>)> = storage_iter(module, b"Metadata").collect();
+
+        let count = ads.len() as Weight;
+
+        for (key, old) in ads {
+            let new = NewMetadata {
+                id: old.id,
+                creator: old.creator,
+                metadata: old.metadata,
+                reward_rate: old.reward_rate,
+                created: old.created,
+                payout_base: old.payout_base,
+                payout_min: old.payout_min,
+                payout_max: old.payout_max,
+                cooldown: old.cooldown,
+                max_payouts: old.max_payouts,
+                targeting: Default::default(),
+            };
+
+            put_storage_value(module, b"Metadata", &key, new);
+        }
+
+        T::DbWeight::get().reads_writes(count, count)
+    }
+}
+
+mod v5 {
+    use super::*;
+    use crate::{BalanceOf, DidOf, HashOf, HeightOf};
+    use codec::{Decode, Encode};
+    use frame_support::{
+        storage::migration::{put_storage_value, storage_iter},
+        traits::PalletInfoAccess,
+    };
+    use sp_runtime::{Percent, RuntimeDebug};
+    use sp_std::vec::Vec;
+
+    use super::v4::{NewMetadata as OldMetadata, Targeting};
+
+    #[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug)]
+    pub struct NewMetadata<B, D, H, N> {
+        pub id: H,
+        pub creator: D,
+        pub metadata: Vec<u8>,
+        pub reward_rate: u16,
+        pub created: N,
+        pub payout_base: B,
+        pub payout_min: B,
+        pub payout_max: B,
+        pub cooldown: N,
+        pub max_payouts: u32,
+        pub targeting: Targeting,
+        pub referral: Vec<Percent>,
+    }
+
+    type OldMetaOf<T> = OldMetadata<BalanceOf<T>, DidOf<T>, HashOf<T>, HeightOf<T>>;
+
+    pub fn migrate<T: Config>() -> Weight {
+        let module = <Pallet<T>>::name().as_bytes();
+
+        let ads: Vec<(Vec<u8>, OldMetaOf<T>)> = storage_iter(module, b"Metadata").collect();
+
+        let count = ads.len() as Weight;
+
+        for (key, old) in ads {
+            let new = NewMetadata {
+                id: old.id,
+                creator: old.creator,
+                metadata: old.metadata,
+                reward_rate: old.reward_rate,
+                created: old.created,
+                payout_base: old.payout_base,
+                payout_min: old.payout_min,
+                payout_max: old.payout_max,
+                cooldown: old.cooldown,
+                max_payouts: old.max_payouts,
+                targeting: old.targeting,
+                referral: Default::default(),
+            };
+
+            put_storage_value(module, b"Metadata", &key, new);
+        }
+
+        T::DbWeight::get().reads_writes(count, count)
+    }
+}
+
+mod v6 {
+    use super::*;
+    use crate::{weights::WeightInfo, SlotMetaOf, SlotOf, SlotsOf};
+    use sp_std::vec::Vec;
+
+    pub fn migrate<T: Config>() -> Weight {
+        let slots: Vec<SlotMetaOf<T>> = <SlotOf<T>>::iter_values().collect();
+
+        let count = slots.len() as Weight;
+        let mut weight = T::DbWeight::get().reads_writes(count * 2, count);
+
+        // index the slots of each advertisement,
+        // budgets of those over the limit go back to the advertiser
+        for slot in slots {
+            let res = <SlotsOf<T>>::try_mutate(&slot.ad_id, |slots| slots.try_push(slot.nft_id));
+            if res.is_ok() {
+                continue;
+            }
+
+            // budget moved as in a withdrawal, then the slot and its deadline removed
+            weight.saturating_accrue(<T as Config>::WeightInfo::withdraw_budget());
+            weight.saturating_accrue(T::DbWeight::get().writes(4));
+
+            if let Err(e) = <Pallet<T>>::drawback(&slot) {
+                sp_runtime::print(e);
+            }
+        }
+
+        weight
+    }
+}
+
+mod v7 {
+    use super::*;
+    use crate::{BidsOf, SealedBidOf};
+
+    pub fn migrate<T: Config>() -> Weight {
+        let mut count: Weight = 0;
+
+        // index sealed bids by bidder
+        for (nft_id, ad_id, bid) in <SealedBidOf<T>>::iter() {
+            <BidsOf<T>>::insert(&bid.bidder, (nft_id, ad_id), ());
+
+            count += 1;
+        }
+
+        T::DbWeight::get().reads_writes(count, count)
+    }
+}
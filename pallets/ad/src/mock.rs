@@ -4,11 +4,14 @@ use frame_support::{
     parameter_types, traits::AsEnsureOriginWithArg, traits::GenesisBuild, PalletId,
 };
 use frame_system::{self as system, EnsureRoot, EnsureSigned};
+use parami_traits::types::Network;
 use sp_core::{sr25519, H160, H256};
 use sp_runtime::{
     testing::{Header, TestXt},
     traits::{BlakeTwo256, Keccak256},
+    Percent, Permill,
 };
+use sp_std::collections::btree_map::BTreeMap;
 
 type UncheckedExtrinsic = system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = system::mocking::MockBlock<Test>;
@@ -171,15 +174,25 @@ impl parami_did::Config for Test {
     type DecentralizedId = H160;
     type Hashing = Keccak256;
     type WeightInfo = ();
-    type Nfts = Nft;
+    type Migrations = (Nft, Swap, Ad);
 }
 
 parameter_types! {
     pub const InitialMintingDeposit: Balance = 1_000;
     pub const InitialMintingLockupPeriod: BlockNumber = 5;
+    pub const InitialMintingVestingCliff: BlockNumber = 0;
+    pub const InitialMintingVestingPeriod: BlockNumber = 0;
+    pub const MaxRoyalty: u32 = 1_000;
     pub const InitialMintingValueBase: Balance = 1_000_000;
     pub const PendingLifetime: BlockNumber = 5;
+    pub const ReverifyInterval: BlockNumber = 10;
+    pub const MaxReverifyPerBlock: u32 = 1;
+    pub const BinanceNetwork: Network = Network::Binance;
+    pub const EthereumNetwork: Network = Network::Ethereum;
+    pub const NearNetwork: Network = Network::Near;
+    pub const SolanaNetwork: Network = Network::Solana;
     pub const NftPalletId: PalletId = PalletId(*b"prm/nft ");
+    pub const TreasuryAccount: sr25519::Public = sr25519::Public([0xaa; 32]);
 }
 
 impl parami_nft::Config for Test {
@@ -188,13 +201,25 @@ impl parami_nft::Config for Test {
     type Assets = Assets;
     type InitialMintingDeposit = InitialMintingDeposit;
     type InitialMintingLockupPeriod = InitialMintingLockupPeriod;
+    type InitialMintingVestingCliff = InitialMintingVestingCliff;
+    type InitialMintingVestingPeriod = InitialMintingVestingPeriod;
     type InitialMintingValueBase = InitialMintingValueBase;
     type Links = ();
     type Nft = Uniques;
+    type MaxRoyalty = MaxRoyalty;
     type PalletId = NftPalletId;
     type PendingLifetime = PendingLifetime;
+    type ReverifyInterval = ReverifyInterval;
+    type MaxReverifyPerBlock = MaxReverifyPerBlock;
+    type Validators = (
+        parami_nft::Validator<BinanceNetwork, parami_nft::Evm<Test>>,
+        parami_nft::Validator<EthereumNetwork, parami_nft::Evm<Test>>,
+        parami_nft::Validator<NearNetwork, parami_nft::Nep171<Test>>,
+        parami_nft::Validator<SolanaNetwork, parami_nft::Metaplex<Test>>,
+    );
     type StringLimit = StringLimit;
     type Swaps = Swap;
+    type TreasuryAccount = TreasuryAccount;
     type WeightInfo = ();
     type UnsignedPriority = ();
 }
@@ -202,6 +227,11 @@ impl parami_nft::Config for Test {
 impl parami_ocw::Config for Test {}
 
 parameter_types! {
+    pub const SwapMaxCurveChanges: u32 = 8;
+    pub const SwapMaxFee: Permill = Permill::from_percent(10);
+    pub const SwapMaxProtocolShare: Permill = Permill::from_percent(50);
+    pub const SwapMaxObservations: u32 = 4;
+    pub const SwapObservationPeriod: BlockNumber = 10;
     pub const SwapPalletId: PalletId = PalletId(*b"prm/swap");
 }
 
@@ -211,7 +241,18 @@ impl parami_swap::Config for Test {
     type Assets = Assets;
     type Currency = Balances;
     type FarmingCurve = ();
+    type ExponentialDecayCurve = ();
+    type HalvingCurve = ();
+    type ForceOrigin = EnsureRoot<Self::AccountId>;
+    type MaxCurveChanges = SwapMaxCurveChanges;
+    type MaxFee = SwapMaxFee;
+    type MaxProtocolShare = SwapMaxProtocolShare;
+    type MaxObservations = SwapMaxObservations;
+    type ObservationPeriod = SwapObservationPeriod;
     type PalletId = SwapPalletId;
+    type Royalties = Nft;
+    type Holders = Nft;
+    type TreasuryAccount = TreasuryAccount;
     type WeightInfo = ();
 }
 
@@ -233,6 +274,16 @@ parameter_types! {
     pub const AdPalletId: PalletId = PalletId(*b"prm/ad  ");
     pub const AdvertiserMinimumFee: Balance = 1;
     pub const SlotLifetime: BlockNumber = 43200;
+    pub static AdMaxSlotsPerNft: u32 = 1;
+    pub static AdMaxSlotsPerAd: u32 = 5;
+    pub const AdOutbidIncrement: Percent = Percent::from_percent(20);
+    pub const AdAuctionCommitPeriod: BlockNumber = 10;
+    pub const AdAuctionRevealPeriod: BlockNumber = 10;
+    pub const AdMaxSealedBids: u32 = 3;
+    pub const AdMaxReferralDepth: u32 = 5;
+    pub const AdMaxPayBatch: u32 = 5;
+    pub const AdMaxExpiriesPerBlock: u32 = 5;
+    pub static AdMaxPurgesPerBlock: u32 = 5;
 }
 
 impl parami_ad::Config for Test {
@@ -240,12 +291,41 @@ impl parami_ad::Config for Test {
     type MinimumFeeBalance = AdvertiserMinimumFee;
     type PalletId = AdPalletId;
     type SlotLifetime = SlotLifetime;
+    type MaxSlotsPerNft = AdMaxSlotsPerNft;
+    type MaxSlotsPerAd = AdMaxSlotsPerAd;
+    type OutbidIncrement = AdOutbidIncrement;
+    type AuctionCommitPeriod = AdAuctionCommitPeriod;
+    type AuctionRevealPeriod = AdAuctionRevealPeriod;
+    type MaxSealedBids = AdMaxSealedBids;
+    type MaxReferralDepth = AdMaxReferralDepth;
+    type MaxPayBatch = AdMaxPayBatch;
+    type MaxExpiriesPerBlock = AdMaxExpiriesPerBlock;
+    type MaxPurgesPerBlock = AdMaxPurgesPerBlock;
     type Tags = Tag;
+    type Links = Linker;
     type CallOrigin = parami_did::EnsureDid<Self>;
     type ForceOrigin = EnsureRoot<Self::AccountId>;
     type WeightInfo = ();
 }
 
+pub struct Linker;
+impl parami_traits::Links<H160> for Linker {
+    fn all_links(did: &H160) -> BTreeMap<Network, Vec<Vec<u8>>> {
+        let mut links = BTreeMap::new();
+        if *did == DID_CHARLIE {
+            links.insert(
+                Network::Twitter,
+                vec![b"https://twitter.com/charlie".to_vec()],
+            );
+        }
+        links
+    }
+
+    fn links(did: &H160, network: Network) -> Vec<Vec<u8>> {
+        Self::all_links(did).remove(&network).unwrap_or_default()
+    }
+}
+
 pub fn new_test_ext() -> sp_io::TestExternalities {
     let mut t = system::GenesisConfig::default()
         .build_storage::<Test>()
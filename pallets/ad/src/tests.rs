@@ -1,8 +1,18 @@
-use crate::{mock::*, AdsOf, Config, DeadlineOf, EndtimeOf, Error, Metadata, SlotOf};
-use frame_support::{assert_noop, assert_ok, traits::Hooks};
-use parami_traits::Tags;
+use crate::{
+    migrations, mock::*, types, AdsOf, BidsOf, Config, DeadlineOf, DeadlineQueue, EndtimeOf,
+    EndtimeQueue, Error, Metadata, PurgeQueue, SealedBidOf, SlotOf, SlotsOf, SweepCursor,
+};
+use codec::Encode;
+use frame_support::{
+    assert_noop, assert_ok,
+    storage::migration::put_storage_value,
+    traits::{Hooks, PalletInfoAccess, StorageVersion},
+    StorageHasher, Twox64Concat,
+};
+use parami_traits::{types::Network, AccountMigration, Migrated, Tags};
+use sp_core::H256;
 use sp_runtime::traits::Hash;
-use sp_runtime::MultiAddress;
+use sp_runtime::{DispatchError, MultiAddress, Percent};
 use sp_std::collections::btree_map::BTreeMap;
 
 #[test]
@@ -29,7 +39,9 @@ fn should_create() {
             1,
             1u128,
             0,
-            10u128
+            10u128,
+            0,
+            1
         ));
 
         assert_eq!(<AdsOf<Test>>::get(&DID_ALICE).unwrap().len(), 1);
@@ -74,7 +86,9 @@ fn should_fail_when_min_greater_than_max() {
                 1,
                 1u128,
                 11u128,
-                10u128
+                10u128,
+                0,
+                1
             ),
             Error::<Test>::WrongPayoutSetting
         );
@@ -99,7 +113,9 @@ fn should_fail_when_tag_not_exists() {
                 1,
                 1u128,
                 0,
-                10u128
+                10u128,
+                0,
+                1
             ),
             Error::<Test>::TagNotExists
         );
@@ -117,7 +133,9 @@ fn should_update_reward_rate() {
             1,
             1u128,
             0,
-            10u128
+            10u128,
+            0,
+            1
         ));
 
         let ad = <Metadata<Test>>::iter_keys().next().unwrap();
@@ -144,7 +162,9 @@ fn should_fail_when_not_exists_or_not_owned() {
             1,
             1u128,
             0,
-            10u128
+            10u128,
+            0,
+            1
         ));
 
         let ad = <Metadata<Test>>::iter_keys().next().unwrap();
@@ -178,7 +198,9 @@ fn should_update_tags() {
             1,
             1u128,
             0,
-            10u128
+            10u128,
+            0,
+            1
         ));
 
         let ad = <Metadata<Test>>::iter_keys().next().unwrap();
@@ -192,10 +214,12 @@ fn should_update_tags() {
 #[test]
 fn should_generate_unique_slot_pot() {
     new_test_ext().execute_with(|| {
-        let pot1 = Ad::generate_slot_pot(0);
-        let pot2 = Ad::generate_slot_pot(1);
+        let pot1 = Ad::generate_slot_pot(0, Default::default());
+        let pot2 = Ad::generate_slot_pot(1, Default::default());
+        let pot3 = Ad::generate_slot_pot(0, [1u8; 32].into());
 
         assert_ne!(pot1, pot2);
+        assert_ne!(pot1, pot3);
     });
 }
 
@@ -217,14 +241,16 @@ fn should_bid() {
             endtime,
             1u128,
             0,
-            10u128
+            10u128,
+            0,
+            1
         ));
 
         let ad1 = <Metadata<Test>>::iter_keys().next().unwrap();
 
         // 2. bob bid for ad1
 
-        let slot = <SlotOf<Test>>::get(nft);
+        let slot = <SlotOf<Test>>::get(nft, &ad1);
         assert_eq!(slot, None);
 
         let bob_bid_fraction = 400;
@@ -242,7 +268,7 @@ fn should_bid() {
         assert_eq!(<EndtimeOf<Test>>::get(&ad1), Some(endtime));
         assert_eq!(<DeadlineOf<Test>>::get(nft, &ad1), Some(endtime));
 
-        let slot = <SlotOf<Test>>::get(nft).unwrap();
+        let slot = <SlotOf<Test>>::get(nft, &ad1).unwrap();
         assert_eq!(slot.ad_id, ad1);
 
         // 3. charlie bid for ad2
@@ -256,7 +282,9 @@ fn should_bid() {
             1,
             1u128,
             0,
-            10u128
+            10u128,
+            0,
+            1
         ));
 
         let ad2 = <Metadata<Test>>::iter_keys().next().unwrap();
@@ -294,7 +322,9 @@ fn should_bid() {
             CHARLIE_BALANCE - charlie_bid_fraction
         );
 
-        let slot = <SlotOf<Test>>::get(nft).unwrap();
+        assert_eq!(<SlotOf<Test>>::get(nft, &ad1), None);
+
+        let slot = <SlotOf<Test>>::get(nft, &ad2).unwrap();
         assert_eq!(slot.ad_id, ad2);
 
         let locked_fraction = Assets::balance(meta.token_asset_id, slot.budget_pot);
@@ -305,6 +335,141 @@ fn should_bid() {
         assert_eq!(<EndtimeOf<Test>>::get(&ad2), Some(1));
         assert_eq!(<DeadlineOf<Test>>::get(nft, &ad1), None);
         assert_eq!(<DeadlineOf<Test>>::get(nft, &ad2), Some(1));
+
+        assert_eq!(<SlotsOf<Test>>::get(&ad1).into_inner(), vec![]);
+        assert_eq!(<SlotsOf<Test>>::get(&ad2).into_inner(), vec![nft]);
+    });
+}
+
+#[test]
+fn should_fail_to_bid_over_slots_per_ad() {
+    new_test_ext().execute_with(|| {
+        AdMaxSlotsPerAd::set(0);
+
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            vec![],
+            [0u8; 64].into(),
+            1,
+            43200,
+            1u128,
+            0,
+            10u128,
+            0,
+            1
+        ));
+
+        let ad = <Metadata<Test>>::iter_keys().next().unwrap();
+
+        assert_noop!(
+            Ad::bid_with_fraction(Origin::signed(BOB), ad, nft, 400, None, None),
+            Error::<Test>::TooManySlots
+        );
+    });
+}
+
+#[test]
+fn should_bid_multiple_slots() {
+    new_test_ext().execute_with(|| {
+        AdMaxSlotsPerNft::set(2);
+
+        // 1. prepare
+
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+        let meta = Nft::meta(nft).unwrap();
+
+        let tags = vec![
+            vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8],
+            vec![5u8, 4u8, 3u8, 2u8, 1u8, 0u8],
+        ];
+
+        for (who, block) in [(BOB, 0), (CHARLIE, 0), (BOB, 1)] {
+            System::set_block_number(block);
+
+            assert_ok!(Ad::create(
+                Origin::signed(who),
+                tags.clone(),
+                [0u8; 64].into(),
+                1,
+                43200,
+                1u128,
+                0,
+                10u128,
+                0,
+                1
+            ));
+        }
+
+        let ad1 = <AdsOf<Test>>::get(&DID_BOB).unwrap()[0];
+        let ad2 = <AdsOf<Test>>::get(&DID_CHARLIE).unwrap()[0];
+        let ad3 = <AdsOf<Test>>::get(&DID_BOB).unwrap()[1];
+
+        // 2. fill both slots
+
+        assert_ok!(Ad::bid_with_fraction(
+            Origin::signed(BOB),
+            ad1,
+            nft,
+            100,
+            None,
+            None
+        ));
+        assert_ok!(Ad::bid_with_fraction(
+            Origin::signed(CHARLIE),
+            ad2,
+            nft,
+            200,
+            None,
+            None
+        ));
+
+        assert_noop!(
+            Ad::bid_with_fraction(Origin::signed(BOB), ad1, nft, 100, None, None),
+            Error::<Test>::AlreadyInSlot
+        );
+
+        assert_eq!(Ad::slots_current(nft).len(), 2);
+
+        // 3. outbid the lowest slot
+
+        assert_noop!(
+            Ad::bid_with_fraction(Origin::signed(BOB), ad3, nft, 120, None, None),
+            Error::<Test>::Underbid
+        );
+
+        assert_ok!(Ad::bid_with_fraction(
+            Origin::signed(BOB),
+            ad3,
+            nft,
+            121,
+            None,
+            None
+        ));
+
+        assert_eq!(<SlotOf<Test>>::get(nft, &ad1), None);
+        assert_ne!(<SlotOf<Test>>::get(nft, &ad2), None);
+        assert_ne!(<SlotOf<Test>>::get(nft, &ad3), None);
+
+        assert_eq!(Assets::balance(meta.token_asset_id, BOB), BOB_BALANCE - 121);
+
+        // 4. pay against any slot
+
+        for (who, ad, visitor) in [(CHARLIE, ad2, DID_TAGA5_TAGB2), (BOB, ad3, DID_CHARLIE)] {
+            assert_ok!(Ad::pay(
+                Origin::signed(who),
+                ad,
+                nft,
+                visitor,
+                vec![(vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8], 5)],
+                None
+            ));
+        }
+
+        assert_eq!(Assets::balance(meta.token_asset_id, &TAGA5_TAGB2), 3);
+        assert_eq!(Ad::slot_budget(nft, ad2), Ok((197, 0)));
+        assert_eq!(Ad::slot_budget(nft, ad3), Ok((119, 0)));
     });
 }
 
@@ -334,7 +499,9 @@ fn should_fail_to_add_budget_when_fungible_not_same_with_bid() {
             1,
             1u128,
             0,
-            10u128
+            10u128,
+            0,
+            1
         ));
 
         let nft = Nft::preferred(DID_ALICE).unwrap();
@@ -349,7 +516,7 @@ fn should_fail_to_add_budget_when_fungible_not_same_with_bid() {
             None,
             None
         ));
-        let slot = <SlotOf<Test>>::get(nft).unwrap();
+        let slot = <SlotOf<Test>>::get(nft, &ad).unwrap();
         assert_eq!(Ad::slot_current_fraction_balance(&slot), bob_bid_fraction);
 
         let new_budget = 250;
@@ -394,7 +561,9 @@ fn should_add_budget() {
             1,
             1u128,
             0,
-            10u128
+            10u128,
+            0,
+            1
         ));
 
         let nft = Nft::preferred(DID_ALICE).unwrap();
@@ -410,7 +579,7 @@ fn should_add_budget() {
             Some(fungible_id),
             Some(bob_bid_fungible)
         ));
-        let slot = <SlotOf<Test>>::get(nft).unwrap();
+        let slot = <SlotOf<Test>>::get(nft, &ad).unwrap();
         assert_eq!(Ad::slot_current_fraction_balance(&slot), bob_bid_fraction);
 
         let new_budget = 250;
@@ -457,7 +626,9 @@ fn should_drawback_when_ad_expired() {
             43200 * 2,
             1u128,
             0,
-            10u128
+            10u128,
+            0,
+            1
         ));
 
         let ad = <Metadata<Test>>::iter_keys().next().unwrap();
@@ -476,23 +647,28 @@ fn should_drawback_when_ad_expired() {
 
         // 2. step in
 
-        System::set_block_number(43200);
-
-        Ad::on_initialize(System::block_number());
+        run_to_block(43200);
 
         // ensure slot, remain
 
-        assert_eq!(<SlotOf<Test>>::get(nft), None);
+        assert_eq!(<SlotOf<Test>>::get(nft, &ad), None);
 
         // 3. step in
-        System::set_block_number(43200 * 2);
-
-        Ad::on_initialize(System::block_number());
+        run_to_block(43200 * 2);
 
         // ensure remain
         assert_eq!(Assets::balance(meta.token_asset_id, BOB), 500);
     });
 }
+fn run_to_block(n: u64) {
+    while System::block_number() < n {
+        let block = System::block_number() + 1;
+
+        System::set_block_number(block);
+        Ad::on_initialize(block);
+    }
+}
+
 macro_rules! prepare_pay {
     ($a:expr,$b:expr,$c: expr) => {
         _prepare_pay($a, $b, $c)
@@ -522,7 +698,9 @@ fn _prepare_pay(base: u128, min: u128, max: u128) -> (HashOf<Test>, NftOf<Test>)
         1,
         base,
         min,
-        max
+        max,
+        0,
+        1
     ));
 
     let ad = <Metadata<Test>>::iter_keys().next().unwrap();
@@ -569,127 +747,396 @@ fn should_pay() {
 }
 
 #[test]
-fn should_pay_3_for_taga5_tagb2() {
+fn should_pay_royalty() {
     new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
         // 1. prepare
-        let (ad, nft) = prepare_pay!();
-        let nft_meta = Nft::meta(nft).unwrap();
-        // 2 pay
+        let (ad, nft) = prepare_pay!(10u128, 0u128, 100u128);
+
+        assert_ok!(Nft::set_royalty(Origin::signed(ALICE), nft, 1_000));
+
+        assert_eq!(
+            Ad::dryly_pay(ad, nft, DID_CHARLIE, None).map(|(_, reward, _, _)| reward),
+            Ok(18)
+        );
+
+        // 2. pay
+
         assert_ok!(Ad::pay(
             Origin::signed(BOB),
             ad,
             nft,
-            DID_TAGA5_TAGB2,
+            DID_CHARLIE,
             vec![(vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8], 5)],
             None
         ));
 
-        assert_eq!(Assets::balance(nft_meta.token_asset_id, &TAGA5_TAGB2), 3);
+        let nft_meta = Nft::meta(nft).unwrap();
+        assert_eq!(Assets::balance(nft_meta.token_asset_id, &CHARLIE), 518);
+        assert_eq!(Assets::balance(nft_meta.token_asset_id, &ALICE), 10002);
+
+        System::assert_has_event(Event::Ad(crate::Event::RoyaltyPaid(ad, nft, ALICE, 2)));
     });
 }
 
 #[test]
-fn should_pay_0_when_all_tags_score_are_zero() {
+fn should_pay_with_frequency() {
     new_test_ext().execute_with(|| {
         // 1. prepare
         let (ad, nft) = prepare_pay!();
-        let nft_meta = Nft::meta(nft).unwrap();
-        // 2 pay
+
+        assert_noop!(
+            Ad::update_frequency(Origin::signed(BOB), ad, 10, 0),
+            Error::<Test>::WrongFrequencySetting
+        );
+        assert_noop!(
+            Ad::update_frequency(Origin::signed(CHARLIE), ad, 10, 2),
+            Error::<Test>::NotOwned
+        );
+
+        assert_ok!(Ad::update_frequency(Origin::signed(BOB), ad, 10, 2));
+        assert_ok!(Ad::extend(Origin::signed(BOB), ad, 100));
+
+        let meta = <Metadata<Test>>::get(&ad).unwrap();
+        assert_eq!(meta.cooldown, 10);
+        assert_eq!(meta.max_payouts, 2);
+
+        // 2. pay, then wait for the cooldown
+
+        let scores = vec![(vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8], 5)];
+
         assert_ok!(Ad::pay(
             Origin::signed(BOB),
             ad,
             nft,
-            DID_TAGA0_TAGB0,
-            vec![(vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8], 5)],
+            DID_CHARLIE,
+            scores.clone(),
             None
         ));
 
-        assert_eq!(Assets::balance(nft_meta.token_asset_id, &TAGA0_TAGB0), 0);
-    });
-}
+        assert_eq!(Ad::payout(&ad, &DID_CHARLIE).unwrap().count, 1);
+
+        assert_noop!(
+            Ad::pay(
+                Origin::signed(BOB),
+                ad,
+                nft,
+                DID_CHARLIE,
+                scores.clone(),
+                None
+            ),
+            Error::<Test>::InCooldown
+        );
+
+        System::set_block_number(10);
 
-#[test]
-fn should_pay_5_when_all_tags_score_are_zero_with_payout_min_is_5() {
-    new_test_ext().execute_with(|| {
-        // 1. prepare
-        let (ad, nft) = prepare_pay!(1u128, 5u128, 10u128);
-        let nft_meta = Nft::meta(nft).unwrap();
-        // 2 pay
         assert_ok!(Ad::pay(
             Origin::signed(BOB),
             ad,
             nft,
-            DID_TAGA0_TAGB0,
-            vec![(vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8], 5)],
+            DID_CHARLIE,
+            scores.clone(),
             None
         ));
 
-        assert_eq!(Assets::balance(nft_meta.token_asset_id, &TAGA0_TAGB0), 5);
+        let payout = Ad::payout(&ad, &DID_CHARLIE).unwrap();
+        assert_eq!(payout.last, 10);
+        assert_eq!(payout.count, 2);
+
+        // 3. capped
+
+        System::set_block_number(20);
+
+        assert_noop!(
+            Ad::pay(Origin::signed(BOB), ad, nft, DID_CHARLIE, scores, None),
+            Error::<Test>::Paid
+        );
     });
 }
+
 #[test]
-fn should_pay_10_when_all_tags_are_full_score() {
+fn should_pay_with_targeting() {
     new_test_ext().execute_with(|| {
         // 1. prepare
         let (ad, nft) = prepare_pay!();
-        let nft_meta = Nft::meta(nft).unwrap();
-        // 2 pay
+
+        let tag = vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8];
+        let scores = vec![(tag.clone(), 5)];
+
+        assert_noop!(
+            Ad::update_targeting(
+                Origin::signed(BOB),
+                ad,
+                Targeting {
+                    excluded: vec![vec![0u8; 6]],
+                    ..Default::default()
+                }
+            ),
+            Error::<Test>::TagNotExists
+        );
+
+        // 2. require a minimum score
+
+        assert_ok!(Ad::update_targeting(
+            Origin::signed(BOB),
+            ad,
+            Targeting {
+                min_scores: vec![(tag.clone(), 10)],
+                ..Default::default()
+            }
+        ));
+
+        assert_noop!(
+            Ad::pay(
+                Origin::signed(BOB),
+                ad,
+                nft,
+                DID_CHARLIE,
+                scores.clone(),
+                None
+            ),
+            Error::<Test>::NotTargeted
+        );
+        assert_noop!(
+            Ad::dryly_pay(ad, nft, DID_CHARLIE, None),
+            Error::<Test>::NotTargeted
+        );
+
         assert_ok!(Ad::pay(
             Origin::signed(BOB),
             ad,
             nft,
             DID_TAGA100_TAGB100,
-            vec![(vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8], 5)],
+            scores.clone(),
             None
         ));
 
-        assert_eq!(
-            Assets::balance(nft_meta.token_asset_id, &TAGA100_TAGB100),
-            10
+        // 3. require a linked network
+
+        assert_ok!(Ad::update_targeting(
+            Origin::signed(BOB),
+            ad,
+            Targeting {
+                networks: vec![Network::Twitter],
+                ..Default::default()
+            }
+        ));
+
+        assert_noop!(
+            Ad::pay(
+                Origin::signed(BOB),
+                ad,
+                nft,
+                DID_TAGA5_TAGB2,
+                scores.clone(),
+                None
+            ),
+            Error::<Test>::NotTargeted
         );
-    });
-}
-#[test]
-fn should_pay_10_when_all_tags_are_full_score_or_overflow() {
-    new_test_ext().execute_with(|| {
-        // 1. prepare
-        let (ad, nft) = prepare_pay!();
-        let nft_meta = Nft::meta(nft).unwrap();
-        // 2 pay
+
         assert_ok!(Ad::pay(
             Origin::signed(BOB),
             ad,
             nft,
-            DID_TAGA120_TAGB0,
-            vec![(vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8], 5)],
+            DID_CHARLIE,
+            scores,
             None
         ));
-
-        assert_eq!(Assets::balance(nft_meta.token_asset_id, &TAGA120_TAGB0), 10);
     });
 }
 
 #[test]
-fn should_pay_dual() {
+fn should_pay_referral_chain() {
     new_test_ext().execute_with(|| {
-        // 1. prepare
+        // 1. prepare, an inviter chain of CHARLIE <- DAVE <- EVE
+        let (ad, nft) = prepare_pay!(1u128, 10u128, 20u128);
+        let nft_meta = Nft::meta(nft).unwrap();
 
-        let nft = Nft::preferred(DID_ALICE).unwrap();
+        let dave = sp_core::sr25519::Public([10; 32]);
+        let eve = sp_core::sr25519::Public([11; 32]);
 
-        // create ad
+        assert_ok!(Did::register(Origin::signed(dave), Some(DID_CHARLIE)));
+        let did_dave = Did::did_of(&dave).unwrap();
 
-        assert_ok!(Ad::create(
-            Origin::signed(BOB),
-            vec![vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8]],
-            [0u8; 64].into(),
-            1,
-            1,
-            1u128,
-            0,
-            10u128
-        ));
+        assert_ok!(Did::register(Origin::signed(eve), Some(did_dave)));
+        let did_eve = Did::did_of(&eve).unwrap();
 
-        assert_ok!(Assets::force_create(
+        // 2. bounded schedule
+
+        assert_noop!(
+            Ad::update_referral(Origin::signed(BOB), ad, vec![Percent::from_percent(1); 6]),
+            Error::<Test>::WrongReferralSetting
+        );
+        assert_noop!(
+            Ad::update_referral(
+                Origin::signed(BOB),
+                ad,
+                vec![Percent::from_percent(60), Percent::from_percent(50)]
+            ),
+            Error::<Test>::WrongReferralSetting
+        );
+
+        assert_ok!(Ad::update_referral(
+            Origin::signed(BOB),
+            ad,
+            vec![
+                Percent::from_percent(20),
+                Percent::from_percent(10),
+                Percent::from_percent(10)
+            ]
+        ));
+
+        // 3. pay, the given referrer is ignored
+
+        assert_eq!(
+            Ad::dryly_pay(ad, nft, did_eve, Some(DID_TAGA5_TAGB2)),
+            Ok((0, 7, 3, 0))
+        );
+
+        assert_ok!(Ad::pay(
+            Origin::signed(BOB),
+            ad,
+            nft,
+            did_eve,
+            vec![(vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8], 5)],
+            Some(DID_TAGA5_TAGB2)
+        ));
+
+        assert_eq!(Assets::balance(nft_meta.token_asset_id, &eve), 7);
+        assert_eq!(Assets::balance(nft_meta.token_asset_id, &dave), 2);
+        assert_eq!(
+            Assets::balance(nft_meta.token_asset_id, &CHARLIE),
+            CHARLIE_BALANCE + 1
+        );
+        assert_eq!(Assets::balance(nft_meta.token_asset_id, &TAGA5_TAGB2), 0);
+    });
+}
+
+#[test]
+fn should_pay_3_for_taga5_tagb2() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare
+        let (ad, nft) = prepare_pay!();
+        let nft_meta = Nft::meta(nft).unwrap();
+        // 2 pay
+        assert_ok!(Ad::pay(
+            Origin::signed(BOB),
+            ad,
+            nft,
+            DID_TAGA5_TAGB2,
+            vec![(vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8], 5)],
+            None
+        ));
+
+        assert_eq!(Assets::balance(nft_meta.token_asset_id, &TAGA5_TAGB2), 3);
+    });
+}
+
+#[test]
+fn should_pay_0_when_all_tags_score_are_zero() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare
+        let (ad, nft) = prepare_pay!();
+        let nft_meta = Nft::meta(nft).unwrap();
+        // 2 pay
+        assert_ok!(Ad::pay(
+            Origin::signed(BOB),
+            ad,
+            nft,
+            DID_TAGA0_TAGB0,
+            vec![(vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8], 5)],
+            None
+        ));
+
+        assert_eq!(Assets::balance(nft_meta.token_asset_id, &TAGA0_TAGB0), 0);
+    });
+}
+
+#[test]
+fn should_pay_5_when_all_tags_score_are_zero_with_payout_min_is_5() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare
+        let (ad, nft) = prepare_pay!(1u128, 5u128, 10u128);
+        let nft_meta = Nft::meta(nft).unwrap();
+        // 2 pay
+        assert_ok!(Ad::pay(
+            Origin::signed(BOB),
+            ad,
+            nft,
+            DID_TAGA0_TAGB0,
+            vec![(vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8], 5)],
+            None
+        ));
+
+        assert_eq!(Assets::balance(nft_meta.token_asset_id, &TAGA0_TAGB0), 5);
+    });
+}
+#[test]
+fn should_pay_10_when_all_tags_are_full_score() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare
+        let (ad, nft) = prepare_pay!();
+        let nft_meta = Nft::meta(nft).unwrap();
+        // 2 pay
+        assert_ok!(Ad::pay(
+            Origin::signed(BOB),
+            ad,
+            nft,
+            DID_TAGA100_TAGB100,
+            vec![(vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8], 5)],
+            None
+        ));
+
+        assert_eq!(
+            Assets::balance(nft_meta.token_asset_id, &TAGA100_TAGB100),
+            10
+        );
+    });
+}
+#[test]
+fn should_pay_10_when_all_tags_are_full_score_or_overflow() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare
+        let (ad, nft) = prepare_pay!();
+        let nft_meta = Nft::meta(nft).unwrap();
+        // 2 pay
+        assert_ok!(Ad::pay(
+            Origin::signed(BOB),
+            ad,
+            nft,
+            DID_TAGA120_TAGB0,
+            vec![(vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8], 5)],
+            None
+        ));
+
+        assert_eq!(Assets::balance(nft_meta.token_asset_id, &TAGA120_TAGB0), 10);
+    });
+}
+
+#[test]
+fn should_pay_dual() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare
+
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+
+        // create ad
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            vec![vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8]],
+            [0u8; 64].into(),
+            1,
+            1,
+            1u128,
+            0,
+            10u128,
+            0,
+            1
+        ));
+
+        assert_ok!(Assets::force_create(
             Origin::root(),
             9,
             MultiAddress::Id(BOB),
@@ -726,7 +1173,7 @@ fn should_pay_dual() {
             None
         ));
 
-        let slot = <SlotOf<Test>>::get(nft).unwrap();
+        let slot = <SlotOf<Test>>::get(nft, &ad).unwrap();
         assert_eq!(slot.fungible_id, Some(9));
         assert_eq!(Assets::balance(9, &CHARLIE), 5);
     });
@@ -734,7 +1181,7 @@ fn should_pay_dual() {
 
 #[test]
 fn should_pay_failed() {
-    use sp_runtime::MultiAddress;
+    use sp_runtime::{MultiAddress, Percent};
 
     new_test_ext().execute_with(|| {
         // 1. prepare
@@ -751,7 +1198,9 @@ fn should_pay_failed() {
             1,
             1u128,
             0,
-            10u128
+            10u128,
+            0,
+            1
         ));
 
         assert_ok!(Assets::force_create(
@@ -804,3 +1253,918 @@ fn should_pay_failed() {
         );
     });
 }
+
+#[test]
+fn should_query_slot() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+
+        assert_eq!(Ad::slots_current(nft), vec![]);
+
+        let (ad, nft) = prepare_pay!();
+
+        assert_eq!(Ad::slots_current(nft), vec![(ad, 1)]);
+        assert_eq!(Ad::slot_budget(nft, ad), Ok((400, 0)));
+        assert_noop!(Ad::slot_budget(nft + 1, ad), Error::<Test>::SlotNotExists);
+        assert_eq!(<AdsOf<Test>>::get(&DID_BOB), Some(vec![ad]));
+    });
+}
+
+#[test]
+fn should_query_paid() {
+    new_test_ext().execute_with(|| {
+        let (ad, nft) = prepare_pay!();
+
+        assert!(!Ad::is_paid(ad, DID_CHARLIE));
+
+        assert_ok!(Ad::pay(
+            Origin::signed(BOB),
+            ad,
+            nft,
+            DID_CHARLIE,
+            vec![(vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8], 5)],
+            None
+        ));
+
+        assert!(Ad::is_paid(ad, DID_CHARLIE));
+        assert!(!Ad::is_paid(ad, DID_TAGA5_TAGB2));
+        assert_eq!(Ad::slot_budget(nft, ad), Ok((398, 0)));
+    });
+}
+
+#[test]
+fn should_pay_batch() {
+    new_test_ext().execute_with(|| {
+        let (ad, nft) = prepare_pay!();
+        let nft_meta = Nft::meta(nft).unwrap();
+
+        let tags = vec![(vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8], 5)];
+
+        assert_ok!(Ad::pay_batch(
+            Origin::signed(BOB),
+            ad,
+            nft,
+            vec![
+                (DID_CHARLIE, tags.clone(), None),
+                (DID_TAGA5_TAGB2, tags.clone(), None),
+                (DID_CHARLIE, tags.clone(), None),
+                (DID_TAGA0_TAGB0, vec![], None),
+            ]
+        ));
+
+        assert_eq!(Assets::balance(nft_meta.token_asset_id, &CHARLIE), 502);
+        assert_eq!(Assets::balance(nft_meta.token_asset_id, &TAGA5_TAGB2), 3);
+
+        assert!(Ad::is_paid(ad, DID_CHARLIE));
+        assert!(Ad::is_paid(ad, DID_TAGA5_TAGB2));
+        assert!(!Ad::is_paid(ad, DID_TAGA0_TAGB0));
+
+        assert_eq!(Ad::slot_budget(nft, ad), Ok((395, 0)));
+    });
+}
+
+#[test]
+fn should_fail_to_pay_batch_over_limit() {
+    new_test_ext().execute_with(|| {
+        let (ad, nft) = prepare_pay!();
+
+        let tags = vec![(vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8], 5)];
+        let batch = vec![(DID_CHARLIE, tags, None); AdMaxPayBatch::get() as usize + 1];
+
+        assert_noop!(
+            Ad::pay_batch(Origin::signed(BOB), ad, nft, batch),
+            Error::<Test>::TooManyPayouts
+        );
+    });
+}
+
+#[test]
+fn should_stop_pay_batch_when_budget_exhausted() {
+    new_test_ext().execute_with(|| {
+        let (ad, nft) = prepare_pay!(1u128, 200u128, 201u128);
+        let nft_meta = Nft::meta(nft).unwrap();
+
+        let tags = vec![(vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8], 5)];
+
+        assert_ok!(Ad::pay_batch(
+            Origin::signed(BOB),
+            ad,
+            nft,
+            vec![
+                (DID_CHARLIE, tags.clone(), None),
+                (DID_TAGA5_TAGB2, tags.clone(), None),
+                (DID_TAGA0_TAGB0, tags.clone(), None),
+            ]
+        ));
+
+        assert_eq!(Assets::balance(nft_meta.token_asset_id, &CHARLIE), 700);
+        assert_eq!(Assets::balance(nft_meta.token_asset_id, &TAGA5_TAGB2), 200);
+        assert_eq!(Assets::balance(nft_meta.token_asset_id, &TAGA0_TAGB0), 0);
+
+        assert!(!Ad::is_paid(ad, DID_TAGA0_TAGB0));
+        assert_eq!(<SlotOf<Test>>::get(nft, &ad), None);
+    });
+}
+
+#[test]
+fn should_fail_to_pay_batch_when_not_owned() {
+    new_test_ext().execute_with(|| {
+        let (ad, nft) = prepare_pay!();
+
+        assert_noop!(
+            Ad::pay_batch(
+                Origin::signed(CHARLIE),
+                ad,
+                nft,
+                vec![(
+                    DID_TAGA5_TAGB2,
+                    vec![(vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8], 5)],
+                    None
+                )]
+            ),
+            Error::<Test>::NotOwned
+        );
+    });
+}
+
+#[test]
+fn should_extend() {
+    new_test_ext().execute_with(|| {
+        let (ad, nft) = prepare_pay!();
+
+        assert_eq!(<DeadlineOf<Test>>::get(nft, &ad), Some(1));
+
+        assert_ok!(Ad::extend(Origin::signed(BOB), ad, 43200 * 2));
+
+        assert_eq!(<EndtimeOf<Test>>::get(&ad), Some(43200 * 2));
+        assert_eq!(<DeadlineOf<Test>>::get(nft, &ad), Some(43200));
+    });
+}
+
+#[test]
+fn should_fail_to_extend() {
+    new_test_ext().execute_with(|| {
+        let (ad, _) = prepare_pay!();
+
+        assert_noop!(
+            Ad::extend(Origin::signed(BOB), ad, 1),
+            Error::<Test>::Deadline
+        );
+
+        assert_noop!(
+            Ad::extend(Origin::signed(CHARLIE), ad, 43200),
+            Error::<Test>::NotOwned
+        );
+    });
+}
+
+#[test]
+fn should_cancel() {
+    new_test_ext().execute_with(|| {
+        let (ad, nft) = prepare_pay!();
+        let meta = Nft::meta(nft).unwrap();
+
+        assert_ok!(Ad::pay(
+            Origin::signed(BOB),
+            ad,
+            nft,
+            DID_CHARLIE,
+            vec![(vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8], 5)],
+            None
+        ));
+
+        assert_noop!(
+            Ad::cancel(Origin::signed(CHARLIE), ad),
+            Error::<Test>::NotOwned
+        );
+
+        assert_ok!(Ad::cancel(Origin::signed(BOB), ad));
+
+        assert_eq!(Assets::balance(meta.token_asset_id, BOB), 498);
+
+        assert_eq!(<SlotOf<Test>>::get(nft, &ad), None);
+        assert_eq!(<SlotsOf<Test>>::get(&ad).len(), 0);
+        assert_eq!(<DeadlineOf<Test>>::get(nft, &ad), None);
+        assert_eq!(<EndtimeOf<Test>>::get(&ad), None);
+        assert_eq!(<Metadata<Test>>::get(&ad), None);
+        assert_eq!(<AdsOf<Test>>::get(&DID_BOB), None);
+        assert!(!Ad::is_paid(ad, DID_CHARLIE));
+    });
+}
+
+#[test]
+fn should_purge_payouts_over_blocks() {
+    new_test_ext().execute_with(|| {
+        AdMaxPurgesPerBlock::set(1);
+
+        let (ad, nft) = prepare_pay!();
+
+        let tags = vec![(vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8], 5)];
+
+        assert_ok!(Ad::pay_batch(
+            Origin::signed(BOB),
+            ad,
+            nft,
+            vec![
+                (DID_CHARLIE, tags.clone(), None),
+                (DID_TAGA5_TAGB2, tags.clone(), None),
+            ]
+        ));
+
+        assert_ok!(Ad::cancel(Origin::signed(BOB), ad));
+
+        assert_eq!(<Metadata<Test>>::get(&ad), None);
+        assert!(Ad::is_paid(ad, DID_CHARLIE) ^ Ad::is_paid(ad, DID_TAGA5_TAGB2));
+        assert!(<PurgeQueue<Test>>::contains_key(&ad));
+
+        Ad::on_initialize(System::block_number());
+
+        assert!(!Ad::is_paid(ad, DID_CHARLIE));
+        assert!(!Ad::is_paid(ad, DID_TAGA5_TAGB2));
+        assert!(!<PurgeQueue<Test>>::contains_key(&ad));
+    });
+}
+
+#[test]
+fn should_withdraw_budget() {
+    new_test_ext().execute_with(|| {
+        let (ad, nft) = prepare_pay!();
+        let meta = Nft::meta(nft).unwrap();
+
+        assert_eq!(Assets::balance(meta.token_asset_id, BOB), 100);
+
+        assert_noop!(
+            Ad::withdraw_budget(Origin::signed(CHARLIE), ad, nft),
+            Error::<Test>::NotOwned
+        );
+
+        assert_noop!(
+            Ad::withdraw_budget(Origin::signed(BOB), ad, 9),
+            Error::<Test>::SlotNotExists
+        );
+
+        assert_ok!(Ad::withdraw_budget(Origin::signed(BOB), ad, nft));
+
+        assert_eq!(Assets::balance(meta.token_asset_id, BOB), 500);
+
+        assert_ne!(<SlotOf<Test>>::get(nft, &ad), None);
+        assert_ne!(<DeadlineOf<Test>>::get(nft, &ad), None);
+        assert_eq!(<SlotsOf<Test>>::get(&ad).into_inner(), vec![nft]);
+        assert_ne!(<Metadata<Test>>::get(&ad), None);
+
+        System::set_block_number(<DeadlineOf<Test>>::get(nft, &ad).unwrap());
+
+        assert_noop!(
+            Ad::withdraw_budget(Origin::signed(BOB), ad, nft),
+            Error::<Test>::Deadline
+        );
+    });
+}
+
+#[test]
+fn should_not_destroy_with_slots() {
+    new_test_ext().execute_with(|| {
+        let (ad, nft) = prepare_pay!();
+
+        assert_noop!(Ad::destroy(ad, 5), Error::<Test>::SlotsRemaining);
+
+        assert_ne!(<SlotOf<Test>>::get(nft, &ad), None);
+        assert_ne!(<Metadata<Test>>::get(&ad), None);
+    });
+}
+
+#[test]
+fn should_cleanup_when_ad_expired() {
+    new_test_ext().execute_with(|| {
+        let (ad, nft) = prepare_pay!();
+
+        assert_ok!(Ad::pay(
+            Origin::signed(BOB),
+            ad,
+            nft,
+            DID_CHARLIE,
+            vec![(vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8], 5)],
+            None
+        ));
+
+        System::set_block_number(1);
+
+        Ad::on_initialize(System::block_number());
+
+        assert_eq!(<SlotOf<Test>>::get(nft, &ad), None);
+        assert_eq!(<EndtimeOf<Test>>::get(&ad), None);
+        assert_eq!(<Metadata<Test>>::get(&ad), None);
+        assert_eq!(<AdsOf<Test>>::get(&DID_BOB), None);
+        assert!(!Ad::is_paid(ad, DID_CHARLIE));
+        assert_eq!(Tag::tags_of(&ad).len(), 0);
+    });
+}
+
+#[test]
+fn should_sweep_expiries_over_blocks() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare, 12 ads ending at the same block
+
+        for n in 1..=12 {
+            System::set_block_number(n);
+            Ad::on_initialize(n);
+
+            assert_ok!(Ad::create(
+                Origin::signed(BOB),
+                vec![],
+                [0u8; 64].into(),
+                1,
+                100,
+                1u128,
+                0,
+                10u128,
+                0,
+                1
+            ));
+        }
+
+        assert_eq!(<AdsOf<Test>>::get(&DID_BOB).unwrap().len(), 12);
+
+        for n in 13..100 {
+            System::set_block_number(n);
+            Ad::on_initialize(n);
+        }
+
+        assert_eq!(<Metadata<Test>>::iter().count(), 12);
+
+        // 2. sweep at most 5 expiries per block
+
+        System::set_block_number(100);
+        Ad::on_initialize(100);
+
+        assert_eq!(<Metadata<Test>>::iter().count(), 7);
+        assert_eq!(<EndtimeOf<Test>>::iter().count(), 7);
+
+        System::set_block_number(101);
+        Ad::on_initialize(101);
+
+        assert_eq!(<Metadata<Test>>::iter().count(), 2);
+
+        System::set_block_number(102);
+        Ad::on_initialize(102);
+
+        assert_eq!(<Metadata<Test>>::iter().count(), 0);
+        assert_eq!(<EndtimeOf<Test>>::iter().count(), 0);
+        assert_eq!(<AdsOf<Test>>::get(&DID_BOB), None);
+    });
+}
+
+#[test]
+fn should_bound_idle_blocks_swept() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(100);
+        Ad::on_initialize(100);
+
+        assert_eq!(<SweepCursor<Test>>::get(), 5);
+    });
+}
+
+#[test]
+fn should_retry_failed_expiries() {
+    new_test_ext().execute_with(|| {
+        let (ad, nft) = prepare_pay!();
+        let meta = Nft::meta(nft).unwrap();
+
+        // drawback fails without the metadata
+        let ad_meta = <Metadata<Test>>::take(&ad).unwrap();
+
+        System::set_block_number(1);
+        Ad::on_initialize(1);
+
+        assert_ne!(<SlotOf<Test>>::get(nft, &ad), None);
+        assert_ne!(<EndtimeOf<Test>>::get(&ad), None);
+        assert!(<DeadlineQueue<Test>>::contains_key(2, (nft, ad)));
+        assert!(<EndtimeQueue<Test>>::contains_key(2, ad));
+
+        <Metadata<Test>>::insert(&ad, ad_meta);
+
+        System::set_block_number(2);
+        Ad::on_initialize(2);
+
+        assert_eq!(Assets::balance(meta.token_asset_id, BOB), 500);
+
+        assert_eq!(<SlotOf<Test>>::get(nft, &ad), None);
+        assert_eq!(<EndtimeOf<Test>>::get(&ad), None);
+        assert_eq!(<Metadata<Test>>::get(&ad), None);
+    });
+}
+
+#[test]
+fn should_not_sweep_extended_ad() {
+    new_test_ext().execute_with(|| {
+        let (ad, nft) = prepare_pay!();
+
+        assert_ok!(Ad::extend(Origin::signed(BOB), ad, 43200 * 2));
+
+        System::set_block_number(1);
+        Ad::on_initialize(1);
+
+        assert_ne!(<SlotOf<Test>>::get(nft, &ad), None);
+        assert_ne!(<Metadata<Test>>::get(&ad), None);
+
+        run_to_block(43200);
+
+        assert_eq!(<SlotOf<Test>>::get(nft, &ad), None);
+        assert_ne!(<Metadata<Test>>::get(&ad), None);
+    });
+}
+
+#[test]
+fn should_calculate_score() {
+    let tag_a = Tag::key(&vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8]);
+    let tag_b = Tag::key(&vec![5u8, 4u8, 3u8, 2u8, 1u8, 0u8]);
+
+    let mut tags = BTreeMap::new();
+    tags.insert(tag_a, true);
+    tags.insert(tag_b, true);
+
+    let mut personas = BTreeMap::new();
+    assert_eq!(Ad::calculate_score(&tags, &personas), 0);
+
+    personas.insert(tag_a, 5);
+    personas.insert(tag_b, 2);
+    assert_eq!(Ad::calculate_score(&tags, &personas), 3);
+
+    personas.insert(tag_a, -10);
+    assert_eq!(Ad::calculate_score(&tags, &personas), 0);
+}
+
+#[test]
+fn should_dryly_pay() {
+    new_test_ext().execute_with(|| {
+        let (ad, nft) = prepare_pay!();
+
+        assert_eq!(Ad::dryly_pay(ad, nft, DID_CHARLIE, None), Ok((2, 2, 0, 0)));
+        assert_eq!(
+            Ad::dryly_pay(ad, nft, DID_TAGA5_TAGB2, None),
+            Ok((3, 3, 0, 0))
+        );
+
+        assert_ok!(Ad::pay(
+            Origin::signed(BOB),
+            ad,
+            nft,
+            DID_CHARLIE,
+            vec![(vec![0u8, 1u8, 2u8, 3u8, 4u8, 5u8], 5)],
+            None
+        ));
+
+        let nft_meta = Nft::meta(nft).unwrap();
+        assert_eq!(Assets::balance(nft_meta.token_asset_id, &CHARLIE), 502);
+
+        assert_noop!(
+            Ad::dryly_pay(ad, nft, DID_CHARLIE, None),
+            Error::<Test>::Paid
+        );
+    });
+}
+
+#[test]
+fn should_auction_slot() {
+    new_test_ext().execute_with(|| {
+        // 1. prepare
+
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+        let meta = Nft::meta(nft).unwrap();
+
+        for who in [BOB, CHARLIE] {
+            assert_ok!(Ad::create(
+                Origin::signed(who),
+                vec![],
+                [0u8; 64].into(),
+                1,
+                43200,
+                1u128,
+                0,
+                10u128,
+                0,
+                1
+            ));
+        }
+
+        let ad1 = <AdsOf<Test>>::get(&DID_BOB).unwrap()[0];
+        let ad2 = <AdsOf<Test>>::get(&DID_CHARLIE).unwrap()[0];
+
+        // 2. commit
+
+        assert_noop!(
+            Ad::start_auction(Origin::signed(BOB), nft),
+            Error::<Test>::NotOwned
+        );
+
+        assert_ok!(Ad::start_auction(Origin::signed(ALICE), nft));
+
+        assert_noop!(
+            Ad::start_auction(Origin::signed(ALICE), nft),
+            Error::<Test>::AuctionExists
+        );
+
+        assert_noop!(
+            Ad::bid_with_fraction(Origin::signed(BOB), ad1, nft, 400, None, None),
+            Error::<Test>::InAuction
+        );
+
+        let sealed1 =
+            <Test as frame_system::Config>::Hashing::hash_of(&(ad1, 300u128, b"bob".to_vec()));
+        let sealed2 =
+            <Test as frame_system::Config>::Hashing::hash_of(&(ad2, 200u128, b"charlie".to_vec()));
+
+        assert_ok!(Ad::commit_bid(Origin::signed(BOB), ad1, nft, sealed1, 400));
+        assert_ok!(Ad::commit_bid(
+            Origin::signed(CHARLIE),
+            ad2,
+            nft,
+            sealed2,
+            250
+        ));
+
+        assert_noop!(
+            Ad::commit_bid(Origin::signed(BOB), ad1, nft, sealed1, 400),
+            Error::<Test>::AlreadyCommitted
+        );
+
+        assert_noop!(
+            Ad::reveal_bid(Origin::signed(BOB), ad1, nft, 300, b"bob".to_vec()),
+            Error::<Test>::NotRevealPeriod
+        );
+
+        assert_eq!(Ad::auction_of(nft).unwrap().bids, 2);
+        assert_eq!(Assets::balance(meta.token_asset_id, BOB), BOB_BALANCE - 400);
+        assert_eq!(
+            Assets::balance(meta.token_asset_id, CHARLIE),
+            CHARLIE_BALANCE - 250
+        );
+
+        // 3. reveal
+
+        System::set_block_number(10);
+
+        assert_noop!(
+            Ad::commit_bid(Origin::signed(BOB), ad1, nft, sealed1, 400),
+            Error::<Test>::NotCommitPeriod
+        );
+
+        assert_noop!(
+            Ad::reveal_bid(Origin::signed(BOB), ad1, nft, 301, b"bob".to_vec()),
+            Error::<Test>::InvalidReveal
+        );
+
+        assert_ok!(Ad::reveal_bid(
+            Origin::signed(BOB),
+            ad1,
+            nft,
+            300,
+            b"bob".to_vec()
+        ));
+        assert_ok!(Ad::reveal_bid(
+            Origin::signed(CHARLIE),
+            ad2,
+            nft,
+            200,
+            b"charlie".to_vec()
+        ));
+
+        // 4. close
+
+        run_to_block(20);
+
+        assert_eq!(Ad::auction_of(nft), None);
+        assert_eq!(<SealedBidOf<Test>>::iter_prefix(nft).count(), 0);
+
+        assert_ne!(<SlotOf<Test>>::get(nft, &ad1), None);
+        assert_eq!(<SlotOf<Test>>::get(nft, &ad2), None);
+
+        assert_eq!(Ad::slot_budget(nft, ad1), Ok((300, 0)));
+        assert_eq!(Assets::balance(meta.token_asset_id, BOB), BOB_BALANCE - 300);
+        assert_eq!(
+            Assets::balance(meta.token_asset_id, CHARLIE),
+            CHARLIE_BALANCE
+        );
+    });
+}
+
+#[test]
+fn should_fall_back_to_next_highest_bid() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+        let meta = Nft::meta(nft).unwrap();
+
+        for who in [BOB, CHARLIE] {
+            assert_ok!(Ad::create(
+                Origin::signed(who),
+                vec![],
+                [0u8; 64].into(),
+                1,
+                43200,
+                1u128,
+                0,
+                10u128,
+                0,
+                1
+            ));
+        }
+
+        let ad1 = <AdsOf<Test>>::get(&DID_BOB).unwrap()[0];
+        let ad2 = <AdsOf<Test>>::get(&DID_CHARLIE).unwrap()[0];
+
+        assert_ok!(Ad::start_auction(Origin::signed(ALICE), nft));
+
+        let sealed1 =
+            <Test as frame_system::Config>::Hashing::hash_of(&(ad1, 300u128, b"bob".to_vec()));
+        let sealed2 =
+            <Test as frame_system::Config>::Hashing::hash_of(&(ad2, 200u128, b"charlie".to_vec()));
+
+        assert_ok!(Ad::commit_bid(Origin::signed(BOB), ad1, nft, sealed1, 400));
+        assert_ok!(Ad::commit_bid(
+            Origin::signed(CHARLIE),
+            ad2,
+            nft,
+            sealed2,
+            250
+        ));
+
+        System::set_block_number(10);
+
+        assert_ok!(Ad::reveal_bid(
+            Origin::signed(BOB),
+            ad1,
+            nft,
+            300,
+            b"bob".to_vec()
+        ));
+        assert_ok!(Ad::reveal_bid(
+            Origin::signed(CHARLIE),
+            ad2,
+            nft,
+            200,
+            b"charlie".to_vec()
+        ));
+
+        // the highest bidder can no longer take the slot
+        assert_ok!(Ad::cancel(Origin::signed(BOB), ad1));
+
+        run_to_block(20);
+
+        assert_eq!(<SlotOf<Test>>::get(nft, &ad1), None);
+        assert_ne!(<SlotOf<Test>>::get(nft, &ad2), None);
+
+        assert_eq!(Ad::slot_budget(nft, ad2), Ok((200, 0)));
+        assert_eq!(Assets::balance(meta.token_asset_id, BOB), BOB_BALANCE);
+        assert_eq!(
+            Assets::balance(meta.token_asset_id, CHARLIE),
+            CHARLIE_BALANCE - 200
+        );
+    });
+}
+
+#[test]
+fn should_refund_unrevealed_bids() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+        let meta = Nft::meta(nft).unwrap();
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            vec![],
+            [0u8; 64].into(),
+            1,
+            43200,
+            1u128,
+            0,
+            10u128,
+            0,
+            1
+        ));
+
+        let ad = <AdsOf<Test>>::get(&DID_BOB).unwrap()[0];
+
+        assert_noop!(
+            Ad::commit_bid(Origin::signed(BOB), ad, nft, Default::default(), 400),
+            Error::<Test>::AuctionNotExists
+        );
+
+        assert_ok!(Ad::start_auction(Origin::signed(ALICE), nft));
+
+        let sealed =
+            <Test as frame_system::Config>::Hashing::hash_of(&(ad, 300u128, b"bob".to_vec()));
+        assert_ok!(Ad::commit_bid(Origin::signed(BOB), ad, nft, sealed, 400));
+
+        run_to_block(20);
+
+        assert_eq!(Ad::auction_of(nft), None);
+        assert_eq!(<SlotOf<Test>>::get(nft, &ad), None);
+        assert_eq!(Assets::balance(meta.token_asset_id, BOB), BOB_BALANCE);
+    });
+}
+
+#[test]
+fn should_migrate_sealed_bids() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+        let meta = Nft::meta(nft).unwrap();
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            vec![],
+            [0u8; 64].into(),
+            1,
+            43200,
+            1u128,
+            0,
+            10u128,
+            0,
+            1
+        ));
+
+        let ad = <AdsOf<Test>>::get(&DID_BOB).unwrap()[0];
+
+        assert_ok!(Ad::start_auction(Origin::signed(ALICE), nft));
+
+        let sealed =
+            <Test as frame_system::Config>::Hashing::hash_of(&(ad, 300u128, b"bob".to_vec()));
+        assert_ok!(Ad::commit_bid(Origin::signed(BOB), ad, nft, sealed, 400));
+
+        assert_eq!(
+            Ad::migrate(&BOB, &CHARLIE, 0),
+            Err(DispatchError::Exhausted)
+        );
+        assert_eq!(
+            Ad::migrate(&BOB, &CHARLIE, 1),
+            Ok(vec![Migrated::SealedBid(nft.into(), 400)])
+        );
+        assert_eq!(<SealedBidOf<Test>>::get(nft, &ad).unwrap().bidder, CHARLIE);
+        assert!(!<BidsOf<Test>>::contains_key(&BOB, (nft, ad)));
+        assert!(<BidsOf<Test>>::contains_key(&CHARLIE, (nft, ad)));
+
+        assert_eq!(Ad::migrate(&BOB, &CHARLIE, 0), Ok(vec![]));
+
+        run_to_block(20);
+
+        assert_eq!(<BidsOf<Test>>::iter().count(), 0);
+
+        assert_eq!(Assets::balance(meta.token_asset_id, BOB), BOB_BALANCE - 400);
+        assert_eq!(
+            Assets::balance(meta.token_asset_id, CHARLIE),
+            CHARLIE_BALANCE + 400
+        );
+    });
+}
+
+#[test]
+fn should_migrate_metadata() {
+    new_test_ext().execute_with(|| {
+        let module = <Ad as PalletInfoAccess>::name().as_bytes();
+
+        let ad = H256::repeat_byte(0x42);
+
+        // id, creator, metadata, reward_rate, created, payout_base, payout_min, payout_max
+        let old = (
+            ad,
+            DID_ALICE,
+            vec![0u8; 8],
+            10u16,
+            3u64,
+            1u128,
+            0u128,
+            10u128,
+        );
+        put_storage_value(module, b"Metadata", &ad.encode(), old);
+
+        let last = 5u64;
+        put_storage_value(module, b"Payout", &(ad, DID_BOB).encode(), last);
+
+        StorageVersion::new(2).put::<Ad>();
+
+        migrations::migrate::<Test>();
+
+        assert_eq!(StorageVersion::get::<Ad>(), crate::STORAGE_VERSION);
+
+        let meta = <Metadata<Test>>::get(&ad).unwrap();
+        assert_eq!(meta.creator, DID_ALICE);
+        assert_eq!(meta.created, 3);
+        assert_eq!(meta.payout_max, 10);
+        assert_eq!(meta.cooldown, 0);
+        assert_eq!(meta.max_payouts, 1);
+        assert_eq!(meta.targeting, Default::default());
+        assert_eq!(meta.referral, vec![]);
+
+        assert_eq!(
+            Ad::payout(&ad, &DID_BOB),
+            Some(types::Payout { last, count: 1 })
+        );
+    });
+}
+
+#[test]
+fn should_migrate_slots() {
+    new_test_ext().execute_with(|| {
+        let module = <Ad as PalletInfoAccess>::name().as_bytes();
+
+        let slots = [
+            types::Slot {
+                ad_id: H256::repeat_byte(0x42),
+                nft_id: 0u64,
+                fraction_id: 0u64,
+                fungible_id: None,
+                budget_pot: ALICE,
+                created: 1u64,
+            },
+            types::Slot {
+                ad_id: H256::repeat_byte(0x43),
+                nft_id: 1u64,
+                fraction_id: 1u64,
+                fungible_id: Some(9u64),
+                budget_pot: BOB,
+                created: 2u64,
+            },
+        ];
+
+        // slots used to be keyed by NFT only
+        for slot in &slots {
+            let key = Twox64Concat::hash(&slot.nft_id.encode());
+            put_storage_value(module, b"SlotOf", &key, slot);
+        }
+
+        StorageVersion::new(1).put::<Ad>();
+
+        migrations::migrate::<Test>();
+
+        assert_eq!(StorageVersion::get::<Ad>(), crate::STORAGE_VERSION);
+
+        for slot in slots {
+            assert_eq!(
+                <SlotsOf<Test>>::get(slot.ad_id).into_inner(),
+                vec![slot.nft_id]
+            );
+            assert_eq!(<SlotOf<Test>>::get(slot.nft_id, slot.ad_id), Some(slot));
+        }
+        assert_eq!(<SlotOf<Test>>::iter().count(), 2);
+    });
+}
+
+#[test]
+fn should_refund_slots_over_limit_when_migrating() {
+    new_test_ext().execute_with(|| {
+        let (ad, nft) = prepare_pay!();
+        let meta = Nft::meta(nft).unwrap();
+
+        assert_eq!(Assets::balance(meta.token_asset_id, BOB), 100);
+
+        // slots were not indexed before
+        <SlotsOf<Test>>::remove(&ad);
+        AdMaxSlotsPerAd::set(0);
+
+        StorageVersion::new(5).put::<Ad>();
+
+        migrations::migrate::<Test>();
+
+        assert_eq!(Assets::balance(meta.token_asset_id, BOB), 500);
+
+        assert_eq!(<SlotOf<Test>>::get(nft, &ad), None);
+        assert_eq!(<DeadlineOf<Test>>::get(nft, &ad), None);
+        assert_eq!(<SlotsOf<Test>>::get(&ad).len(), 0);
+    });
+}
+
+#[test]
+fn should_index_sealed_bids_when_migrating() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+
+        assert_ok!(Ad::create(
+            Origin::signed(BOB),
+            vec![],
+            [0u8; 64].into(),
+            1,
+            43200,
+            1u128,
+            0,
+            10u128,
+            0,
+            1
+        ));
+
+        let ad = <AdsOf<Test>>::get(&DID_BOB).unwrap()[0];
+
+        assert_ok!(Ad::start_auction(Origin::signed(ALICE), nft));
+
+        let sealed =
+            <Test as frame_system::Config>::Hashing::hash_of(&(ad, 300u128, b"bob".to_vec()));
+        assert_ok!(Ad::commit_bid(Origin::signed(BOB), ad, nft, sealed, 400));
+
+        // bids were not indexed before
+        <BidsOf<Test>>::remove(&BOB, (nft, ad));
+
+        StorageVersion::new(6).put::<Ad>();
+
+        migrations::migrate::<Test>();
+
+        assert_eq!(StorageVersion::get::<Ad>(), crate::STORAGE_VERSION);
+        assert!(<BidsOf<Test>>::contains_key(&BOB, (nft, ad)));
+    });
+}
@@ -1,8 +1,9 @@
 use codec::{Decode, Encode, MaxEncodedLen};
+use parami_traits::types::Network;
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{Percent, RuntimeDebug};
 use sp_std::prelude::*;
 
 #[derive(Clone, Decode, Default, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
@@ -16,6 +17,29 @@ pub struct Metadata<B, D, H, N> {
     pub payout_base: B,
     pub payout_min: B,
     pub payout_max: B,
+    pub cooldown: N,
+    pub max_payouts: u32,
+    pub targeting: Targeting,
+    // awards of each level of the inviter chain, empty to award the given referrer only
+    pub referral: Vec<Percent>,
+}
+
+#[derive(Clone, Decode, Default, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Targeting {
+    // visitors must score at least the given value on each tag
+    pub min_scores: Vec<(Vec<u8>, i32)>,
+    // visitors with a negative score on any of these tags are excluded
+    pub excluded: Vec<Vec<u8>>,
+    // visitors must have linked each of these networks
+    pub networks: Vec<Network>,
+}
+
+#[derive(Clone, Decode, Default, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Payout<Height> {
+    pub last: Height,
+    pub count: u32,
 }
 
 #[derive(Clone, Decode, Default, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
@@ -29,3 +53,21 @@ pub struct Slot<Hash, Height, NftId, TokenId, AccountId> {
     pub budget_pot: AccountId,
     pub created: Height,
 }
+
+#[derive(Clone, Decode, Default, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Auction<Height> {
+    pub commit_end: Height,
+    pub reveal_end: Height,
+    pub bids: u32,
+}
+
+#[derive(Clone, Decode, Default, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SealedBid<AccountId, Balance, Hash> {
+    pub bidder: AccountId,
+    // hash of (ad_id, value, salt)
+    pub sealed: Hash,
+    pub deposit: Balance,
+    pub revealed: Option<Balance>,
+}
@@ -45,10 +45,23 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
     fn create() -> Weight;
     fn update_reward_rate() -> Weight;
+    fn update_frequency() -> Weight;
     fn update_tags() -> Weight;
+    fn update_targeting() -> Weight;
+    fn update_referral() -> Weight;
     fn add_budget() -> Weight;
+    fn extend(n: u32, ) -> Weight;
+    fn cancel(n: u32, ) -> Weight;
+    fn withdraw_budget() -> Weight;
     fn bid() -> Weight;
-    fn pay() -> Weight;
+    fn start_auction() -> Weight;
+    fn commit_bid() -> Weight;
+    fn reveal_bid() -> Weight;
+    fn close_auction(n: u32, ) -> Weight;
+    fn pay(r: u32, ) -> Weight;
+    fn pay_batch(n: u32, r: u32, ) -> Weight;
+    fn on_initialize(n: u32, ) -> Weight;
+    fn migrate_account(n: u32, ) -> Weight;
 }
 
 /// Weights for parami_ad using the Substrate node and recommended hardware.
@@ -79,6 +92,15 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     // Storage: Did DidOf (r:1 w:0)
     // Storage: Balances Reserves (r:1 w:0)
     // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:1)
+    fn update_frequency() -> Weight {
+        (24_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
     // Storage: Ad Metadata (r:1 w:0)
     // Storage: Tag Metadata (r:1 w:0)
     // Storage: Tag TagsOf (r:0 w:1)
@@ -91,6 +113,25 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     // Storage: Balances Reserves (r:1 w:0)
     // Storage: Ad EndtimeOf (r:1 w:0)
     // Storage: Ad Metadata (r:1 w:1)
+    // Storage: Tag Metadata (r:1 w:0)
+    fn update_targeting() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:1)
+    fn update_referral() -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:1)
     // Storage: System Account (r:1 w:1)
     fn add_budget() -> Weight {
         (41_000_000 as Weight)
@@ -99,6 +140,59 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     }
     // Storage: Did DidOf (r:1 w:0)
     // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:1)
+    // Storage: Ad Metadata (r:1 w:0)
+    // Storage: Ad SlotsOf (r:1 w:0)
+    // Storage: Ad DeadlineOf (r:2 w:1)
+    // Storage: Ad SlotOf (r:1 w:0)
+    fn extend(n: u32, ) -> Weight {
+        (25_000_000 as Weight)
+            // Standard Error: 6_000
+            .saturating_add((9_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+    }
+    // Storage: Did DidOf (r:2 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:1)
+    // Storage: Ad Metadata (r:1 w:1)
+    // Storage: Ad SlotsOf (r:1 w:1)
+    // Storage: Ad DeadlineOf (r:2 w:1)
+    // Storage: Ad SlotOf (r:1 w:1)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Ad AdsOf (r:1 w:1)
+    // Storage: Ad Payout (r:0 w:1)
+    // Storage: Tag TagsOf (r:0 w:1)
+    fn cancel(n: u32, ) -> Weight {
+        (44_000_000 as Weight)
+            // Standard Error: 21_000
+            .saturating_add((52_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().reads((7 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+    }
+    // Storage: Did DidOf (r:2 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad Metadata (r:2 w:0)
+    // Storage: Ad SlotOf (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad DeadlineOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    fn withdraw_budget() -> Weight {
+        (68_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(13 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
     // Storage: Ad EndtimeOf (r:1 w:0)
     // Storage: Ad Metadata (r:1 w:1)
     // Storage: Nft Metadata (r:1 w:0)
@@ -108,12 +202,68 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     // Storage: Assets Asset (r:1 w:1)
     // Storage: Ad SlotsOf (r:1 w:1)
     // Storage: Ad DeadlineOf (r:0 w:1)
+    // Storage: Nft Frozen (r:1 w:0)
     fn bid() -> Weight {
         (110_000_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(12 as Weight))
+            .saturating_add(T::DbWeight::get().reads(13 as Weight))
             .saturating_add(T::DbWeight::get().writes(9 as Weight))
     }
     // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Ad AuctionOf (r:1 w:1)
+    // Storage: Ad AuctionQueue (r:0 w:1)
+    // Storage: Nft Frozen (r:1 w:0)
+    fn start_auction() -> Weight {
+        (32_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:0)
+    // Storage: Ad AuctionOf (r:1 w:1)
+    // Storage: Ad SealedBidOf (r:1 w:1)
+    // Storage: Ad SlotOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Nft Frozen (r:1 w:0)
+    // Storage: Ad BidsOf (r:0 w:1)
+    fn commit_bid() -> Weight {
+        (78_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(12 as Weight))
+            .saturating_add(T::DbWeight::get().writes(7 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:0)
+    // Storage: Ad AuctionOf (r:1 w:0)
+    // Storage: Ad SealedBidOf (r:1 w:1)
+    fn reveal_bid() -> Weight {
+        (35_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Ad AuctionOf (r:0 w:1)
+    // Storage: Ad SealedBidOf (r:1 w:1)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad SlotOf (r:1 w:1)
+    // Storage: Ad DeadlineOf (r:0 w:1)
+    // Storage: Ad DeadlineQueue (r:0 w:1)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: Ad BidsOf (r:0 w:1)
+    fn close_auction(n: u32, ) -> Weight {
+        (46_000_000 as Weight)
+            // Standard Error: 16_000
+            .saturating_add((38_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+    }
+    // Storage: Did DidOf (r:1 w:0)
     // Storage: Balances Reserves (r:1 w:0)
     // Storage: Ad EndtimeOf (r:1 w:0)
     // Storage: Ad Metadata (r:1 w:0)
@@ -127,10 +277,75 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     // Storage: Assets Asset (r:1 w:1)
     // Storage: Assets Account (r:2 w:2)
     // Storage: System Account (r:1 w:1)
-    fn pay() -> Weight {
+    // Storage: Did ReferrerOf (r:1 w:0)
+    // Storage: Nft Royalty (r:1 w:0)
+    fn pay(r: u32, ) -> Weight {
         (98_000_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(17 as Weight))
-            .saturating_add(T::DbWeight::get().writes(7 as Weight))
+            // Standard Error: 12_000
+            .saturating_add((22_000_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(T::DbWeight::get().reads(20 as Weight))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(T::DbWeight::get().writes(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Ad DeadlineOf (r:1 w:0)
+    // Storage: Ad SlotOf (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:0)
+    // Storage: Ad Payout (r:1 w:1)
+    // Storage: Tag TagsOf (r:2 w:0)
+    // Storage: Tag PersonasOf (r:2 w:1)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Did ReferrerOf (r:1 w:0)
+    // Storage: Nft Royalty (r:1 w:0)
+    fn pay_batch(n: u32, r: u32, ) -> Weight {
+        (36_000_000 as Weight)
+            // Standard Error: 21_000
+            .saturating_add((71_000_000 as Weight).saturating_mul(n as Weight))
+            // Standard Error: 9_000
+            .saturating_add((22_000_000 as Weight).saturating_mul(n as Weight).saturating_mul(r as Weight))
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().reads((13 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight).saturating_mul(r as Weight)))
+            .saturating_add(T::DbWeight::get().writes((7 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight).saturating_mul(r as Weight)))
+    }
+    // Storage: Ad PurgeQueue (r:1 w:0)
+    // Storage: Ad SweepCursor (r:1 w:1)
+    // Storage: Ad AuctionQueue (r:1 w:0)
+    // Storage: Ad DeadlineQueue (r:2 w:1)
+    // Storage: Ad DeadlineOf (r:1 w:1)
+    // Storage: Ad SlotOf (r:1 w:1)
+    // Storage: Ad Metadata (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Ad SlotsOf (r:1 w:1)
+    // Storage: Ad EndtimeQueue (r:1 w:0)
+    fn on_initialize(n: u32, ) -> Weight {
+        (4_000_000 as Weight)
+            // Standard Error: 18_000
+            .saturating_add((52_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().reads((10 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((8 as Weight).saturating_mul(n as Weight)))
+    }
+    // Storage: Ad BidsOf (r:1 w:2)
+    // Storage: Ad SealedBidOf (r:1 w:1)
+    fn migrate_account(n: u32, ) -> Weight {
+        (2_000_000 as Weight)
+            // Standard Error: 4_000
+            .saturating_add((9_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
     }
 }
 
@@ -161,6 +376,15 @@ impl WeightInfo for () {
     // Storage: Did DidOf (r:1 w:0)
     // Storage: Balances Reserves (r:1 w:0)
     // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:1)
+    fn update_frequency() -> Weight {
+        (24_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
     // Storage: Ad Metadata (r:1 w:0)
     // Storage: Tag Metadata (r:1 w:0)
     // Storage: Tag TagsOf (r:0 w:1)
@@ -173,6 +397,25 @@ impl WeightInfo for () {
     // Storage: Balances Reserves (r:1 w:0)
     // Storage: Ad EndtimeOf (r:1 w:0)
     // Storage: Ad Metadata (r:1 w:1)
+    // Storage: Tag Metadata (r:1 w:0)
+    fn update_targeting() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:1)
+    fn update_referral() -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:1)
     // Storage: System Account (r:1 w:1)
     fn add_budget() -> Weight {
         (41_000_000 as Weight)
@@ -181,6 +424,59 @@ impl WeightInfo for () {
     }
     // Storage: Did DidOf (r:1 w:0)
     // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:1)
+    // Storage: Ad Metadata (r:1 w:0)
+    // Storage: Ad SlotsOf (r:1 w:0)
+    // Storage: Ad DeadlineOf (r:2 w:1)
+    // Storage: Ad SlotOf (r:1 w:0)
+    fn extend(n: u32, ) -> Weight {
+        (25_000_000 as Weight)
+            // Standard Error: 6_000
+            .saturating_add((9_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+    }
+    // Storage: Did DidOf (r:2 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:1)
+    // Storage: Ad Metadata (r:1 w:1)
+    // Storage: Ad SlotsOf (r:1 w:1)
+    // Storage: Ad DeadlineOf (r:2 w:1)
+    // Storage: Ad SlotOf (r:1 w:1)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Ad AdsOf (r:1 w:1)
+    // Storage: Ad Payout (r:0 w:1)
+    // Storage: Tag TagsOf (r:0 w:1)
+    fn cancel(n: u32, ) -> Weight {
+        (44_000_000 as Weight)
+            // Standard Error: 21_000
+            .saturating_add((52_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((7 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(7 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+    }
+    // Storage: Did DidOf (r:2 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad Metadata (r:2 w:0)
+    // Storage: Ad SlotOf (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad DeadlineOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    fn withdraw_budget() -> Weight {
+        (68_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(13 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
     // Storage: Ad EndtimeOf (r:1 w:0)
     // Storage: Ad Metadata (r:1 w:1)
     // Storage: Nft Metadata (r:1 w:0)
@@ -190,12 +486,68 @@ impl WeightInfo for () {
     // Storage: Assets Asset (r:1 w:1)
     // Storage: Ad SlotsOf (r:1 w:1)
     // Storage: Ad DeadlineOf (r:0 w:1)
+    // Storage: Nft Frozen (r:1 w:0)
     fn bid() -> Weight {
         (110_000_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(12 as Weight))
+            .saturating_add(RocksDbWeight::get().reads(13 as Weight))
             .saturating_add(RocksDbWeight::get().writes(9 as Weight))
     }
     // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Ad AuctionOf (r:1 w:1)
+    // Storage: Ad AuctionQueue (r:0 w:1)
+    // Storage: Nft Frozen (r:1 w:0)
+    fn start_auction() -> Weight {
+        (32_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:0)
+    // Storage: Ad AuctionOf (r:1 w:1)
+    // Storage: Ad SealedBidOf (r:1 w:1)
+    // Storage: Ad SlotOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Nft Frozen (r:1 w:0)
+    // Storage: Ad BidsOf (r:0 w:1)
+    fn commit_bid() -> Weight {
+        (78_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(12 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(7 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:0)
+    // Storage: Ad AuctionOf (r:1 w:0)
+    // Storage: Ad SealedBidOf (r:1 w:1)
+    fn reveal_bid() -> Weight {
+        (35_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Ad AuctionOf (r:0 w:1)
+    // Storage: Ad SealedBidOf (r:1 w:1)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Ad SlotOf (r:1 w:1)
+    // Storage: Ad DeadlineOf (r:0 w:1)
+    // Storage: Ad DeadlineQueue (r:0 w:1)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: Ad BidsOf (r:0 w:1)
+    fn close_auction(n: u32, ) -> Weight {
+        (46_000_000 as Weight)
+            // Standard Error: 16_000
+            .saturating_add((38_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+    }
+    // Storage: Did DidOf (r:1 w:0)
     // Storage: Balances Reserves (r:1 w:0)
     // Storage: Ad EndtimeOf (r:1 w:0)
     // Storage: Ad Metadata (r:1 w:0)
@@ -209,9 +561,74 @@ impl WeightInfo for () {
     // Storage: Assets Asset (r:1 w:1)
     // Storage: Assets Account (r:2 w:2)
     // Storage: System Account (r:1 w:1)
-    fn pay() -> Weight {
+    // Storage: Did ReferrerOf (r:1 w:0)
+    // Storage: Nft Royalty (r:1 w:0)
+    fn pay(r: u32, ) -> Weight {
         (98_000_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(17 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(7 as Weight))
+            // Standard Error: 12_000
+            .saturating_add((22_000_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(RocksDbWeight::get().reads(20 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(8 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Balances Reserves (r:1 w:0)
+    // Storage: Ad EndtimeOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Ad DeadlineOf (r:1 w:0)
+    // Storage: Ad SlotOf (r:1 w:0)
+    // Storage: Ad Metadata (r:1 w:0)
+    // Storage: Ad Payout (r:1 w:1)
+    // Storage: Tag TagsOf (r:2 w:0)
+    // Storage: Tag PersonasOf (r:2 w:1)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Did ReferrerOf (r:1 w:0)
+    // Storage: Nft Royalty (r:1 w:0)
+    fn pay_batch(n: u32, r: u32, ) -> Weight {
+        (36_000_000 as Weight)
+            // Standard Error: 21_000
+            .saturating_add((71_000_000 as Weight).saturating_mul(n as Weight))
+            // Standard Error: 9_000
+            .saturating_add((22_000_000 as Weight).saturating_mul(n as Weight).saturating_mul(r as Weight))
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((13 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight).saturating_mul(r as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((7 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight).saturating_mul(r as Weight)))
+    }
+    // Storage: Ad PurgeQueue (r:1 w:0)
+    // Storage: Ad SweepCursor (r:1 w:1)
+    // Storage: Ad AuctionQueue (r:1 w:0)
+    // Storage: Ad DeadlineQueue (r:2 w:1)
+    // Storage: Ad DeadlineOf (r:1 w:1)
+    // Storage: Ad SlotOf (r:1 w:1)
+    // Storage: Ad Metadata (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Ad SlotsOf (r:1 w:1)
+    // Storage: Ad EndtimeQueue (r:1 w:0)
+    fn on_initialize(n: u32, ) -> Weight {
+        (4_000_000 as Weight)
+            // Standard Error: 18_000
+            .saturating_add((52_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((10 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((8 as Weight).saturating_mul(n as Weight)))
+    }
+    // Storage: Ad BidsOf (r:1 w:2)
+    // Storage: Ad SealedBidOf (r:1 w:1)
+    fn migrate_account(n: u32, ) -> Weight {
+        (2_000_000 as Weight)
+            // Standard Error: 4_000
+            .saturating_add((9_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
     }
 }
@@ -18,10 +18,13 @@ use frame_support::{
     dispatch::DispatchResult,
     ensure,
     traits::{Currency, EnsureOrigin, NamedReservableCurrency, OnUnbalanced},
+    weights::Weight,
     PalletId,
 };
 use parami_did::{EnsureDid, Pallet as Did};
-use sp_runtime::traits::Saturating;
+use parami_traits::{AccountMigration, Migrated, NamedReserveMigration};
+use sp_runtime::{traits::Saturating, DispatchError};
+use sp_std::prelude::*;
 
 use weights::WeightInfo;
 
@@ -166,7 +169,7 @@ impl<T: pallet::Config> EnsureOrigin<T::Origin> for EnsureAdvertiser<T> {
     type Success = (DidOf<T>, AccountOf<T>);
 
     fn try_origin(o: T::Origin) -> Result<Self::Success, T::Origin> {
-        use frame_support::traits::{Get, OriginTrait};
+        use frame_support::traits::OriginTrait;
 
         let (did, who) = EnsureDid::<T>::ensure_origin(o).or(Err(T::Origin::none()))?;
 
@@ -190,3 +193,23 @@ impl<T: pallet::Config> EnsureOrigin<T::Origin> for EnsureAdvertiser<T> {
         T::Origin::from(RawOrigin::Root)
     }
 }
+
+type ReserveMigration<T> = NamedReserveMigration<
+    CurrencyOf<T>,
+    <T as Config>::PalletId,
+    <T as frame_system::Config>::DbWeight,
+>;
+
+impl<T: Config> AccountMigration<AccountOf<T>> for Pallet<T> {
+    fn migrate(
+        src: &AccountOf<T>,
+        dest: &AccountOf<T>,
+        items: u32,
+    ) -> Result<Vec<Migrated>, DispatchError> {
+        ReserveMigration::<T>::migrate(src, dest, items)
+    }
+
+    fn migrate_weight(items: u32) -> Weight {
+        ReserveMigration::<T>::migrate_weight(items)
+    }
+}
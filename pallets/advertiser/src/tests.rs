@@ -1,5 +1,6 @@
 use crate::{mock::*, Blocked, EnsureAdvertiser, Error};
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::Currency};
+use parami_traits::{AccountMigration, Migrated};
 use sp_core::sr25519;
 
 #[test]
@@ -17,6 +18,26 @@ fn should_deposit() {
     });
 }
 
+#[test]
+fn should_migrate_deposit() {
+    new_test_ext().execute_with(|| {
+        let alice = sr25519::Public([1; 32]);
+        let bob = sr25519::Public([2; 32]);
+
+        assert_ok!(Advertiser::deposit(Origin::signed(alice), 10));
+        Balances::make_free_balance_be(&bob, 50);
+
+        assert_eq!(
+            Advertiser::migrate(&alice, &bob, 0),
+            Ok(vec![Migrated::Reserved(*b"prm/ader", 10)])
+        );
+
+        assert_eq!(Balances::reserved_balance(alice), 0);
+        assert_eq!(Balances::reserved_balance(bob), 10);
+        assert_eq!(Balances::free_balance(&bob), 50);
+    });
+}
+
 #[test]
 fn should_fail_when_insufficient() {
     new_test_ext().execute_with(|| {
@@ -24,13 +24,14 @@ use frame_support::{
         Currency, EnsureOrigin, ExistenceRequirement::AllowDeath, NamedReservableCurrency,
         StorageVersion,
     },
+    transactional,
 };
 use parami_did_utils::derive_storage_key;
-use parami_traits::Nfts;
+use parami_traits::{AccountMigration, Migrated};
 use sp_runtime::{
     traits::{
         Hash, LookupError, MaybeDisplay, MaybeMallocSizeOf, MaybeSerializeDeserialize, Member,
-        SimpleBitOps, StaticLookup,
+        SaturatedConversion, SimpleBitOps, StaticLookup, Zero,
     },
     DispatchError, MultiAddress,
 };
@@ -84,7 +85,8 @@ pub mod pallet {
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
 
-        type Nfts: Nfts<AccountOf<Self>>;
+        /// The assets and positions moved along with a DID
+        type Migrations: AccountMigration<AccountOf<Self>>;
     }
 
     #[pallet::pallet]
@@ -121,6 +123,13 @@ pub mod pallet {
         Revoked(T::DecentralizedId),
         /// DID transferred \[did, from, to\]
         Transferred(T::DecentralizedId, AccountOf<T>, AccountOf<T>),
+        /// Assets moved along with a DID \[did, from, to, items\]
+        Migrated(
+            T::DecentralizedId,
+            AccountOf<T>,
+            AccountOf<T>,
+            Vec<Migrated>,
+        ),
     }
 
     #[pallet::hooks]
@@ -164,11 +173,17 @@ pub mod pallet {
             Ok(())
         }
 
-        #[pallet::weight(T::WeightInfo::transfer())]
+        /// Transfer a DID with all assets and positions of its account.
+        ///
+        /// `items` bounds the storage items visited by each of the migrations.
+        #[pallet::weight(T::WeightInfo::transfer()
+            .saturating_add(T::Migrations::migrate_weight(*items)))]
+        #[transactional]
         pub fn force_transfer_with_assets(
             origin: OriginFor<T>,
             did: T::DecentralizedId,
             dest: AccountOf<T>,
+            #[pallet::compact] items: u32,
         ) -> DispatchResult {
             let _who = ensure_root(origin)?;
 
@@ -179,15 +194,26 @@ pub mod pallet {
             meta.account = dest.clone();
             meta.created = <frame_system::Pallet<T>>::block_number();
 
-            let ad3_balance = T::Currency::total_balance(&source);
-            T::Currency::transfer(&source, &dest, ad3_balance, AllowDeath)?;
-            T::Nfts::force_transfer_all_fractions(&source, &dest)?;
+            let mut migrated = Vec::new();
+
+            let free = T::Currency::free_balance(&source);
+            T::Currency::transfer(&source, &dest, free, AllowDeath)?;
+            if !free.is_zero() {
+                migrated.push(Migrated::Currency(free.saturated_into()));
+            }
+
+            migrated.extend(T::Migrations::migrate(&source, &dest, items)?);
 
             <Metadata<T>>::insert(did, meta);
             <DidOf<T>>::remove(&source);
             <DidOf<T>>::insert(dest.clone(), did);
 
-            Self::deposit_event(Event::<T>::Transferred(did.clone(), source, dest.clone()));
+            Self::deposit_event(Event::<T>::Transferred(
+                did.clone(),
+                source.clone(),
+                dest.clone(),
+            ));
+            Self::deposit_event(Event::<T>::Migrated(did, source, dest, migrated));
 
             Ok(())
         }
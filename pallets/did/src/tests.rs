@@ -1,6 +1,10 @@
 use crate::{mock::*, DidOf, EnsureDid, Error, Metadata, ReferrerOf};
-use frame_support::{assert_noop, assert_ok, traits::Currency};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{Currency, ReservableCurrency},
+};
 use parami_did_utils::derive_storage_key;
+use parami_traits::Migrated;
 use sp_core::offchain::{
     testing::{TestOffchainExt, TestPersistentOffchainDB},
     OffchainDbExt,
@@ -203,7 +207,8 @@ fn should_force_transfer_did() {
         assert_ok!(Did::force_transfer_with_assets(
             Origin::root(),
             DID_ALICE,
-            BOB
+            BOB,
+            0
         ));
 
         assert_eq!(<Test as crate::Config>::Currency::total_balance(&ALICE), 0);
@@ -213,11 +218,41 @@ fn should_force_transfer_did() {
     });
 }
 
+#[test]
+fn should_force_transfer_did_with_reserves() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(<Test as crate::Config>::Currency::reserve(&ALICE, 10));
+
+        assert_ok!(Did::force_transfer_with_assets(
+            Origin::root(),
+            DID_ALICE,
+            BOB,
+            0
+        ));
+
+        assert_eq!(<Test as crate::Config>::Currency::free_balance(&ALICE), 0);
+        assert_eq!(
+            <Test as crate::Config>::Currency::reserved_balance(&ALICE),
+            10
+        );
+        assert_eq!(<Test as crate::Config>::Currency::free_balance(&BOB), 190);
+
+        System::assert_last_event(Event::Did(crate::Event::Migrated(
+            DID_ALICE,
+            ALICE,
+            BOB,
+            vec![Migrated::Currency(90)],
+        )));
+    });
+}
+
 #[test]
 fn should_fail_force_transfer_did_if_not_root_user() {
     new_test_ext().execute_with(|| {
         assert_noop!(
-            Did::force_transfer_with_assets(Origin::signed(ALICE), DID_ALICE, BOB),
+            Did::force_transfer_with_assets(Origin::signed(ALICE), DID_ALICE, BOB, 0),
             DispatchError::BadOrigin
         );
     });
@@ -227,7 +262,7 @@ fn should_fail_force_transfer_did_if_not_root_user() {
 fn should_fail_force_transfer_did_if_did_not_exist() {
     new_test_ext().execute_with(|| {
         assert_noop!(
-            Did::force_transfer_with_assets(Origin::root(), DID_BOB, BOB),
+            Did::force_transfer_with_assets(Origin::root(), DID_BOB, BOB, 0),
             Error::<Test>::DidNotExists,
         );
     });
@@ -238,7 +273,7 @@ fn should_fail_force_transfer_did_if_dest_bind_did() {
     new_test_ext().execute_with(|| {
         assert_ok!(Did::register(Origin::signed(BOB), None));
         assert_noop!(
-            Did::force_transfer_with_assets(Origin::root(), DID_ALICE, BOB),
+            Did::force_transfer_with_assets(Origin::root(), DID_ALICE, BOB, 0),
             Error::<Test>::DidExists,
         );
     });
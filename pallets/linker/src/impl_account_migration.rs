@@ -0,0 +1,26 @@
+use crate::{AccountOf, Config, CurrencyOf, Pallet};
+
+use frame_support::weights::Weight;
+use parami_traits::{AccountMigration, Migrated, NamedReserveMigration};
+use sp_runtime::DispatchError;
+use sp_std::prelude::*;
+
+type ReserveMigration<T> = NamedReserveMigration<
+    CurrencyOf<T>,
+    <T as Config>::PalletId,
+    <T as frame_system::Config>::DbWeight,
+>;
+
+impl<T: Config> AccountMigration<AccountOf<T>> for Pallet<T> {
+    fn migrate(
+        src: &AccountOf<T>,
+        dest: &AccountOf<T>,
+        items: u32,
+    ) -> Result<Vec<Migrated>, DispatchError> {
+        ReserveMigration::<T>::migrate(src, dest, items)
+    }
+
+    fn migrate_weight(items: u32) -> Weight {
+        ReserveMigration::<T>::migrate_weight(items)
+    }
+}
@@ -19,6 +19,7 @@ mod benchmarking;
 mod btc;
 mod did;
 mod functions;
+mod impl_account_migration;
 mod impl_links;
 mod migrations;
 mod ocw;
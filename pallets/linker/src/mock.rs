@@ -112,7 +112,7 @@ impl parami_did::Config for Test {
     type DecentralizedId = H160;
     type Hashing = Keccak256;
     type WeightInfo = ();
-    type Nfts = ();
+    type Migrations = ();
 }
 
 impl parami_ocw::Config for Test {}
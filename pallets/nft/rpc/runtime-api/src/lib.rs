@@ -0,0 +1,115 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use parami_primitives::{BalanceWrapper, Network};
+use sp_runtime::traits::{MaybeDisplay, MaybeFromStr};
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+pub type ApiResult<T> = Result<T, DispatchError>;
+
+sp_api::decl_runtime_apis! {
+    pub trait NftRuntimeApi<AssetId, Balance, DecentralizedId, Height>
+    where
+        AssetId: Codec,
+        Balance: Codec + MaybeDisplay + MaybeFromStr,
+        DecentralizedId: Codec,
+        Height: Codec,
+    {
+        /// Get crowdfunding state of a NFT
+        ///
+        /// # Arguments
+        ///
+        /// * `nft_id` - The NFT ID
+        ///
+        /// # Results
+        ///
+        /// tuple of (pot, deposit, required)
+        ///
+        /// * `pot` - The balance of the pot
+        /// * `deposit` - The total deposit of supporters
+        /// * `required` - The deposit required to mint
+        fn crowdfunding_of(
+            nft_id: AssetId,
+        ) -> ApiResult<(
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+        )>;
+
+        /// Get shares of supporters of a NFT
+        ///
+        /// # Arguments
+        ///
+        /// * `nft_id` - The NFT ID
+        /// * `start_after` - The DID to continue after, from the last page
+        /// * `limit` - The maximum number of supporters to return
+        ///
+        /// # Results
+        ///
+        /// list of tuple of (did, deposit, tokens, claimable)
+        ///
+        /// * `did` - The DID of the supporter
+        /// * `deposit` - The deposit of the supporter
+        /// * `tokens` - The amount of fractions entitled, zero until minted
+        /// * `claimable` - The amount of fractions can be claimed now
+        fn shares_of(
+            nft_id: AssetId,
+            start_after: Option<DecentralizedId>,
+            limit: u32,
+        ) -> ApiResult<Vec<(
+            DecentralizedId,
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+        )>>;
+
+        /// Get the end of lockup of the owner of a NFT
+        ///
+        /// # Arguments
+        ///
+        /// * `nft_id` - The NFT ID
+        ///
+        /// # Results
+        ///
+        /// The block number at which fractions of the owner are unlocked
+        fn lockup_of(
+            nft_id: AssetId,
+        ) -> ApiResult<Height>;
+
+        /// Get pending imports of a DID
+        ///
+        /// # Arguments
+        ///
+        /// * `did` - The DID of the owner
+        /// * `start_after` - The (network, namespace, token) to continue after, from the last page
+        /// * `limit` - The maximum number of imports to return
+        ///
+        /// # Results
+        ///
+        /// list of tuple of (network, namespace, token, deadline)
+        ///
+        /// * `network` - The network of the NFT
+        /// * `namespace` - The contract address of the NFT
+        /// * `token` - The token ID of the NFT
+        /// * `deadline` - The block number at which the import expires
+        fn porting_of(
+            did: DecentralizedId,
+            start_after: Option<(Network, Vec<u8>, Vec<u8>)>,
+            limit: u32,
+        ) -> ApiResult<Vec<(Network, Vec<u8>, Vec<u8>, Height)>>;
+
+        /// Get the NFT which minted fragments of a symbol
+        ///
+        /// # Arguments
+        ///
+        /// * `symbol` - The symbol of fragments, case-insensitive
+        ///
+        /// # Results
+        ///
+        /// The NFT ID, or none if the symbol is not in use
+        fn symbol_of(
+            symbol: Vec<u8>,
+        ) -> ApiResult<Option<AssetId>>;
+    }
+}
@@ -0,0 +1,305 @@
+use codec::Codec;
+use jsonrpsee::{
+    core::{async_trait, Error, RpcResult},
+    proc_macros::rpc,
+    types::error::{CallError, ErrorObject, INTERNAL_ERROR_CODE},
+};
+pub use parami_nft_rpc_runtime_api::{ApiResult, NftRuntimeApi};
+use parami_primitives::{BalanceWrapper, Network};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{
+    generic::BlockId,
+    traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
+};
+use std::sync::Arc;
+
+#[rpc(client, server)]
+pub trait NftApi<BlockHash, AssetId, Balance, DecentralizedId, Height>
+where
+    Balance: MaybeDisplay + MaybeFromStr,
+{
+    /// Get crowdfunding state of a NFT
+    ///
+    /// # Arguments
+    ///
+    /// * `nft_id` - The NFT ID
+    ///
+    /// # Results
+    ///
+    /// tuple of (pot, deposit, required)
+    ///
+    /// * `pot` - The balance of the pot
+    /// * `deposit` - The total deposit of supporters
+    /// * `required` - The deposit required to mint
+    #[method(name = "nft_getCrowdfunding")]
+    fn crowdfunding_of(
+        &self,
+        nft_id: AssetId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<(
+        BalanceWrapper<Balance>,
+        BalanceWrapper<Balance>,
+        BalanceWrapper<Balance>,
+    )>;
+
+    /// Get shares of supporters of a NFT
+    ///
+    /// # Arguments
+    ///
+    /// * `nft_id` - The NFT ID
+    /// * `start_after` - The DID to continue after, from the last page
+    /// * `limit` - The maximum number of supporters to return
+    ///
+    /// # Results
+    ///
+    /// list of tuple of (did, deposit, tokens, claimable)
+    ///
+    /// * `did` - The DID of the supporter
+    /// * `deposit` - The deposit of the supporter
+    /// * `tokens` - The amount of fractions entitled, zero until minted
+    /// * `claimable` - The amount of fractions can be claimed now
+    #[method(name = "nft_getSharesOf")]
+    fn shares_of(
+        &self,
+        nft_id: AssetId,
+        start_after: Option<DecentralizedId>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<
+        Vec<(
+            DecentralizedId,
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+        )>,
+    >;
+
+    /// Get the end of lockup of the owner of a NFT
+    ///
+    /// # Arguments
+    ///
+    /// * `nft_id` - The NFT ID
+    ///
+    /// # Results
+    ///
+    /// The block number at which fractions of the owner are unlocked
+    #[method(name = "nft_getLockupOf")]
+    fn lockup_of(&self, nft_id: AssetId, at: Option<BlockHash>) -> RpcResult<Height>;
+
+    /// Get pending imports of a DID
+    ///
+    /// # Arguments
+    ///
+    /// * `did` - The DID of the owner
+    /// * `start_after` - The (network, namespace, token) to continue after, from the last page
+    /// * `limit` - The maximum number of imports to return
+    ///
+    /// # Results
+    ///
+    /// list of tuple of (network, namespace, token, deadline)
+    ///
+    /// * `network` - The network of the NFT
+    /// * `namespace` - The contract address of the NFT
+    /// * `token` - The token ID of the NFT
+    /// * `deadline` - The block number at which the import expires
+    #[method(name = "nft_getPortingOf")]
+    fn porting_of(
+        &self,
+        did: DecentralizedId,
+        start_after: Option<(Network, Bytes, Bytes)>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(Network, Bytes, Bytes, Height)>>;
+
+    /// Get the NFT which minted fragments of a symbol
+    ///
+    /// # Arguments
+    ///
+    /// * `symbol` - The symbol of fragments, case-insensitive
+    ///
+    /// # Results
+    ///
+    /// The NFT ID, or none if the symbol is not in use
+    #[method(name = "nft_getSymbolOf")]
+    fn symbol_of(&self, symbol: Bytes, at: Option<BlockHash>) -> RpcResult<Option<AssetId>>;
+}
+
+pub struct NftRpcHandler<C, Block, AssetId, Balance, DecentralizedId, Height> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<(Block, AssetId, Balance, DecentralizedId, Height)>,
+}
+
+impl<C, Block, AssetId, Balance, DecentralizedId, Height>
+    NftRpcHandler<C, Block, AssetId, Balance, DecentralizedId, Height>
+{
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl<C, Block, AssetId, Balance, DecentralizedId, Height>
+    NftApiServer<<Block as BlockT>::Hash, AssetId, Balance, DecentralizedId, Height>
+    for NftRpcHandler<C, Block, AssetId, Balance, DecentralizedId, Height>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: NftRuntimeApi<Block, AssetId, Balance, DecentralizedId, Height>,
+    AssetId: Codec + Send + Sync + 'static,
+    Balance: Codec + MaybeDisplay + MaybeFromStr + Send + Sync + 'static,
+    DecentralizedId: Codec + Send + Sync + 'static,
+    Height: Codec + Send + Sync + 'static,
+{
+    fn crowdfunding_of(
+        &self,
+        nft_id: AssetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<(
+        BalanceWrapper<Balance>,
+        BalanceWrapper<Balance>,
+        BalanceWrapper<Balance>,
+    )> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api.crowdfunding_of(&at, nft_id).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get crowdfunding.",
+                Some(format!("{:?}", e)),
+            )))
+        })?;
+
+        res.map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get crowdfunding.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    fn shares_of(
+        &self,
+        nft_id: AssetId,
+        start_after: Option<DecentralizedId>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<
+        Vec<(
+            DecentralizedId,
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+        )>,
+    > {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api
+            .shares_of(&at, nft_id, start_after, limit)
+            .map_err(|e| {
+                Error::Call(CallError::Custom(ErrorObject::owned(
+                    INTERNAL_ERROR_CODE,
+                    "Unable to get shares.",
+                    Some(format!("{:?}", e)),
+                )))
+            })?;
+
+        res.map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get shares.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    fn lockup_of(&self, nft_id: AssetId, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Height> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api.lockup_of(&at, nft_id).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get lockup.",
+                Some(format!("{:?}", e)),
+            )))
+        })?;
+
+        res.map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get lockup.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    fn porting_of(
+        &self,
+        did: DecentralizedId,
+        start_after: Option<(Network, Bytes, Bytes)>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(Network, Bytes, Bytes, Height)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let start_after = start_after
+            .map(|(network, namespace, token)| (network, namespace.to_vec(), token.to_vec()));
+
+        let res = api.porting_of(&at, did, start_after, limit).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get porting.",
+                Some(format!("{:?}", e)),
+            )))
+        })?;
+
+        let res = res.map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get porting.",
+                Some(format!("{:?}", e)),
+            )))
+        })?;
+
+        Ok(res
+            .into_iter()
+            .map(|(network, namespace, token, deadline)| {
+                (network, namespace.into(), token.into(), deadline)
+            })
+            .collect())
+    }
+
+    fn symbol_of(
+        &self,
+        symbol: Bytes,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<AssetId>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api.symbol_of(&at, symbol.to_vec()).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get symbol.",
+                Some(format!("{:?}", e)),
+            )))
+        })?;
+
+        res.map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get symbol.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+}
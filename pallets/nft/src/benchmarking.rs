@@ -3,7 +3,7 @@ use super::*;
 #[allow(unused)]
 use crate::Pallet as Nft;
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
-use frame_support::traits::tokens::fungibles::Inspect;
+use frame_support::traits::tokens::fungibles::{Inspect, Mutate};
 use frame_system::RawOrigin;
 use parami_did::Pallet as Did;
 use parami_linker::Pallet as Linker;
@@ -24,19 +24,37 @@ benchmarks! {
         let did = Did::<T>::did_of(&caller).unwrap();
 
         Linker::<T>::submit_link(RawOrigin::None.into(), did, Network::Ethereum, vec![1u8; 20], true)?;
-    }: _(RawOrigin::Signed(caller.clone()), Network::Ethereum, vec![1u8; 20], vec![1u8; 32])
+
+        <Presets<T>>::insert(1, Nft::<T>::profile_of(Zero::zero()));
+
+        let funding = types::Funding {
+            goal: T::InitialMintingDeposit::get(),
+            deadline: HeightOf::<T>::max_value(),
+        };
+    }: _(RawOrigin::Signed(caller.clone()), Network::Ethereum, vec![1u8; 20], vec![1u8; 32], Some(1), Some(funding))
     verify {
         assert_ne!(<Porting<T>>::get((Network::Ethereum, &vec![1u8; 20], &vec![1u8; 32])), None);
+        assert_ne!(<PortingProfile<T>>::get((Network::Ethereum, &vec![1u8; 20], &vec![1u8; 32])), None);
+        assert_ne!(<PortingFunding<T>>::get((Network::Ethereum, &vec![1u8; 20], &vec![1u8; 32])), None);
     }
 
     kick {
         let caller: T::AccountId = whitelisted_caller();
 
         Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
-    }: _(RawOrigin::Signed(caller.clone()))
+
+        <Presets<T>>::insert(1, Nft::<T>::profile_of(Zero::zero()));
+
+        let funding = types::Funding {
+            goal: T::InitialMintingDeposit::get(),
+            deadline: HeightOf::<T>::max_value(),
+        };
+    }: _(RawOrigin::Signed(caller.clone()), Some(1), Some(funding))
     verify {
         let did = Did::<T>::did_of(&caller).unwrap();
         assert_eq!(<Preferred<T>>::get(&did), Some(Zero::zero()));
+        assert_ne!(<Profile<T>>::get(Zero::zero()), None);
+        assert_ne!(<Funding<T>>::get(Zero::zero()), None);
     }
 
     back {
@@ -54,7 +72,7 @@ benchmarks! {
         Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
         Did::<T>::register(RawOrigin::Signed(kol.clone()).into(), None)?;
 
-        Nft::<T>::kick(RawOrigin::Signed(kol.clone()).into())?;
+        Nft::<T>::kick(RawOrigin::Signed(kol.clone()).into(), None, None)?;
 
         let did = Did::<T>::did_of(&kol).unwrap();
         let nft = <Preferred<T>>::get(&did).unwrap();
@@ -64,12 +82,44 @@ benchmarks! {
         assert_eq!(T::Currency::free_balance(&meta.pot), pot);
     }
 
+    withdraw_backing {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let kol: T::AccountId = account("kol", 1, 1);
+
+        let max = BalanceOf::<T>::max_value();
+        let min = T::Currency::minimum_balance();
+        let pot = min.saturating_mul(1_000_000u32.into());
+
+        T::Currency::make_free_balance_be(&caller, max);
+        T::Currency::make_free_balance_be(&kol, pot);
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        Did::<T>::register(RawOrigin::Signed(kol.clone()).into(), None)?;
+
+        let goal = T::InitialMintingDeposit::get().saturating_add(pot);
+        let deadline = <frame_system::Pallet<T>>::block_number().saturating_add(One::one());
+        let funding = types::Funding { goal, deadline };
+        Nft::<T>::kick(RawOrigin::Signed(kol.clone()).into(), None, Some(funding))?;
+
+        let did = Did::<T>::did_of(&kol).unwrap();
+        let nft = <Preferred<T>>::get(&did).unwrap();
+
+        Nft::<T>::back(RawOrigin::Signed(caller.clone()).into(), nft, pot)?;
+
+        <frame_system::Pallet<T>>::set_block_number(deadline);
+    }: _(RawOrigin::Signed(caller.clone()), nft)
+    verify {
+        let did = Did::<T>::did_of(&caller).unwrap();
+        assert_eq!(<Deposits<T>>::get(nft, &did), None);
+    }
+
     mint {
         let n in 1 .. 1000 - 4;
         let s in 1 .. 1000 - 4;
 
         let name = vec![b'x'; n as usize];
-        let symbol = vec![b'x'; n as usize];
+        let symbol = vec![b'x'; s as usize];
 
         let caller: T::AccountId = whitelisted_caller();
 
@@ -85,7 +135,7 @@ benchmarks! {
         Did::<T>::register(RawOrigin::Signed(supporter.clone()).into(), None)?;
         Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
 
-        Nft::<T>::kick(RawOrigin::Signed(caller.clone()).into())?;
+        Nft::<T>::kick(RawOrigin::Signed(caller.clone()).into(), None, None)?;
 
         let did = Did::<T>::did_of(&caller).unwrap();
         let nft = <Preferred<T>>::get(&did).unwrap();
@@ -112,7 +162,7 @@ benchmarks! {
         Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
         Did::<T>::register(RawOrigin::Signed(kol.clone()).into(), None)?;
 
-        Nft::<T>::kick(RawOrigin::Signed(kol.clone()).into())?;
+        Nft::<T>::kick(RawOrigin::Signed(kol.clone()).into(), None, None)?;
 
         let did = Did::<T>::did_of(&kol).unwrap();
         let nft = <Preferred<T>>::get(&did).unwrap();
@@ -120,6 +170,11 @@ benchmarks! {
         Nft::<T>::back(RawOrigin::Signed(caller.clone()).into(), nft, pot)?;
 
         Nft::<T>::mint(RawOrigin::Signed(kol).into(), nft, b"Test Token".to_vec(), b"XTT".to_vec())?;
+
+        let vested = frame_system::Pallet::<T>::block_number()
+            .saturating_add(T::InitialMintingVestingCliff::get())
+            .saturating_add(T::InitialMintingVestingPeriod::get());
+        frame_system::Pallet::<T>::set_block_number(vested);
     }: _(RawOrigin::Signed(caller.clone()), nft)
     verify {
         assert!(T::Assets::balance(nft, &caller) > Zero::zero());
@@ -133,12 +188,181 @@ benchmarks! {
 
         Linker::<T>::submit_link(RawOrigin::None.into(), did, Network::Ethereum, vec![1u8; 20], true)?;
 
-        Nft::<T>::port(RawOrigin::Signed(caller).into(), Network::Ethereum, vec![1u8; 20], vec![1u8; 32])?;
+        <Presets<T>>::insert(1, Nft::<T>::profile_of(Zero::zero()));
+
+        let funding = types::Funding {
+            goal: T::InitialMintingDeposit::get(),
+            deadline: HeightOf::<T>::max_value(),
+        };
+        Nft::<T>::port(RawOrigin::Signed(caller).into(), Network::Ethereum, vec![1u8; 20], vec![1u8; 32], Some(1), Some(funding))?;
     }: _(RawOrigin::None, did, Network::Ethereum, vec![1u8; 20], vec![1u8; 32], true)
     verify {
         assert_eq!(<Porting<T>>::get((Network::Ethereum, &vec![1u8; 20], &vec![1u8; 32])), None);
         assert_ne!(<Ported<T>>::get((Network::Ethereum, &vec![1u8; 20], &vec![1u8; 32])), None);
     }
+
+    report_ownership_lost {
+        let caller: T::AccountId = whitelisted_caller();
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        Nft::<T>::kick(RawOrigin::Signed(caller.clone()).into(), None, None)?;
+
+        let did = Did::<T>::did_of(&caller).unwrap();
+        let nft = <Preferred<T>>::get(&did).unwrap();
+
+        <External<T>>::insert(nft, types::External {
+            owner: did,
+            network: Network::Ethereum,
+            namespace: vec![1u8; 20],
+            token: vec![1u8; 32],
+        });
+    }: _(RawOrigin::None, nft)
+    verify {
+        assert!(<Frozen<T>>::contains_key(nft));
+    }
+
+    unport {
+        let caller: T::AccountId = whitelisted_caller();
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        Nft::<T>::kick(RawOrigin::Signed(caller.clone()).into(), None, None)?;
+
+        let did = Did::<T>::did_of(&caller).unwrap();
+        let nft = <Preferred<T>>::get(&did).unwrap();
+
+        <External<T>>::insert(nft, types::External {
+            owner: did,
+            network: Network::Ethereum,
+            namespace: vec![1u8; 20],
+            token: vec![1u8; 32],
+        });
+        <Ported<T>>::insert((Network::Ethereum, vec![1u8; 20], vec![1u8; 32]), nft);
+    }: _(RawOrigin::Signed(caller), nft)
+    verify {
+        assert!(<Frozen<T>>::contains_key(nft));
+        assert_eq!(<Ported<T>>::get((Network::Ethereum, vec![1u8; 20], vec![1u8; 32])), None);
+    }
+
+    transfer {
+        let caller: T::AccountId = whitelisted_caller();
+        let dest: T::AccountId = account("dest", 1, 1);
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        Did::<T>::register(RawOrigin::Signed(dest.clone()).into(), None)?;
+        Nft::<T>::kick(RawOrigin::Signed(caller.clone()).into(), None, None)?;
+
+        let did = Did::<T>::did_of(&caller).unwrap();
+        let dest = Did::<T>::did_of(&dest).unwrap();
+        let nft = <Preferred<T>>::get(&did).unwrap();
+    }: _(RawOrigin::Signed(caller), nft, dest)
+    verify {
+        assert_eq!(<Metadata<T>>::get(nft).unwrap().owner, dest);
+    }
+
+    set_preferred {
+        let caller: T::AccountId = whitelisted_caller();
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        Nft::<T>::kick(RawOrigin::Signed(caller.clone()).into(), None, None)?;
+
+        let did = Did::<T>::did_of(&caller).unwrap();
+        let nft = <Preferred<T>>::get(&did).unwrap();
+
+        <Preferred<T>>::remove(&did);
+    }: _(RawOrigin::Signed(caller), nft)
+    verify {
+        assert_eq!(<Preferred<T>>::get(&did), Some(nft));
+    }
+
+    set_royalty {
+        let caller: T::AccountId = whitelisted_caller();
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+        Nft::<T>::kick(RawOrigin::Signed(caller.clone()).into(), None, None)?;
+
+        let did = Did::<T>::did_of(&caller).unwrap();
+        let nft = <Preferred<T>>::get(&did).unwrap();
+
+        let royalty = T::MaxRoyalty::get();
+    }: _(RawOrigin::Signed(caller), nft, royalty)
+    verify {
+        assert_eq!(<Royalty<T>>::get(nft), royalty);
+    }
+
+    set_preset {
+        let profile = Nft::<T>::profile_of(Zero::zero());
+    }: _(RawOrigin::Root, 1, Some(profile))
+    verify {
+        assert_ne!(<Presets<T>>::get(1), None);
+    }
+
+    set_reserved_symbol {
+        let caller: T::AccountId = whitelisted_caller();
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+
+        let did = Did::<T>::did_of(&caller).unwrap();
+    }: _(RawOrigin::Root, b"XTT".to_vec(), Some(did))
+    verify {
+        assert_eq!(<ReservedSymbols<T>>::get(b"XTT".to_vec()), Some(did));
+    }
+
+    migrate_account {
+        let n in 1 .. 100;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let dest: T::AccountId = account("dest", 0, 0);
+
+        let max = BalanceOf::<T>::max_value();
+        let min = T::Currency::minimum_balance();
+        let pot = min.saturating_mul(1_000_000_000u32.into());
+
+        T::Currency::make_free_balance_be(&caller, max);
+        T::Currency::make_free_balance_be(&dest, pot);
+
+        Did::<T>::register(RawOrigin::Signed(caller.clone()).into(), None)?;
+
+        let mut nfts = vec![];
+        for i in 0 .. n {
+            let kol: T::AccountId = account("kol", i, i);
+            T::Currency::make_free_balance_be(&kol, pot);
+
+            Did::<T>::register(RawOrigin::Signed(kol.clone()).into(), None)?;
+            Nft::<T>::kick(RawOrigin::Signed(kol.clone()).into(), None, None)?;
+
+            let did = Did::<T>::did_of(&kol).unwrap();
+            let nft = <Preferred<T>>::get(&did).unwrap();
+
+            Nft::<T>::back(RawOrigin::Signed(caller.clone()).into(), nft, pot)?;
+
+            // symbols of fragments are unique, suffix them with the index in letters
+            let mut symbol = b"XT".to_vec();
+            let mut k = i;
+            loop {
+                symbol.push(b'A' + (k % 26) as u8);
+                k /= 26;
+                if k == 0 {
+                    break;
+                }
+            }
+
+            Nft::<T>::mint(RawOrigin::Signed(kol).into(), nft, b"Test Token".to_vec(), symbol)?;
+
+            T::Assets::mint_into(nft, &caller, pot)?;
+            Nft::<T>::note_holder(nft, &caller);
+
+            nfts.push(nft);
+        }
+
+        let items = <FractionsOf<T>>::iter_prefix(&caller).count() as u32;
+    }: {
+        Nft::<T>::migrate(&caller, &dest, items)?;
+    }
+    verify {
+        for nft in nfts {
+            assert!(T::Assets::balance(nft, &caller).is_zero());
+        }
+    }
 }
 
 impl_benchmark_test_suite!(Did, crate::mock::new_test_ext(), crate::mock::Test);
@@ -1,6 +1,6 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-pub use ocw::eth_abi;
+pub use ocw::{eth_abi, Erc1155, Erc721, Evm, Metaplex, Nep171, Validator, Validators};
 pub use pallet::*;
 
 #[rustfmt::skip]
@@ -19,6 +19,8 @@ mod migrations;
 mod ocw;
 mod types;
 
+pub mod validation;
+
 use frame_support::{
     dispatch::DispatchResult,
     ensure,
@@ -26,26 +28,28 @@ use frame_support::{
         tokens::{
             fungibles::{
                 metadata::Mutate as FungMetaMutate, Create as FungCreate, Inspect,
-                Mutate as FungMutate, Transfer as FungTransfer,
+                InspectMetadata as FungMetaInspect, Mutate as FungMutate, Transfer as FungTransfer,
             },
             nonfungibles::{Create as NftCreate, Mutate as NftMutate},
         },
         Currency, EnsureOrigin,
-        ExistenceRequirement::KeepAlive,
+        ExistenceRequirement::{AllowDeath, KeepAlive},
         Get, StorageVersion,
     },
+    weights::Weight,
     PalletId,
 };
 use frame_system::offchain::SendTransactionTypes;
 use parami_did::EnsureDid;
 use parami_traits::{
     types::{Network, Task},
-    Links, Nfts, Swaps,
+    AccountMigration, Holders, Links, Migrated, Royalties, Swaps,
 };
 use sp_core::U512;
 use sp_runtime::{
     traits::{
-        AccountIdConversion, AtLeast32BitUnsigned, Bounded, CheckedAdd, One, Saturating, Zero,
+        AccountIdConversion, AtLeast32BitUnsigned, Bounded, CheckedAdd, One, SaturatedConversion,
+        Saturating, Zero,
     },
     DispatchError, RuntimeDebug,
 };
@@ -61,12 +65,17 @@ type AssetOf<T> = <T as Config>::AssetId;
 type BalanceOf<T> = <<T as parami_did::Config>::Currency as Currency<AccountOf<T>>>::Balance;
 type DidOf<T> = <T as parami_did::Config>::DecentralizedId;
 type ExternalOf<T> = types::External<DidOf<T>>;
+type FundingOf<T> = types::Funding<BalanceOf<T>, HeightOf<T>>;
 type HeightOf<T> = <T as frame_system::Config>::BlockNumber;
 type MetaOf<T> = types::Metadata<DidOf<T>, AccountOf<T>, NftOf<T>, AssetOf<T>>;
 type NftOf<T> = <T as Config>::AssetId;
+type ProfileOf<T> = types::Profile<BalanceOf<T>>;
 type TaskOf<T> = Task<ExternalOf<T>, HeightOf<T>>;
 
-const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
+
+/// The maximum number of items returned by a paginated query
+const MAX_PAGE_SIZE: u32 = 100;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -96,6 +105,7 @@ pub mod pallet {
 
         /// The assets trait to create, mint, and transfer fragments (fungible token)
         type Assets: FungCreate<AccountOf<Self>, AssetId = AssetOf<Self>>
+            + FungMetaInspect<AccountOf<Self>, AssetId = AssetOf<Self>>
             + FungMetaMutate<AccountOf<Self>, AssetId = AssetOf<Self>>
             + FungMutate<AccountOf<Self>, AssetId = AssetOf<Self>, Balance = BalanceOf<Self>>
             + FungTransfer<AccountOf<Self>, AssetId = AssetOf<Self>, Balance = BalanceOf<Self>>;
@@ -108,6 +118,14 @@ pub mod pallet {
         #[pallet::constant]
         type InitialMintingLockupPeriod: Get<HeightOf<Self>>;
 
+        /// The vesting cliff for fragments, nothing can be claimed before this period
+        #[pallet::constant]
+        type InitialMintingVestingCliff: Get<HeightOf<Self>>;
+
+        /// The vesting period for fragments, they are released linearly during this period
+        #[pallet::constant]
+        type InitialMintingVestingPeriod: Get<HeightOf<Self>>;
+
         /// The ICO value base of fragments, system will mint triple of the value
         /// once for KOL, once to swaps, once to supporters
         /// The maximum value of fragments is decuple of this value
@@ -121,6 +139,10 @@ pub mod pallet {
         /// The links trait
         type Links: Links<DidOf<Self>>;
 
+        /// The maximum royalty of fragments in basis points
+        #[pallet::constant]
+        type MaxRoyalty: Get<u32>;
+
         /// The NFT trait to create, mint non-fungible token
         type Nft: NftCreate<AccountOf<Self>, ItemId = NftOf<Self>, CollectionId = NftOf<Self>>
             + NftMutate<AccountOf<Self>, ItemId = NftOf<Self>, CollectionId = NftOf<Self>>;
@@ -133,6 +155,21 @@ pub mod pallet {
         #[pallet::constant]
         type PendingLifetime: Get<HeightOf<Self>>;
 
+        /// Interval of re-verifying ownership of ported NFTs, zero to disable
+        #[pallet::constant]
+        type ReverifyInterval: Get<HeightOf<Self>>;
+
+        /// Maximum ported NFTs re-verified by the offchain worker in a block
+        #[pallet::constant]
+        type MaxReverifyPerBlock: Get<u32>;
+
+        /// Ownership validators of the networks supported for importing
+        type Validators: Validators;
+
+        /// The treasury account, receives the treasury cut of initial minting
+        #[pallet::constant]
+        type TreasuryAccount: Get<AccountOf<Self>>;
+
         /// The maximum length of a name or symbol stored on-chain.
         #[pallet::constant]
         type StringLimit: Get<u32>;
@@ -170,6 +207,82 @@ pub mod pallet {
         BalanceOf<T>,
     >;
 
+    /// Fragments claimed by supporter
+    #[pallet::storage]
+    pub(super) type Claimed<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        NftOf<T>,
+        Identity,
+        T::DecentralizedId, // Supporter
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    /// Fraction tokens received by an account, moved in an account migration
+    #[pallet::storage]
+    #[pallet::getter(fn fractions_of)]
+    pub(super) type FractionsOf<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        AccountOf<T>,
+        Twox64Concat,
+        NftOf<T>, // Fraction Token ID
+        (),
+    >;
+
+    /// Crowdfunding goal and deadline
+    #[pallet::storage]
+    #[pallet::getter(fn funding_of)]
+    pub(super) type Funding<T: Config> = StorageMap<_, Twox64Concat, NftOf<T>, FundingOf<T>>;
+
+    /// Governance approved minting profiles
+    #[pallet::storage]
+    #[pallet::getter(fn preset)]
+    pub(super) type Presets<T: Config> = StorageMap<_, Twox64Concat, u32, ProfileOf<T>>;
+
+    /// Minting profile chosen for NFT
+    #[pallet::storage]
+    pub(super) type Profile<T: Config> = StorageMap<_, Twox64Concat, NftOf<T>, ProfileOf<T>>;
+
+    /// Royalty of NFT in basis points, paid out of swap fees and advertisement payouts
+    #[pallet::storage]
+    #[pallet::getter(fn royalty)]
+    pub(super) type Royalty<T: Config> = StorageMap<_, Twox64Concat, NftOf<T>, u32, ValueQuery>;
+
+    /// Symbols of minted fragments, normalized to upper case
+    #[pallet::storage]
+    pub(super) type Symbols<T: Config> = StorageMap<_, Blake2_128Concat, Vec<u8>, NftOf<T>>;
+
+    /// Symbols reserved for the NFTs of a brand, normalized to upper case
+    #[pallet::storage]
+    pub(super) type ReservedSymbols<T: Config> =
+        StorageMap<_, Blake2_128Concat, Vec<u8>, T::DecentralizedId>;
+
+    /// Minting profile chosen for importing in progress
+    #[pallet::storage]
+    pub(super) type PortingProfile<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Twox64Concat, Network>,
+            NMapKey<Blake2_128, Vec<u8>>, // Namespace
+            NMapKey<Blake2_128, Vec<u8>>, // Token
+        ),
+        ProfileOf<T>,
+    >;
+
+    /// Crowdfunding goal and deadline chosen for importing in progress
+    #[pallet::storage]
+    pub(super) type PortingFunding<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Twox64Concat, Network>,
+            NMapKey<Blake2_128, Vec<u8>>, // Namespace
+            NMapKey<Blake2_128, Vec<u8>>, // Token
+        ),
+        FundingOf<T>,
+    >;
+
     /// Importing in progress
     #[pallet::storage]
     pub(super) type Porting<T: Config> = StorageNMap<
@@ -182,6 +295,17 @@ pub mod pallet {
         TaskOf<T>,
     >;
 
+    /// Importing in progress of a DID
+    #[pallet::storage]
+    pub(super) type PortingOf<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        T::DecentralizedId, // Owner
+        Blake2_128Concat,
+        (Network, Vec<u8>, Vec<u8>), // Network, Namespace, Token
+        (),
+    >;
+
     /// Ported NFTs
     #[pallet::storage]
     pub(super) type Ported<T: Config> = StorageNMap<
@@ -198,6 +322,10 @@ pub mod pallet {
     #[pallet::storage]
     pub(super) type External<T: Config> = StorageMap<_, Twox64Concat, NftOf<T>, ExternalOf<T>>;
 
+    /// Imported NFTs no longer held by the owner, frozen since height
+    #[pallet::storage]
+    pub(super) type Frozen<T: Config> = StorageMap<_, Twox64Concat, NftOf<T>, HeightOf<T>>;
+
     /// Metadata
     #[pallet::storage]
     #[pallet::getter(fn meta)]
@@ -212,6 +340,10 @@ pub mod pallet {
     #[pallet::storage]
     pub(super) type Date<T: Config> = StorageMap<_, Twox64Concat, NftOf<T>, HeightOf<T>>;
 
+    /// KOL who minted the NFT, vesting the fragments of the owner
+    #[pallet::storage]
+    pub(super) type Minter<T: Config> = StorageMap<_, Twox64Concat, NftOf<T>, DidOf<T>>;
+
     #[pallet::type_value]
     pub(crate) fn DefaultId<T: Config>() -> NftOf<T> {
         One::one()
@@ -245,6 +377,34 @@ pub mod pallet {
         ),
         /// Import NFT Failed \[did, network, namespace, token_id\]
         ImportFailed(T::DecentralizedId, Network, Vec<u8>, Vec<u8>),
+        /// Crowdfunding started \[instance, goal, deadline\]
+        FundingStarted(NftOf<T>, BalanceOf<T>, HeightOf<T>),
+        /// Crowdfunding progressed \[instance, total, goal\]
+        FundingProgressed(NftOf<T>, BalanceOf<T>, BalanceOf<T>),
+        /// Crowdfunding goal reached \[instance, total\]
+        FundingReached(NftOf<T>, BalanceOf<T>),
+        /// Backing withdrawn from a failed crowdfunding \[did, instance, value\]
+        Withdrawn(T::DecentralizedId, NftOf<T>, BalanceOf<T>),
+        /// Minting profile preset set \[preset\]
+        PresetSet(u32),
+        /// Minting profile preset removed \[preset\]
+        PresetRemoved(u32),
+        /// Imported NFT no longer held by the owner \[did, instance\]
+        OwnershipLost(T::DecentralizedId, NftOf<T>),
+        /// Imported NFT unported by the owner \[did, instance\]
+        Unported(T::DecentralizedId, NftOf<T>),
+        /// Frozen NFT reclaimed by the current holder \[did, instance\]
+        Reclaimed(T::DecentralizedId, NftOf<T>),
+        /// NFT transferred \[from, to, instance\]
+        Transferred(T::DecentralizedId, T::DecentralizedId, NftOf<T>),
+        /// Preferred NFT set \[did, instance\]
+        PreferredSet(T::DecentralizedId, NftOf<T>),
+        /// Royalty set \[instance, royalty\]
+        RoyaltySet(NftOf<T>, u32),
+        /// Symbol reserved for a brand \[symbol, did\]
+        SymbolReserved(Vec<u8>, T::DecentralizedId),
+        /// Symbol reservation removed \[symbol\]
+        SymbolReleased(Vec<u8>),
     }
 
     #[pallet::hooks]
@@ -276,6 +436,15 @@ pub mod pallet {
         NetworkNotLinked,
         OcwParseError,
         NotTokenOwner,
+        InsufficientGoal,
+        NotFailed,
+        NotOwned,
+        NotVested,
+        BadProfile,
+        Frozen,
+        BadRoyalty,
+        SymbolReserved,
+        SymbolTaken,
     }
 
     #[pallet::call]
@@ -287,6 +456,8 @@ pub mod pallet {
             network: Network,
             namespace: Vec<u8>,
             token: Vec<u8>,
+            preset: Option<u32>,
+            funding: Option<FundingOf<T>>,
         ) -> DispatchResult {
             let (owner, _) = EnsureDid::<T>::ensure_origin(origin)?;
 
@@ -295,10 +466,10 @@ pub mod pallet {
                 Error::<T>::Exists
             );
 
-            ensure!(
-                !<Ported<T>>::contains_key((network, &namespace, &token)),
-                Error::<T>::Exists
-            );
+            // frozen NFT can be reclaimed by the current holder
+            if let Some(nft) = <Ported<T>>::get((network, &namespace, &token)) {
+                ensure!(<Frozen<T>>::contains_key(nft), Error::<T>::Exists);
+            }
 
             // user should link network first
             ensure!(
@@ -306,10 +477,26 @@ pub mod pallet {
                 Error::<T>::NetworkNotLinked
             );
 
+            let profile = Self::choose_preset(preset)?;
+
+            if let Some(funding) = &funding {
+                Self::ensure_funding(funding)?;
+            }
+
             let created = <frame_system::Pallet<T>>::block_number();
             let lifetime = T::PendingLifetime::get();
             let deadline = created.saturating_add(lifetime);
 
+            if let Some(profile) = profile {
+                <PortingProfile<T>>::insert((network, &namespace, &token), profile);
+            }
+
+            if let Some(funding) = funding {
+                <PortingFunding<T>>::insert((network, &namespace, &token), funding);
+            }
+
+            <PortingOf<T>>::insert(&owner, (network, namespace.clone(), token.clone()), ());
+
             <Porting<T>>::insert(
                 (network, &namespace.clone(), &token.clone()),
                 Task {
@@ -327,12 +514,22 @@ pub mod pallet {
             Ok(())
         }
 
-        /// Create a new NFT for crowdfunding.
+        /// Create a new NFT for crowdfunding, optionally with a goal and deadline.
         #[pallet::weight(<T as Config>::WeightInfo::kick())]
-        pub fn kick(origin: OriginFor<T>) -> DispatchResult {
+        pub fn kick(
+            origin: OriginFor<T>,
+            preset: Option<u32>,
+            funding: Option<FundingOf<T>>,
+        ) -> DispatchResult {
             let (owner, _) = EnsureDid::<T>::ensure_origin(origin)?;
 
-            Self::create(owner)?;
+            let profile = Self::choose_preset(preset)?;
+
+            if let Some(funding) = &funding {
+                Self::ensure_funding(funding)?;
+            }
+
+            Self::create(owner, profile, funding)?;
 
             Ok(())
         }
@@ -352,6 +549,15 @@ pub mod pallet {
 
             ensure!(!meta.minted, Error::<T>::Minted);
 
+            ensure!(!<Frozen<T>>::contains_key(nft), Error::<T>::Frozen);
+
+            let funding = <Funding<T>>::get(nft);
+
+            if let Some(funding) = &funding {
+                let height = <frame_system::Pallet<T>>::block_number();
+                ensure!(height < funding.deadline, Error::<T>::Deadline);
+            }
+
             T::Currency::transfer(&who, &meta.pot, value, KeepAlive)?;
 
             <Deposit<T>>::mutate(nft, |maybe| {
@@ -372,6 +578,51 @@ pub mod pallet {
 
             Self::deposit_event(Event::Backed(did, nft, value));
 
+            if let Some(funding) = funding {
+                let total = <Deposit<T>>::get(nft).unwrap_or_default();
+
+                Self::deposit_event(Event::FundingProgressed(nft, total, funding.goal));
+
+                if total >= funding.goal && total.saturating_sub(value) < funding.goal {
+                    Self::deposit_event(Event::FundingReached(nft, total));
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Withdraw the backing after the crowdfunding failed to reach its goal.
+        #[pallet::weight(<T as Config>::WeightInfo::withdraw_backing())]
+        pub fn withdraw_backing(origin: OriginFor<T>, nft: NftOf<T>) -> DispatchResult {
+            let (did, who) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let meta = <Metadata<T>>::get(nft).ok_or(Error::<T>::NotExists)?;
+
+            ensure!(!meta.minted, Error::<T>::Minted);
+
+            let funding = <Funding<T>>::get(nft).ok_or(Error::<T>::NotFailed)?;
+
+            let height = <frame_system::Pallet<T>>::block_number();
+            let total = <Deposit<T>>::get(nft).unwrap_or_default();
+            ensure!(
+                height >= funding.deadline && total < funding.goal,
+                Error::<T>::NotFailed
+            );
+
+            let deposit = <Deposits<T>>::get(nft, &did).ok_or(Error::<T>::NotExists)?;
+
+            T::Currency::transfer(&meta.pot, &who, deposit, AllowDeath)?;
+
+            <Deposit<T>>::mutate(nft, |maybe| {
+                if let Some(total) = maybe {
+                    total.saturating_reduce(deposit);
+                }
+            });
+
+            <Deposits<T>>::remove(nft, &did);
+
+            Self::deposit_event(Event::Withdrawn(did, nft, deposit));
+
             Ok(())
         }
 
@@ -384,25 +635,14 @@ pub mod pallet {
             name: Vec<u8>,
             symbol: Vec<u8>,
         ) -> DispatchResult {
-            let limit = T::StringLimit::get() as usize - 4;
-
-            ensure!(
-                0 < name.len() && name.len() <= limit,
-                Error::<T>::BadMetadata
-            );
-            ensure!(
-                0 < name.len() && symbol.len() <= limit,
-                Error::<T>::BadMetadata
-            );
-
-            let is_valid_char = |c: &u8| c.is_ascii_whitespace() || c.is_ascii_alphanumeric();
+            let limit = Self::string_limit();
 
             ensure!(
-                name[0].is_ascii_alphabetic() && name.iter().all(is_valid_char),
+                validation::is_valid_name(&name, limit),
                 Error::<T>::BadMetadata
             );
             ensure!(
-                symbol[0].is_ascii_alphabetic() && symbol.iter().all(is_valid_char),
+                validation::is_valid_symbol(&symbol, limit),
                 Error::<T>::BadMetadata
             );
 
@@ -414,11 +654,22 @@ pub mod pallet {
             let mut meta = <Metadata<T>>::get(nft).ok_or(Error::<T>::NotExists)?;
             ensure!(!meta.minted, Error::<T>::Minted);
 
-            let deposit = T::Currency::free_balance(&meta.pot);
+            let key = validation::normalize_symbol(&symbol);
+            ensure!(!<Symbols<T>>::contains_key(&key), Error::<T>::SymbolTaken);
+            if let Some(brand) = <ReservedSymbols<T>>::get(&key) {
+                ensure!(brand == meta.owner, Error::<T>::SymbolReserved);
+            }
+
+            // the pot is shared by the NFTs of a KOL, only the backing of this one is used
+            let deposit = <Deposit<T>>::get(nft).unwrap_or_default();
 
             let init = T::InitialMintingDeposit::get();
             ensure!(deposit >= init, Error::<T>::InsufficientBalance);
 
+            if let Some(funding) = <Funding<T>>::get(nft) {
+                ensure!(deposit >= funding.goal, Error::<T>::InsufficientBalance);
+            }
+
             // 2. create NFT token
             let tid = nft;
 
@@ -427,17 +678,29 @@ pub mod pallet {
 
             // 3. initial minting
 
-            let initial = T::InitialMintingValueBase::get();
-            let supply = initial.saturating_mul(3u32.into());
+            let profile = Self::profile_of(nft);
+            let supply = profile.supply;
+
+            let (swap, _, _, treasury) = Self::allocate(&profile)?;
 
             T::Assets::create(tid, meta.pot.clone(), true, One::one())?;
-            T::Assets::set(tid, &meta.pot, name.clone(), symbol.clone(), 18)?;
-            T::Assets::mint_into(tid, &meta.pot, supply)?;
+            T::Assets::set(
+                tid,
+                &meta.pot,
+                name.clone(),
+                symbol.clone(),
+                profile.decimals,
+            )?;
+            T::Assets::mint_into(tid, &meta.pot, supply.saturating_sub(treasury))?;
+
+            if !treasury.is_zero() {
+                T::Assets::mint_into(tid, &T::TreasuryAccount::get(), treasury)?;
+            }
 
-            // 4. transfer third of initial minting to swap
+            // 4. transfer swap share of initial minting to swap
 
             T::Swaps::new(tid)?;
-            T::Swaps::mint(meta.pot.clone(), tid, deposit, deposit, initial, false)?;
+            T::Swaps::mint(meta.pot.clone(), tid, deposit, deposit, swap, false)?;
 
             // 5. update local variable
             meta.minted = true;
@@ -446,6 +709,10 @@ pub mod pallet {
             <Metadata<T>>::insert(nft, meta);
 
             <Date<T>>::insert(nft, minted);
+            <Minter<T>>::insert(nft, &did);
+
+            <Symbols<T>>::insert(&key, nft);
+            <ReservedSymbols<T>>::remove(&key);
 
             <Deposits<T>>::mutate(nft, &did, |maybe| {
                 *maybe = Some(deposit);
@@ -456,40 +723,30 @@ pub mod pallet {
             Ok(())
         }
 
-        /// Claim the fragments.
+        /// Claim the vested fragments.
         #[pallet::weight(<T as Config>::WeightInfo::claim())]
         pub fn claim(origin: OriginFor<T>, nft: NftOf<T>) -> DispatchResult {
             let (did, who) = EnsureDid::<T>::ensure_origin(origin)?;
 
-            let height = <frame_system::Pallet<T>>::block_number();
-
             let meta = <Metadata<T>>::get(nft).ok_or(Error::<T>::NotExists)?;
 
-            if meta.owner == did {
-                let minted_block_number = <Date<T>>::get(nft).ok_or(Error::<T>::NotExists)?;
-                ensure!(
-                    height - minted_block_number >= T::InitialMintingLockupPeriod::get(),
-                    Error::<T>::NotExists
-                );
-            }
+            let (tokens, vested, claimed) = Self::vesting(nft, &did)?;
 
-            let total = <Deposit<T>>::get(nft).ok_or(Error::<T>::NotExists)?;
-            let deposit = <Deposits<T>>::get(nft, &did).ok_or(Error::<T>::NotExists)?;
-            let initial = T::InitialMintingValueBase::get();
+            let amount = vested.saturating_sub(claimed);
 
-            let total: U512 = Self::try_into(total)?;
-            let deposit: U512 = Self::try_into(deposit)?;
-            let initial: U512 = Self::try_into(initial)?;
-
-            let tokens = initial * deposit / total;
+            ensure!(!amount.is_zero(), Error::<T>::NotVested);
 
-            let tokens = Self::try_into(tokens)?;
+            T::Assets::transfer(meta.token_asset_id, &meta.pot, &who, amount, false)?;
+            Self::note_holder(meta.token_asset_id, &who);
 
-            T::Assets::transfer(meta.token_asset_id, &meta.pot, &who, tokens, false)?;
-
-            <Deposits<T>>::remove(nft, &did);
+            if vested >= tokens {
+                <Deposits<T>>::remove(nft, &did);
+                <Claimed<T>>::remove(nft, &did);
+            } else {
+                <Claimed<T>>::insert(nft, &did, vested);
+            }
 
-            Self::deposit_event(Event::Claimed(did, nft, tokens));
+            Self::deposit_event(Event::Claimed(did, nft, amount));
 
             Ok(())
         }
@@ -511,8 +768,15 @@ pub mod pallet {
 
             let task = task.unwrap();
 
-            if validated {
-                let id = Self::create(task.task.owner)?;
+            let profile = <PortingProfile<T>>::take((network, &namespace, &token));
+            let funding = <PortingFunding<T>>::take((network, &namespace, &token));
+
+            let ported = <Ported<T>>::get((network, &namespace, &token));
+
+            if validated && ported.is_some() {
+                Self::reclaim(ported.unwrap(), task.task.owner)?;
+            } else if validated {
+                let id = Self::create(task.task.owner, profile, funding)?;
 
                 <Ported<T>>::insert((network, namespace.clone(), token.clone()), id);
 
@@ -534,10 +798,124 @@ pub mod pallet {
                 ));
             }
 
+            <PortingOf<T>>::remove(
+                &task.task.owner,
+                (network, namespace.clone(), token.clone()),
+            );
+
             <Porting<T>>::remove((network, namespace, token));
             Ok(().into())
         }
 
+        /// Freeze an imported NFT no longer held by its owner, reported by offchain worker.
+        #[pallet::weight(<T as Config>::WeightInfo::report_ownership_lost())]
+        pub fn report_ownership_lost(origin: OriginFor<T>, nft: NftOf<T>) -> DispatchResult {
+            ensure_none(origin)?;
+
+            let external = <External<T>>::get(nft).ok_or(Error::<T>::NotExists)?;
+
+            ensure!(!<Frozen<T>>::contains_key(nft), Error::<T>::Frozen);
+
+            let height = <frame_system::Pallet<T>>::block_number();
+
+            <Frozen<T>>::insert(nft, height);
+
+            Self::deposit_event(Event::OwnershipLost(external.owner, nft));
+
+            Ok(())
+        }
+
+        /// Give up an imported NFT, so that it can be ported again.
+        ///
+        /// the NFT is no longer linked to the external token, and stays frozen.
+        #[pallet::weight(<T as Config>::WeightInfo::unport())]
+        pub fn unport(origin: OriginFor<T>, nft: NftOf<T>) -> DispatchResult {
+            let (did, _) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let meta = <Metadata<T>>::get(nft).ok_or(Error::<T>::NotExists)?;
+            ensure!(meta.owner == did, Error::<T>::NotOwned);
+
+            let external = <External<T>>::get(nft).ok_or(Error::<T>::NotExists)?;
+
+            ensure!(!<Frozen<T>>::contains_key(nft), Error::<T>::Frozen);
+
+            let height = <frame_system::Pallet<T>>::block_number();
+
+            <Frozen<T>>::insert(nft, height);
+
+            <Ported<T>>::remove((external.network, &external.namespace, &external.token));
+            <External<T>>::remove(nft);
+
+            Self::deposit_event(Event::Unported(did, nft));
+
+            Ok(())
+        }
+
+        /// Transfer an NFT to another DID.
+        ///
+        /// Fragments of the KOL stay vesting to the minter, only the control is transferred.
+        #[pallet::weight(<T as Config>::WeightInfo::transfer())]
+        pub fn transfer(
+            origin: OriginFor<T>,
+            nft: NftOf<T>,
+            dest: T::DecentralizedId,
+        ) -> DispatchResult {
+            let (did, _) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let meta = <Metadata<T>>::get(nft).ok_or(Error::<T>::NotExists)?;
+            ensure!(meta.owner == did, Error::<T>::NotOwned);
+
+            ensure!(dest != did, Error::<T>::YourSelf);
+            ensure!(
+                parami_did::Pallet::<T>::meta(&dest).is_some(),
+                Error::<T>::NotExists
+            );
+
+            ensure!(!<Frozen<T>>::contains_key(nft), Error::<T>::Frozen);
+
+            Self::hand_over(nft, dest)?;
+
+            Self::deposit_event(Event::Transferred(did, dest, nft));
+
+            Ok(())
+        }
+
+        /// Set the preferred NFT of the owner.
+        #[pallet::weight(<T as Config>::WeightInfo::set_preferred())]
+        pub fn set_preferred(origin: OriginFor<T>, nft: NftOf<T>) -> DispatchResult {
+            let (did, _) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let meta = <Metadata<T>>::get(nft).ok_or(Error::<T>::NotExists)?;
+            ensure!(meta.owner == did, Error::<T>::NotOwned);
+
+            <Preferred<T>>::insert(&did, nft);
+
+            Self::deposit_event(Event::PreferredSet(did, nft));
+
+            Ok(())
+        }
+
+        /// Set the royalty of an NFT in basis points, capped by `MaxRoyalty`.
+        #[pallet::weight(<T as Config>::WeightInfo::set_royalty())]
+        pub fn set_royalty(origin: OriginFor<T>, nft: NftOf<T>, royalty: u32) -> DispatchResult {
+            let (did, _) = EnsureDid::<T>::ensure_origin(origin)?;
+
+            let meta = <Metadata<T>>::get(nft).ok_or(Error::<T>::NotExists)?;
+            ensure!(meta.owner == did, Error::<T>::NotOwned);
+
+            ensure!(royalty <= T::MaxRoyalty::get(), Error::<T>::BadRoyalty);
+
+            if royalty.is_zero() {
+                <Royalty<T>>::remove(nft);
+            } else {
+                <Royalty<T>>::insert(nft, royalty);
+            }
+
+            Self::deposit_event(Event::RoyaltySet(nft, royalty));
+
+            Ok(())
+        }
+
         #[pallet::weight(<T as Config>::WeightInfo::submit_porting())]
         pub fn set_validate_endpoint(
             origin: OriginFor<T>,
@@ -551,6 +929,66 @@ pub mod pallet {
             <ValidateEndpoint<T>>::insert(network, endpoint);
             Ok(())
         }
+
+        /// Approve or revoke a minting profile preset.
+        #[pallet::weight(<T as Config>::WeightInfo::set_preset())]
+        pub fn set_preset(
+            origin: OriginFor<T>,
+            preset: u32,
+            profile: Option<ProfileOf<T>>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            match profile {
+                Some(profile) => {
+                    Self::allocate(&profile)?;
+
+                    <Presets<T>>::insert(preset, profile);
+
+                    Self::deposit_event(Event::PresetSet(preset));
+                }
+                None => {
+                    <Presets<T>>::remove(preset);
+
+                    Self::deposit_event(Event::PresetRemoved(preset));
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Reserve a symbol of fragments for the NFTs of a brand, or remove the reservation.
+        #[pallet::weight(<T as Config>::WeightInfo::set_reserved_symbol())]
+        pub fn set_reserved_symbol(
+            origin: OriginFor<T>,
+            symbol: Vec<u8>,
+            brand: Option<T::DecentralizedId>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            ensure!(
+                validation::is_valid_symbol(&symbol, Self::string_limit()),
+                Error::<T>::BadMetadata
+            );
+
+            let key = validation::normalize_symbol(&symbol);
+            ensure!(!<Symbols<T>>::contains_key(&key), Error::<T>::SymbolTaken);
+
+            match brand {
+                Some(brand) => {
+                    <ReservedSymbols<T>>::insert(&key, brand);
+
+                    Self::deposit_event(Event::SymbolReserved(key, brand));
+                }
+                None => {
+                    <ReservedSymbols<T>>::remove(&key);
+
+                    Self::deposit_event(Event::SymbolReleased(key));
+                }
+            }
+
+            Ok(())
+        }
     }
 
     #[pallet::genesis_config]
@@ -619,6 +1057,7 @@ pub mod pallet {
                     T::Nft::mint_into(&id, &id, &pot).unwrap();
 
                     <Date<T>>::insert(id, HeightOf::<T>::zero());
+                    <Minter<T>>::insert(id, owner);
                 }
             }
 
@@ -668,6 +1107,9 @@ pub mod pallet {
 
             match call {
                 Call::submit_porting { .. } => valid_tx(b"submit_porting".to_vec()),
+                Call::report_ownership_lost { nft } => {
+                    valid_tx((b"report_ownership_lost", nft).encode())
+                }
                 _ => InvalidTransaction::Call.into(),
             }
         }
@@ -675,7 +1117,11 @@ pub mod pallet {
 }
 
 impl<T: Config> Pallet<T> {
-    fn create(owner: DidOf<T>) -> Result<NftOf<T>, DispatchError> {
+    fn create(
+        owner: DidOf<T>,
+        profile: Option<ProfileOf<T>>,
+        funding: Option<FundingOf<T>>,
+    ) -> Result<NftOf<T>, DispatchError> {
         let id = <NextClassId<T>>::try_mutate(|id| -> Result<NftOf<T>, DispatchError> {
             let current_id = *id;
             *id = id.checked_add(&One::one()).ok_or(Error::<T>::Overflow)?;
@@ -697,15 +1143,306 @@ impl<T: Config> Pallet<T> {
             },
         );
 
+        if let Some(profile) = profile {
+            <Profile<T>>::insert(id, profile);
+        }
+
         if !<Preferred<T>>::contains_key(&owner) {
             <Preferred<T>>::insert(&owner, id);
         }
 
         Self::deposit_event(Event::Created(owner, id));
 
+        if let Some(funding) = funding {
+            Self::deposit_event(Event::FundingStarted(id, funding.goal, funding.deadline));
+
+            <Funding<T>>::insert(id, funding);
+        }
+
         Ok(id)
     }
 
+    /// Whether new backing and bids of an imported NFT are frozen.
+    pub fn is_frozen(nft: NftOf<T>) -> bool {
+        <Frozen<T>>::contains_key(nft)
+    }
+
+    /// Hand a frozen NFT over to the current holder of the external token.
+    fn reclaim(nft: NftOf<T>, owner: DidOf<T>) -> DispatchResult {
+        ensure!(<Frozen<T>>::contains_key(nft), Error::<T>::Exists);
+
+        Self::hand_over(nft, owner)?;
+
+        <Frozen<T>>::remove(nft);
+
+        Self::deposit_event(Event::Reclaimed(owner, nft));
+
+        Ok(())
+    }
+
+    /// Change owner of an NFT, returns the previous owner.
+    ///
+    /// fragments of the KOL keep vesting to the minter.
+    fn hand_over(nft: NftOf<T>, owner: DidOf<T>) -> Result<DidOf<T>, DispatchError> {
+        let prev = <Metadata<T>>::try_mutate(nft, |maybe| -> Result<DidOf<T>, DispatchError> {
+            let meta = maybe.as_mut().ok_or(Error::<T>::NotExists)?;
+            let prev = meta.owner;
+            meta.owner = owner;
+            Ok(prev)
+        })?;
+
+        <External<T>>::mutate(nft, |maybe| {
+            if let Some(external) = maybe {
+                external.owner = owner;
+            }
+        });
+
+        if <Preferred<T>>::get(&prev) == Some(nft) {
+            <Preferred<T>>::remove(&prev);
+        }
+        if !<Preferred<T>>::contains_key(&owner) {
+            <Preferred<T>>::insert(&owner, nft);
+        }
+
+        Ok(prev)
+    }
+
+    /// Minting profile of an NFT, defaults to thirds of triple the value base.
+    pub fn profile_of(nft: NftOf<T>) -> ProfileOf<T> {
+        <Profile<T>>::get(nft).unwrap_or_else(|| types::Profile {
+            supply: T::InitialMintingValueBase::get().saturating_mul(3u32.into()),
+            decimals: 18,
+            swap: 1,
+            backers: 1,
+            kol: 1,
+            treasury: 0,
+        })
+    }
+
+    fn ensure_funding(funding: &FundingOf<T>) -> DispatchResult {
+        let height = <frame_system::Pallet<T>>::block_number();
+        ensure!(funding.deadline > height, Error::<T>::Deadline);
+
+        ensure!(
+            funding.goal >= T::InitialMintingDeposit::get(),
+            Error::<T>::InsufficientGoal
+        );
+
+        Ok(())
+    }
+
+    fn choose_preset(preset: Option<u32>) -> Result<Option<ProfileOf<T>>, DispatchError> {
+        match preset {
+            Some(preset) => Ok(Some(
+                <Presets<T>>::get(preset).ok_or(Error::<T>::NotExists)?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Splits the supply of a profile into swap, backers, kol and treasury shares.
+    fn allocate(
+        profile: &ProfileOf<T>,
+    ) -> Result<(BalanceOf<T>, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>), DispatchError> {
+        ensure!(
+            !profile.supply.is_zero() && profile.swap > 0 && profile.backers > 0 && profile.kol > 0,
+            Error::<T>::BadProfile
+        );
+
+        let shares = profile.swap as u128
+            + profile.backers as u128
+            + profile.kol as u128
+            + profile.treasury as u128;
+
+        let supply: U512 = Self::try_into(profile.supply)?;
+        let shares: U512 = shares.into();
+
+        let share = |part: u32| Self::try_into(supply * U512::from(part) / shares);
+
+        let swap: BalanceOf<T> = share(profile.swap)?;
+        let kol: BalanceOf<T> = share(profile.kol)?;
+        let treasury: BalanceOf<T> = share(profile.treasury)?;
+        let backers = profile
+            .supply
+            .saturating_sub(swap)
+            .saturating_sub(kol)
+            .saturating_sub(treasury);
+
+        Ok((swap, backers, kol, treasury))
+    }
+
+    /// Fragments of a supporter vested but not claimed yet.
+    pub fn claimable(nft: NftOf<T>, did: &DidOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+        let (_, vested, claimed) = Self::vesting(nft, did)?;
+
+        Ok(vested.saturating_sub(claimed))
+    }
+
+    /// Crowdfunding state of an NFT, returns pot balance, total deposit and deposit required to mint.
+    ///
+    /// the pot is shared by the NFTs of the owner, only the total deposit counts toward minting.
+    pub fn crowdfunding(
+        nft: NftOf<T>,
+    ) -> Result<(BalanceOf<T>, BalanceOf<T>, BalanceOf<T>), DispatchError> {
+        let meta = <Metadata<T>>::get(nft).ok_or(Error::<T>::NotExists)?;
+
+        let pot = T::Currency::free_balance(&meta.pot);
+        let deposit = <Deposit<T>>::get(nft).unwrap_or_default();
+
+        let mut required = T::InitialMintingDeposit::get();
+        if let Some(funding) = <Funding<T>>::get(nft) {
+            required = required.max(funding.goal);
+        }
+
+        Ok((pot, deposit, required))
+    }
+
+    /// Shares of supporters, starting after `start_after` if given,
+    /// returns deposit, fragments entitled and claimable of each.
+    ///
+    /// fragments are zero until minted.
+    pub fn shares_of(
+        nft: NftOf<T>,
+        start_after: Option<DidOf<T>>,
+        limit: u32,
+    ) -> Result<Vec<(DidOf<T>, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>)>, DispatchError> {
+        let meta = <Metadata<T>>::get(nft).ok_or(Error::<T>::NotExists)?;
+
+        let limit = limit.min(MAX_PAGE_SIZE) as usize;
+
+        let iter = match start_after {
+            Some(did) => {
+                <Deposits<T>>::iter_prefix_from(nft, <Deposits<T>>::hashed_key_for(nft, did))
+            }
+            None => <Deposits<T>>::iter_prefix(nft),
+        };
+
+        let shares = iter
+            .take(limit)
+            .map(|(did, deposit)| {
+                let (tokens, claimable) = match Self::vesting(nft, &did) {
+                    Ok((tokens, vested, claimed)) if meta.minted => {
+                        (tokens, vested.saturating_sub(claimed))
+                    }
+                    _ => (Zero::zero(), Zero::zero()),
+                };
+
+                (did, deposit, tokens, claimable)
+            })
+            .collect();
+
+        Ok(shares)
+    }
+
+    /// Height at which fragments of the owner are no longer locked up.
+    pub fn lockup_of(nft: NftOf<T>) -> Result<HeightOf<T>, DispatchError> {
+        let minted = <Date<T>>::get(nft).ok_or(Error::<T>::NotExists)?;
+
+        let cliff = T::InitialMintingVestingCliff::get().max(T::InitialMintingLockupPeriod::get());
+
+        Ok(minted.saturating_add(cliff))
+    }
+
+    /// The NFT which minted fragments of a symbol, symbols are case-insensitive.
+    pub fn symbol_of(symbol: &[u8]) -> Option<NftOf<T>> {
+        <Symbols<T>>::get(validation::normalize_symbol(symbol))
+    }
+
+    /// Whether a symbol of fragments can be minted by the NFTs of a DID.
+    pub fn is_symbol_available(symbol: &[u8], did: &DidOf<T>) -> bool {
+        if !validation::is_valid_symbol(symbol, Self::string_limit()) {
+            return false;
+        }
+
+        let key = validation::normalize_symbol(symbol);
+
+        !<Symbols<T>>::contains_key(&key)
+            && <ReservedSymbols<T>>::get(&key).map_or(true, |brand| brand == *did)
+    }
+
+    /// Importing in progress of a DID, starting after `start_after` if given,
+    /// returns network, namespace, token and deadline of each.
+    pub fn porting_of(
+        did: &DidOf<T>,
+        start_after: Option<(Network, Vec<u8>, Vec<u8>)>,
+        limit: u32,
+    ) -> Vec<(Network, Vec<u8>, Vec<u8>, HeightOf<T>)> {
+        let limit = limit.min(MAX_PAGE_SIZE) as usize;
+
+        let iter = match start_after {
+            Some(key) => {
+                <PortingOf<T>>::iter_key_prefix_from(did, <PortingOf<T>>::hashed_key_for(did, key))
+            }
+            None => <PortingOf<T>>::iter_key_prefix(did),
+        };
+
+        iter.filter_map(|(network, namespace, token)| {
+            let porting = <Porting<T>>::get((network, &namespace, &token))?;
+
+            Some((network, namespace, token, porting.deadline))
+        })
+        .take(limit)
+        .collect()
+    }
+
+    /// Maximum length of names and symbols of fragments.
+    fn string_limit() -> usize {
+        T::StringLimit::get().saturating_sub(4) as usize
+    }
+
+    /// Returns fragments entitled, vested and claimed of a supporter.
+    fn vesting(
+        nft: NftOf<T>,
+        did: &DidOf<T>,
+    ) -> Result<(BalanceOf<T>, BalanceOf<T>, BalanceOf<T>), DispatchError> {
+        let meta = <Metadata<T>>::get(nft).ok_or(Error::<T>::NotExists)?;
+        let minted = <Date<T>>::get(nft).ok_or(Error::<T>::NotExists)?;
+
+        let total = <Deposit<T>>::get(nft).ok_or(Error::<T>::NotExists)?;
+        let deposit = <Deposits<T>>::get(nft, did).ok_or(Error::<T>::NotExists)?;
+
+        let (_, backers, kol, _) = Self::allocate(&Self::profile_of(nft))?;
+
+        let minter = <Minter<T>>::get(nft).unwrap_or(meta.owner);
+
+        let tokens: U512 = if minter == *did {
+            Self::try_into(kol)?
+        } else {
+            let total: U512 = Self::try_into(total)?;
+            let deposit: U512 = Self::try_into(deposit)?;
+            let backers: U512 = Self::try_into(backers)?;
+
+            backers * deposit / total
+        };
+
+        let height = <frame_system::Pallet<T>>::block_number();
+        let elapsed = height.saturating_sub(minted);
+
+        let mut cliff = T::InitialMintingVestingCliff::get();
+        if minter == *did {
+            cliff = cliff.max(T::InitialMintingLockupPeriod::get());
+        }
+
+        let period = T::InitialMintingVestingPeriod::get();
+
+        let vested = if elapsed < cliff {
+            U512::zero()
+        } else if period.is_zero() || elapsed >= period {
+            tokens
+        } else {
+            let elapsed: U512 = Self::try_into(elapsed)?;
+            let period: U512 = Self::try_into(period)?;
+
+            tokens * elapsed / period
+        };
+
+        let tokens = Self::try_into(tokens)?;
+        let vested = Self::try_into(vested)?;
+        let claimed = <Claimed<T>>::get(nft, did);
+
+        Ok((tokens, vested, claimed))
+    }
+
     fn try_into<S, D>(value: S) -> Result<D, DispatchError>
     where
         S: TryInto<u128>,
@@ -719,16 +1456,64 @@ impl<T: Config> Pallet<T> {
     }
 }
 
-impl<T: Config> Nfts<T::AccountId> for Pallet<T> {
-    fn force_transfer_all_fractions(
-        src: &T::AccountId,
-        dest: &T::AccountId,
-    ) -> Result<(), DispatchError> {
-        for (_nft_id, nft_meta) in <Metadata<T>>::iter() {
-            let balance = T::Assets::balance(nft_meta.token_asset_id, &src);
-            T::Assets::transfer(nft_meta.token_asset_id, src, dest, balance, false)?;
+impl<T: Config> Royalties<AccountOf<T>, NftOf<T>> for Pallet<T> {
+    fn royalty_of(nft: NftOf<T>) -> Option<(AccountOf<T>, u32)> {
+        let royalty = <Royalty<T>>::get(nft).min(T::MaxRoyalty::get());
+        if royalty.is_zero() || <Frozen<T>>::contains_key(nft) {
+            return None;
         }
 
-        Ok(())
+        let meta = <Metadata<T>>::get(nft)?;
+        if !meta.minted {
+            return None;
+        }
+
+        let account = parami_did::Pallet::<T>::lookup_did(meta.owner)?;
+
+        Some((account, royalty))
+    }
+}
+
+impl<T: Config> Holders<AccountOf<T>, NftOf<T>> for Pallet<T> {
+    fn note_holder(token_id: NftOf<T>, who: &AccountOf<T>) {
+        <FractionsOf<T>>::insert(who, token_id, ());
+    }
+}
+
+impl<T: Config> AccountMigration<AccountOf<T>> for Pallet<T> {
+    fn migrate(
+        src: &AccountOf<T>,
+        dest: &AccountOf<T>,
+        items: u32,
+    ) -> Result<Vec<Migrated>, DispatchError> {
+        let tokens: Vec<NftOf<T>> = <FractionsOf<T>>::iter_key_prefix(src)
+            .take((items as usize).saturating_add(1))
+            .collect();
+        ensure!(tokens.len() as u32 <= items, DispatchError::Exhausted);
+
+        let mut migrated = Vec::new();
+
+        for token_id in tokens {
+            <FractionsOf<T>>::remove(src, token_id);
+
+            let balance = T::Assets::balance(token_id, src);
+            if balance.is_zero() {
+                continue;
+            }
+
+            T::Assets::transfer(token_id, src, dest, balance, false)?;
+            Self::note_holder(token_id, dest);
+
+            migrated.push(Migrated::Fractions(
+                token_id.saturated_into(),
+                balance.saturated_into(),
+            ));
+        }
+
+        Ok(migrated)
+    }
+
+    fn migrate_weight(items: u32) -> Weight {
+        <T as Config>::WeightInfo::migrate_account(items)
     }
 }
@@ -13,6 +13,21 @@ pub fn migrate<T: Config>() -> Weight {
         StorageVersion::new(2).put::<Pallet<T>>();
     }
 
+    if version < 3 {
+        weight.saturating_accrue(v3::migrate::<T>());
+        StorageVersion::new(3).put::<Pallet<T>>();
+    }
+
+    if version < 4 {
+        weight.saturating_accrue(v4::migrate::<T>());
+        StorageVersion::new(4).put::<Pallet<T>>();
+    }
+
+    if version < 5 {
+        weight.saturating_accrue(v5::migrate::<T>());
+        StorageVersion::new(5).put::<Pallet<T>>();
+    }
+
     weight
 }
 
@@ -41,3 +56,84 @@ mod v2 {
         Weight::max_value()
     }
 }
+
+mod v3 {
+    use super::*;
+    use crate::{validation::normalize_symbol, Metadata, Symbols};
+
+    use frame_support::traits::{tokens::fungibles::InspectMetadata, Get};
+
+    pub fn migrate<T: Config>() -> Weight {
+        let mut weight: Weight = 0;
+
+        for (nft, meta) in <Metadata<T>>::iter() {
+            weight.saturating_accrue(T::DbWeight::get().reads(1));
+
+            if !meta.minted {
+                continue;
+            }
+
+            let key = normalize_symbol(&T::Assets::symbol(meta.token_asset_id));
+
+            weight.saturating_accrue(T::DbWeight::get().reads(2));
+
+            // symbols minted twice before the registry are kept by either NFT
+            if key.is_empty() || <Symbols<T>>::contains_key(&key) {
+                continue;
+            }
+
+            <Symbols<T>>::insert(&key, nft);
+
+            weight.saturating_accrue(T::DbWeight::get().writes(1));
+        }
+
+        weight
+    }
+}
+
+mod v4 {
+    use super::*;
+    use crate::{Metadata, Minter};
+
+    use frame_support::traits::Get;
+
+    pub fn migrate<T: Config>() -> Weight {
+        let mut weight: Weight = 0;
+
+        // fragments of the owner were moved along with the NFT before
+        for (nft, meta) in <Metadata<T>>::iter() {
+            weight.saturating_accrue(T::DbWeight::get().reads(1));
+
+            if !meta.minted {
+                continue;
+            }
+
+            <Minter<T>>::insert(nft, meta.owner);
+
+            weight.saturating_accrue(T::DbWeight::get().writes(1));
+        }
+
+        weight
+    }
+}
+
+mod v5 {
+    use super::*;
+    use crate::{Porting, PortingOf};
+
+    use frame_support::traits::Get;
+
+    pub fn migrate<T: Config>() -> Weight {
+        let mut weight: Weight = 0;
+
+        for porting in <Porting<T>>::iter_values() {
+            let task = porting.task;
+
+            <PortingOf<T>>::insert(&task.owner, (task.network, task.namespace, task.token), ());
+
+            weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+        }
+
+        weight
+    }
+}
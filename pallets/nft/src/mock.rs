@@ -8,6 +8,7 @@ use sp_core::{sr25519, H160, H256};
 use sp_runtime::{
     testing::{Header, TestXt},
     traits::{BlakeTwo256, Keccak256},
+    Permill,
 };
 
 use parami_primitives::constants::DOLLARS;
@@ -171,12 +172,17 @@ impl parami_did::Config for Test {
     type DecentralizedId = H160;
     type Hashing = Keccak256;
     type WeightInfo = ();
-    type Nfts = Nft;
+    type Migrations = (Nft, Swap);
 }
 
 impl parami_ocw::Config for Test {}
 
 parameter_types! {
+    pub const SwapMaxCurveChanges: u32 = 8;
+    pub const SwapMaxFee: Permill = Permill::from_percent(10);
+    pub const SwapMaxProtocolShare: Permill = Permill::from_percent(50);
+    pub const SwapMaxObservations: u32 = 4;
+    pub const SwapObservationPeriod: BlockNumber = 10;
     pub const SwapPalletId: PalletId = PalletId(*b"prm/swap");
 }
 
@@ -186,7 +192,18 @@ impl parami_swap::Config for Test {
     type Assets = Assets;
     type Currency = Balances;
     type FarmingCurve = ();
+    type ExponentialDecayCurve = ();
+    type HalvingCurve = ();
+    type ForceOrigin = EnsureRoot<Self::AccountId>;
+    type MaxCurveChanges = SwapMaxCurveChanges;
+    type MaxFee = SwapMaxFee;
+    type MaxProtocolShare = SwapMaxProtocolShare;
+    type MaxObservations = SwapMaxObservations;
+    type ObservationPeriod = SwapObservationPeriod;
     type PalletId = SwapPalletId;
+    type Royalties = Nft;
+    type Holders = Nft;
+    type TreasuryAccount = TreasuryAccount;
     type WeightInfo = ();
 }
 
@@ -225,9 +242,19 @@ impl parami_linker::Config for Test {
 parameter_types! {
     pub const InitialMintingDeposit: Balance = 1_000 * DOLLARS;
     pub const InitialMintingLockupPeriod: BlockNumber = 5;
+    pub const InitialMintingVestingCliff: BlockNumber = 2;
+    pub const InitialMintingVestingPeriod: BlockNumber = 10;
+    pub const MaxRoyalty: u32 = 1_000;
     pub const InitialMintingValueBase: Balance = 1_000_000 * DOLLARS;
     pub const PendingLifetime: BlockNumber = 5;
+    pub const ReverifyInterval: BlockNumber = 10;
+    pub const MaxReverifyPerBlock: u32 = 1;
+    pub const BinanceNetwork: Network = Network::Binance;
+    pub const EthereumNetwork: Network = Network::Ethereum;
+    pub const NearNetwork: Network = Network::Near;
+    pub const SolanaNetwork: Network = Network::Solana;
     pub const NftPalletId: PalletId = PalletId(*b"prm/nft ");
+    pub const TreasuryAccount: sr25519::Public = sr25519::Public([0xaa; 32]);
 }
 
 impl parami_nft::Config for Test {
@@ -236,13 +263,25 @@ impl parami_nft::Config for Test {
     type Assets = Assets;
     type InitialMintingDeposit = InitialMintingDeposit;
     type InitialMintingLockupPeriod = InitialMintingLockupPeriod;
+    type InitialMintingVestingCliff = InitialMintingVestingCliff;
+    type InitialMintingVestingPeriod = InitialMintingVestingPeriod;
     type InitialMintingValueBase = InitialMintingValueBase;
     type Links = Linker;
     type Nft = Uniques;
+    type MaxRoyalty = MaxRoyalty;
     type PalletId = NftPalletId;
     type PendingLifetime = PendingLifetime;
+    type ReverifyInterval = ReverifyInterval;
+    type MaxReverifyPerBlock = MaxReverifyPerBlock;
+    type Validators = (
+        crate::Validator<BinanceNetwork, crate::Evm<Test>>,
+        crate::Validator<EthereumNetwork, crate::Evm<Test>>,
+        crate::Validator<NearNetwork, crate::Nep171<Test>>,
+        crate::Validator<SolanaNetwork, crate::Metaplex<Test>>,
+    );
     type StringLimit = StringLimit;
     type Swaps = Swap;
+    type TreasuryAccount = TreasuryAccount;
     type WeightInfo = ();
     type UnsignedPriority = ();
 }
@@ -280,7 +319,12 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
             vec![0x01],
             DID_ALICE,
         )],
-        validate_endpoints: vec![(Network::Ethereum, "http://etherum.endpoint/example".into())],
+        validate_endpoints: vec![
+            (Network::Binance, "http://binance.endpoint/example".into()),
+            (Network::Ethereum, "http://etherum.endpoint/example".into()),
+            (Network::Near, "http://near.endpoint/example".into()),
+            (Network::Solana, "http://solana.endpoint/example".into()),
+        ],
     }
     .assimilate_storage(&mut t)
     .unwrap();
@@ -1,44 +1,37 @@
 pub use abi::eth_abi;
+pub use validators::{
+    Erc1155, Erc721, Evm, Metaplex, Nep171, OwnershipValidator, Validator, Validators,
+};
 
 mod abi;
 mod types;
+mod validators;
 
-use crate::{Call, Config, Error, Pallet, Porting, ValidateEndpoint};
-use ethabi::ethereum_types::U256;
+use crate::{Call, Config, Error, External, Frozen, NftOf, Pallet, Porting, ValidateEndpoint};
+use ethabi::ethereum_types::{H160, U256};
 use frame_support::dispatch::DispatchError;
 use frame_support::dispatch::DispatchResult;
 use frame_system::offchain::{SendTransactionTypes, SubmitTransaction};
 use log;
-use parami_ocw::JsonValue;
+use parami_ocw::{parse_json, JsonValue};
 use parami_ocw::{submit_unsigned, Pallet as Ocw};
 use parami_traits::Links;
 use scale_info::prelude::string::String;
+use sp_runtime::{offchain::storage::StorageValueRef, traits::Zero};
 use sp_std::prelude::Vec;
-use sp_std::str;
+
+/// Offchain local storage key of the last ported NFT re-verified, kept while a sweep is in progress
+const REVERIFY_CURSOR: &[u8] = b"parami-nft::reverify-cursor";
 
 impl<T: Config + SendTransactionTypes<Call<T>>> Pallet<T> {
     pub fn ocw_begin_block(block_number: T::BlockNumber) -> DispatchResult {
-        use parami_traits::types::Network::*;
-
-        for network in [Ethereum] {
+        for network in T::Validators::networks() {
             let porting = <Porting<T>>::iter_prefix_values((network,));
 
-            let endpoint = <ValidateEndpoint<T>>::get(network);
-            if endpoint.is_none() {
-                log::error!("network {:?} endpoint not found, skip import", network);
-                continue;
-            }
-            let endpoint = endpoint.unwrap().into_inner();
-            let endpoint = str::from_utf8(&endpoint);
-            if endpoint.is_err() {
-                log::error!(
-                    "Convert endpoint to str failed, err = {:?}",
-                    endpoint.unwrap_err(),
-                );
-                continue;
-            }
-
-            let endpoint = endpoint.unwrap();
+            let endpoint = match Self::ocw_endpoint(network) {
+                Some(endpoint) => endpoint,
+                None => continue,
+            };
 
             for task in porting {
                 if task.deadline <= block_number {
@@ -56,14 +49,15 @@ impl<T: Config + SendTransactionTypes<Call<T>>> Pallet<T> {
 
                 let links = T::Links::links(&task.task.owner, task.task.network);
 
-                let result = match task.task.network {
-                    Ethereum => Self::ocw_validate_etherum_token_owner(
-                        &links,
-                        endpoint,
-                        &task.task.namespace,
-                        &task.task.token,
-                    ),
-                    _ => {
+                let result = match Self::ocw_validate(
+                    task.task.network,
+                    &endpoint,
+                    &links,
+                    &task.task.namespace,
+                    &task.task.token,
+                ) {
+                    Some(result) => result,
+                    None => {
                         // drop unsupported sites
                         Self::ocw_submit_porting(
                             task.task.owner,
@@ -89,9 +83,98 @@ impl<T: Config + SendTransactionTypes<Call<T>>> Pallet<T> {
             }
         }
 
+        Self::ocw_reverify(block_number);
+
         Ok(())
     }
 
+    /// Re-verify ownership of ported NFTs, a sweep starts every `ReverifyInterval`
+    /// and goes on in the following blocks, `MaxReverifyPerBlock` NFTs at a time.
+    fn ocw_reverify(block_number: T::BlockNumber) {
+        let interval = T::ReverifyInterval::get();
+        if interval.is_zero() {
+            return;
+        }
+
+        let cursor = StorageValueRef::persistent(REVERIFY_CURSOR);
+
+        let ported = match cursor.get::<NftOf<T>>() {
+            Ok(Some(last)) => <External<T>>::iter_from(<External<T>>::hashed_key_for(last)),
+            _ if (block_number % interval).is_zero() => <External<T>>::iter(),
+            _ => return,
+        };
+
+        let limit = T::MaxReverifyPerBlock::get();
+        let mut count = 0u32;
+
+        for (nft, external) in ported {
+            if count >= limit {
+                return;
+            }
+
+            cursor.set(&nft);
+
+            if <Frozen<T>>::contains_key(nft) {
+                continue;
+            }
+
+            let endpoint = match Self::ocw_endpoint(external.network) {
+                Some(endpoint) => endpoint,
+                None => continue,
+            };
+
+            count += 1;
+
+            let links = T::Links::links(&external.owner, external.network);
+
+            let result = Self::ocw_validate(
+                external.network,
+                &endpoint,
+                &links,
+                &external.namespace,
+                &external.token,
+            );
+
+            // only a definite answer from the network counts as lost
+            if let Some(Err(e)) = result {
+                if e == Error::<T>::NotTokenOwner.into() {
+                    Self::ocw_report_ownership_lost(nft);
+                }
+            }
+        }
+
+        // sweep finished
+        cursor.clear();
+    }
+
+    fn ocw_endpoint(network: parami_traits::types::Network) -> Option<String> {
+        let endpoint = match <ValidateEndpoint<T>>::get(network) {
+            Some(endpoint) => endpoint.into_inner(),
+            None => {
+                log::error!("network {:?} endpoint not found, skip import", network);
+                return None;
+            }
+        };
+
+        match String::from_utf8(endpoint) {
+            Ok(endpoint) => Some(endpoint),
+            Err(e) => {
+                log::error!("Convert endpoint to str failed, err = {:?}", e);
+                None
+            }
+        }
+    }
+
+    pub(super) fn ocw_validate(
+        network: parami_traits::types::Network,
+        endpoint: &str,
+        links: &[Vec<u8>],
+        namespace: &[u8],
+        token: &[u8],
+    ) -> Option<DispatchResult> {
+        T::Validators::validate(network, endpoint, links, namespace, token)
+    }
+
     pub(self) fn ocw_submit_porting(
         did: T::DecentralizedId,
         network: parami_traits::types::Network,
@@ -110,7 +193,40 @@ impl<T: Config + SendTransactionTypes<Call<T>>> Pallet<T> {
         let _ = submit_unsigned!(call);
     }
 
+    pub(self) fn ocw_report_ownership_lost(nft: NftOf<T>) {
+        let call = Call::report_ownership_lost { nft };
+
+        let _ = submit_unsigned!(call);
+    }
+
     pub(super) fn construct_request_body(namespace: &[u8], token: &[u8]) -> String {
+        let encoded = eth_abi::encode_input(
+            "ownerOf".as_bytes(),
+            &[types::ParamType::Uint(256)],
+            &[types::Token::Uint(U256::from(token))],
+        );
+
+        Self::construct_eth_call_body(namespace, &encoded)
+    }
+
+    pub(super) fn construct_balance_of_body(
+        namespace: &[u8],
+        owner: &[u8],
+        token: &[u8],
+    ) -> String {
+        let encoded = eth_abi::encode_input(
+            "balanceOf".as_bytes(),
+            &[types::ParamType::Address, types::ParamType::Uint(256)],
+            &[
+                types::Token::Address(H160::from_slice(owner)),
+                types::Token::Uint(U256::from(token)),
+            ],
+        );
+
+        Self::construct_eth_call_body(namespace, &encoded)
+    }
+
+    fn construct_eth_call_body(contract: &[u8], data: &[u8]) -> String {
         let body = r#"{
     "jsonrpc": "2.0",
     "id": 1,
@@ -124,14 +240,48 @@ impl<T: Config + SendTransactionTypes<Call<T>>> Pallet<T> {
         "latest"
     ]
 }"#;
-        let encoded = eth_abi::encode_input(
-            "ownerOf".as_bytes(),
-            &[types::ParamType::Uint(256)],
-            &[types::Token::Uint(U256::from(token))],
-        );
         let body = body
-            .replace("<data>", &hex::encode(&encoded))
-            .replace("<contract>", &hex::encode(&namespace));
+            .replace("<data>", &hex::encode(data))
+            .replace("<contract>", &hex::encode(contract));
+        return body;
+    }
+
+    pub(super) fn construct_token_accounts_body(owner: &str, mint: &str) -> String {
+        let body = r#"{
+    "jsonrpc": "2.0",
+    "id": 1,
+    "method": "getTokenAccountsByOwner",
+    "params": [
+        "<owner>",
+        {
+            "mint": "<mint>"
+        },
+        {
+            "encoding": "jsonParsed"
+        }
+    ]
+}"#;
+        let body = body.replace("<owner>", owner).replace("<mint>", mint);
+        return body;
+    }
+
+    pub(super) fn construct_nft_token_body(contract: &str, token: &str) -> String {
+        let body = r#"{
+    "jsonrpc": "2.0",
+    "id": 1,
+    "method": "query",
+    "params": {
+        "request_type": "call_function",
+        "finality": "final",
+        "account_id": "<contract>",
+        "method_name": "nft_token",
+        "args_base64": "<args>"
+    }
+}"#;
+        let args = r#"{"token_id":"<token>"}"#.replace("<token>", token);
+        let body = body
+            .replace("<contract>", contract)
+            .replace("<args>", &base64::encode(args));
         return body;
     }
 
@@ -152,6 +302,11 @@ impl<T: Config + SendTransactionTypes<Call<T>>> Pallet<T> {
         token: &[u8],
     ) -> Result<U256, DispatchError> {
         let body = Self::construct_request_body(contract, token);
+
+        Self::ocw_eth_call(rpc, body)
+    }
+
+    pub(super) fn ocw_eth_call(rpc: &str, body: String) -> Result<U256, DispatchError> {
         let res = Ocw::<T>::ocw_post(rpc, body.into())?;
 
         let json = res.json();
@@ -184,4 +339,21 @@ impl<T: Config + SendTransactionTypes<Call<T>>> Pallet<T> {
             Err(Error::<T>::NotTokenOwner)?
         }
     }
+
+    pub(super) fn json_get<'a>(value: &'a JsonValue, path: &[&str]) -> Option<&'a JsonValue> {
+        path.iter().try_fold(value, |value, key| match value {
+            JsonValue::Object(fields) => fields
+                .iter()
+                .find(|(k, _)| k.iter().copied().eq(key.chars()))
+                .map(|(_, v)| v),
+            _ => None,
+        })
+    }
+
+    pub(super) fn json_string(value: &JsonValue) -> Option<String> {
+        match value {
+            JsonValue::String(chars) => Some(chars.iter().collect()),
+            _ => None,
+        }
+    }
 }
@@ -1,16 +1,22 @@
 use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
-use sp_core::U256;
+use sp_core::{H160, U256};
 use sp_runtime_interface::pass_by::PassByCodec;
 
 #[derive(PassByCodec, Encode, Decode, Eq, PartialEq, TypeInfo, MaxEncodedLen)]
 pub enum ParamType {
+    /// Address.
+    Address,
     /// Unsigned integer.
     Uint(u32),
 }
 
 #[derive(PassByCodec, Encode, Decode, Eq, PartialEq, TypeInfo, MaxEncodedLen)]
 pub enum Token {
+    /// Address.
+    ///
+    /// solidity name: address
+    Address(H160),
     /// Unisnged integer.
     ///
     /// solidity name: uint
@@ -21,6 +27,7 @@ pub enum Token {
 impl From<&Token> for ethabi::Token {
     fn from(token: &Token) -> ethabi::Token {
         match token {
+            Token::Address(address) => ethabi::Token::Address(address.0.into()),
             Token::Uint(number) => ethabi::Token::Uint(number.into()),
         }
     }
@@ -30,6 +37,7 @@ impl From<&Token> for ethabi::Token {
 impl From<&ParamType> for ethabi::ParamType {
     fn from(param_type: &ParamType) -> ethabi::ParamType {
         match param_type {
+            ParamType::Address => ethabi::ParamType::Address,
             ParamType::Uint(number) => ethabi::ParamType::Uint(*number as usize),
         }
     }
@@ -0,0 +1,235 @@
+use super::*;
+use frame_support::{ensure, traits::Get};
+use parami_traits::types::Network;
+use sp_std::{marker::PhantomData, str};
+
+/// Validate ownership of an external NFT against the RPC endpoint of its network
+pub trait OwnershipValidator {
+    /// Succeeds if one of the linked addresses owns the token
+    fn validate(rpc: &str, links: &[Vec<u8>], namespace: &[u8], token: &[u8]) -> DispatchResult;
+}
+
+/// Validators of the networks supported for importing, a tuple of `Validator`s
+pub trait Validators {
+    /// Networks with a validator
+    fn networks() -> Vec<Network>;
+
+    /// Validate with the validator of a network, `None` if the network is not supported
+    fn validate(
+        network: Network,
+        rpc: &str,
+        links: &[Vec<u8>],
+        namespace: &[u8],
+        token: &[u8],
+    ) -> Option<DispatchResult>;
+}
+
+/// Validates NFTs of the network `N` with `V`
+pub struct Validator<N, V>(PhantomData<(N, V)>);
+
+impl<N: Get<Network>, V: OwnershipValidator> Validators for Validator<N, V> {
+    fn networks() -> Vec<Network> {
+        sp_std::vec![N::get()]
+    }
+
+    fn validate(
+        network: Network,
+        rpc: &str,
+        links: &[Vec<u8>],
+        namespace: &[u8],
+        token: &[u8],
+    ) -> Option<DispatchResult> {
+        if network == N::get() {
+            Some(V::validate(rpc, links, namespace, token))
+        } else {
+            None
+        }
+    }
+}
+
+impl Validators for () {
+    fn networks() -> Vec<Network> {
+        Vec::new()
+    }
+
+    fn validate(
+        _network: Network,
+        _rpc: &str,
+        _links: &[Vec<u8>],
+        _namespace: &[u8],
+        _token: &[u8],
+    ) -> Option<DispatchResult> {
+        None
+    }
+}
+
+macro_rules! impl_validators_for_tuples {
+    ($($name:ident),+) => {
+        impl<$($name: Validators),+> Validators for ($($name,)+) {
+            fn networks() -> Vec<Network> {
+                let mut networks = Vec::new();
+                $(networks.extend($name::networks());)+
+                networks
+            }
+
+            fn validate(
+                network: Network,
+                rpc: &str,
+                links: &[Vec<u8>],
+                namespace: &[u8],
+                token: &[u8],
+            ) -> Option<DispatchResult> {
+                $(
+                    if let Some(result) = $name::validate(network, rpc, links, namespace, token) {
+                        return Some(result);
+                    }
+                )+
+                None
+            }
+        }
+    };
+}
+
+impl_validators_for_tuples!(A);
+impl_validators_for_tuples!(A, B);
+impl_validators_for_tuples!(A, B, C);
+impl_validators_for_tuples!(A, B, C, D);
+impl_validators_for_tuples!(A, B, C, D, E);
+impl_validators_for_tuples!(A, B, C, D, E, F);
+impl_validators_for_tuples!(A, B, C, D, E, F, G);
+impl_validators_for_tuples!(A, B, C, D, E, F, G, H);
+
+/// ERC-721 contracts on EVM networks, checked with `ownerOf`
+pub struct Erc721<T>(PhantomData<T>);
+
+impl<T: Config> OwnershipValidator for Erc721<T> {
+    fn validate(rpc: &str, links: &[Vec<u8>], namespace: &[u8], token: &[u8]) -> DispatchResult {
+        Pallet::<T>::ocw_validate_etherum_token_owner(links, rpc, namespace, token)
+    }
+}
+
+/// ERC-1155 contracts on EVM networks, checked with `balanceOf`
+pub struct Erc1155<T>(PhantomData<T>);
+
+impl<T: Config> OwnershipValidator for Erc1155<T> {
+    fn validate(rpc: &str, links: &[Vec<u8>], namespace: &[u8], token: &[u8]) -> DispatchResult {
+        for link in links.iter().filter(|link| link.len() == 20) {
+            let body = Pallet::<T>::construct_balance_of_body(namespace, link, token);
+
+            let balance = Pallet::<T>::ocw_eth_call(rpc, body)?;
+
+            if !balance.is_zero() {
+                return Ok(());
+            }
+        }
+
+        Err(Error::<T>::NotTokenOwner)?
+    }
+}
+
+/// EVM networks such as Ethereum and BSC, tries ERC-721 first and falls back to ERC-1155
+pub struct Evm<T>(PhantomData<T>);
+
+impl<T: Config> OwnershipValidator for Evm<T> {
+    fn validate(rpc: &str, links: &[Vec<u8>], namespace: &[u8], token: &[u8]) -> DispatchResult {
+        match Erc721::<T>::validate(rpc, links, namespace, token) {
+            Err(e) if e == Error::<T>::OcwParseError.into() => {
+                Erc1155::<T>::validate(rpc, links, namespace, token)
+            }
+            result => result,
+        }
+    }
+}
+
+/// Metaplex NFTs on Solana, the namespace is the mint address and the token is empty
+pub struct Metaplex<T>(PhantomData<T>);
+
+impl<T: Config> OwnershipValidator for Metaplex<T> {
+    fn validate(rpc: &str, links: &[Vec<u8>], namespace: &[u8], token: &[u8]) -> DispatchResult {
+        ensure!(token.is_empty(), Error::<T>::BadMetadata);
+
+        let mint = str::from_utf8(namespace).map_err(|_| Error::<T>::BadMetadata)?;
+        ensure!(
+            !mint.is_empty() && mint.chars().all(|c| c.is_ascii_alphanumeric()),
+            Error::<T>::BadMetadata
+        );
+
+        for link in links {
+            let owner = match str::from_utf8(link) {
+                Ok(owner) if owner.chars().all(|c| c.is_ascii_alphanumeric()) => owner,
+                _ => continue,
+            };
+
+            let body = Pallet::<T>::construct_token_accounts_body(owner, mint);
+            let res = Ocw::<T>::ocw_post(rpc, body.into())?;
+
+            let json = res.json();
+            let accounts = match Pallet::<T>::json_get(&json, &["result", "value"]) {
+                Some(JsonValue::Array(accounts)) => accounts,
+                _ => Err(Error::<T>::OcwParseError)?,
+            };
+
+            let owned = accounts.iter().any(|account| {
+                Pallet::<T>::json_get(
+                    account,
+                    &["account", "data", "parsed", "info", "tokenAmount", "amount"],
+                )
+                .and_then(Pallet::<T>::json_string)
+                .and_then(|amount| amount.parse::<u64>().ok())
+                .map_or(false, |amount| amount > 0)
+            });
+
+            if owned {
+                return Ok(());
+            }
+        }
+
+        Err(Error::<T>::NotTokenOwner)?
+    }
+}
+
+/// NEP-171 contracts on NEAR, checked with `nft_token`
+pub struct Nep171<T>(PhantomData<T>);
+
+impl<T: Config> OwnershipValidator for Nep171<T> {
+    fn validate(rpc: &str, links: &[Vec<u8>], namespace: &[u8], token: &[u8]) -> DispatchResult {
+        let is_valid_char = |c: char| c != '"' && c != '\\' && !c.is_control();
+
+        let contract = str::from_utf8(namespace).map_err(|_| Error::<T>::BadMetadata)?;
+        let token = str::from_utf8(token).map_err(|_| Error::<T>::BadMetadata)?;
+        ensure!(
+            contract.chars().all(is_valid_char) && token.chars().all(is_valid_char),
+            Error::<T>::BadMetadata
+        );
+
+        let body = Pallet::<T>::construct_nft_token_body(contract, token);
+        let res = Ocw::<T>::ocw_post(rpc, body.into())?;
+
+        let json = res.json();
+        let bytes = match Pallet::<T>::json_get(&json, &["result", "result"]) {
+            Some(JsonValue::Array(bytes)) => bytes
+                .iter()
+                .map(|byte| match byte {
+                    JsonValue::Number(number) if number.integer <= u8::MAX as u64 => {
+                        Ok(number.integer as u8)
+                    }
+                    _ => Err(Error::<T>::OcwParseError),
+                })
+                .collect::<Result<Vec<u8>, _>>()?,
+            _ => Err(Error::<T>::OcwParseError)?,
+        };
+
+        let text = str::from_utf8(&bytes).map_err(|_| Error::<T>::OcwParseError)?;
+        let nft = parse_json(text).map_err(|_| Error::<T>::OcwParseError)?;
+
+        let owner = Pallet::<T>::json_get(&nft, &["owner_id"])
+            .and_then(Pallet::<T>::json_string)
+            .ok_or(Error::<T>::NotTokenOwner)?;
+
+        if links.iter().any(|link| link == owner.as_bytes()) {
+            Ok(())
+        } else {
+            Err(Error::<T>::NotTokenOwner)?
+        }
+    }
+}
@@ -1,13 +1,28 @@
-use crate::{mock::*, Deposit, Deposits, Error, External, Metadata, Ported, Porting, Preferred};
+use crate::{
+    migrations,
+    mock::*,
+    ocw::{Evm, Metaplex, Nep171, OwnershipValidator, Validators},
+    types, validation, Claimed, Deposit, Deposits, Error, External, FractionsOf, Metadata, Minter,
+    Ported, Porting, PortingFunding, PortingOf, Preferred, Presets, Profile, ReservedSymbols,
+};
 
 use codec::Decode;
-use frame_support::{assert_noop, assert_ok, traits::fungibles::Mutate};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{
+        fungibles::{metadata::Inspect as MetaInspect, Inspect, Mutate},
+        StorageVersion,
+    },
+};
 use parami_primitives::constants::DOLLARS;
-use parami_traits::{types::Network, Nfts, Swaps};
+use parami_traits::{types::Network, AccountMigration, Holders, Migrated, Royalties, Swaps};
 use parking_lot::RwLock;
-use sp_core::offchain::{testing, OffchainWorkerExt, TransactionPoolExt};
+use sp_core::{
+    offchain::{testing, OffchainDbExt, OffchainWorkerExt, TransactionPoolExt},
+    H160,
+};
 use sp_runtime::offchain::testing::PoolState;
-use sp_runtime::traits::AccountIdConversion;
+use sp_runtime::{traits::AccountIdConversion, DispatchError};
 use sp_std::prelude::*;
 use std::sync::Arc;
 
@@ -24,7 +39,9 @@ fn should_import() {
             Origin::signed(BOB),
             Network::Ethereum,
             namespace.clone(),
-            token.clone()
+            token.clone(),
+            None,
+            None
         ));
 
         let maybe_porting = <Porting<Test>>::get((Network::Ethereum, &namespace, &token));
@@ -51,7 +68,9 @@ fn should_fail_when_imported() {
                 Origin::signed(BOB),
                 Network::Ethereum,
                 namespace,
-                token.clone()
+                token.clone(),
+                None,
+                None
             ),
             Error::<Test>::Exists
         );
@@ -72,6 +91,8 @@ fn should_fail_when_importing() {
             Network::Ethereum,
             namespace.clone(),
             token.clone(),
+            None,
+            None
         ));
 
         assert_noop!(
@@ -79,7 +100,9 @@ fn should_fail_when_importing() {
                 Origin::signed(ALICE),
                 Network::Ethereum,
                 namespace,
-                token.clone()
+                token.clone(),
+                None,
+                None
             ),
             Error::<Test>::Exists
         );
@@ -98,6 +121,8 @@ fn should_fail_when_did_not_linked_network() {
                 Network::Ethereum,
                 namespace.clone(),
                 token.clone(),
+                None,
+                None
             ),
             Error::<Test>::NetworkNotLinked
         );
@@ -107,7 +132,7 @@ fn should_fail_when_did_not_linked_network() {
 #[test]
 fn should_create() {
     new_test_ext().execute_with(|| {
-        assert_ok!(Nft::kick(Origin::signed(BOB)));
+        assert_ok!(Nft::kick(Origin::signed(BOB), None, None));
 
         let maybe_nft = Nft::preferred(DID_BOB);
         assert_ne!(maybe_nft, None);
@@ -152,6 +177,214 @@ fn should_back() {
     });
 }
 
+#[test]
+fn should_kick_with_funding() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let goal = 1500 * DOLLARS;
+
+        assert_noop!(
+            Nft::kick(
+                Origin::signed(ALICE),
+                None,
+                Some(types::Funding {
+                    goal: 999 * DOLLARS,
+                    deadline: 10
+                })
+            ),
+            Error::<Test>::InsufficientGoal
+        );
+        assert_noop!(
+            Nft::kick(
+                Origin::signed(ALICE),
+                None,
+                Some(types::Funding { goal, deadline: 1 })
+            ),
+            Error::<Test>::Deadline
+        );
+
+        assert_ok!(Nft::kick(
+            Origin::signed(ALICE),
+            None,
+            Some(types::Funding { goal, deadline: 10 })
+        ));
+
+        let nft = NEXT_INSTANCE_ID;
+
+        System::assert_last_event(Event::Nft(crate::Event::FundingStarted(nft, goal, 10)));
+
+        assert_eq!(
+            Nft::funding_of(nft),
+            Some(types::Funding { goal, deadline: 10 })
+        );
+
+        // progress toward the goal
+
+        assert_ok!(Nft::back(Origin::signed(BOB), nft, 1200 * DOLLARS));
+
+        System::assert_last_event(Event::Nft(crate::Event::FundingProgressed(
+            nft,
+            1200 * DOLLARS,
+            goal,
+        )));
+
+        assert_noop!(
+            Nft::mint(
+                Origin::signed(ALICE),
+                nft,
+                b"Test Token".to_vec(),
+                b"XTT".to_vec()
+            ),
+            Error::<Test>::InsufficientBalance
+        );
+
+        assert_ok!(Nft::back(Origin::signed(CHARLIE), nft, 300 * DOLLARS));
+
+        System::assert_last_event(Event::Nft(crate::Event::FundingReached(nft, goal)));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            nft,
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        System::set_block_number(10);
+
+        assert_noop!(
+            Nft::withdraw_backing(Origin::signed(BOB), nft),
+            Error::<Test>::Minted
+        );
+    });
+}
+
+#[test]
+fn should_port_with_funding() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let namespace = NAMESPACE.to_vec();
+        let token = vec![0x22];
+        let funding = types::Funding {
+            goal: 1500 * DOLLARS,
+            deadline: 10,
+        };
+
+        let _result = Linker::insert_link(DID_BOB, Network::Ethereum, "something".into(), DID_BOB);
+
+        assert_ok!(Nft::port(
+            Origin::signed(BOB),
+            Network::Ethereum,
+            namespace.clone(),
+            token.clone(),
+            None,
+            Some(funding.clone())
+        ));
+        assert_ok!(Nft::submit_porting(
+            Origin::none(),
+            DID_BOB,
+            Network::Ethereum,
+            namespace.clone(),
+            token.clone(),
+            true,
+        ));
+
+        assert_eq!(
+            <PortingFunding<Test>>::get((Network::Ethereum, &namespace, &token)),
+            None
+        );
+        assert_eq!(Nft::porting_of(&DID_BOB, None, 10), vec![]);
+        assert_eq!(Nft::funding_of(NEXT_INSTANCE_ID), Some(funding));
+    });
+}
+
+#[test]
+fn should_withdraw_backing() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Nft::kick(
+            Origin::signed(ALICE),
+            None,
+            Some(types::Funding {
+                goal: 1500 * DOLLARS,
+                deadline: 10
+            })
+        ));
+
+        let nft = NEXT_INSTANCE_ID;
+        let meta = <Metadata<Test>>::get(nft).unwrap();
+
+        let bob = Balances::free_balance(&BOB);
+
+        assert_noop!(
+            Nft::withdraw_backing(Origin::signed(BOB), 0),
+            Error::<Test>::NotFailed
+        );
+
+        assert_ok!(Nft::back(Origin::signed(BOB), nft, 500 * DOLLARS));
+        assert_ok!(Nft::back(Origin::signed(CHARLIE), nft, 300 * DOLLARS));
+
+        assert_noop!(
+            Nft::withdraw_backing(Origin::signed(BOB), nft),
+            Error::<Test>::NotFailed
+        );
+
+        // deadline reached without reaching the goal
+
+        System::set_block_number(10);
+
+        assert_noop!(
+            Nft::back(Origin::signed(BOB), nft, 700 * DOLLARS),
+            Error::<Test>::Deadline
+        );
+
+        assert_ok!(Nft::withdraw_backing(Origin::signed(BOB), nft));
+
+        assert_eq!(Balances::free_balance(&BOB), bob);
+        assert_eq!(<Deposits<Test>>::get(nft, &DID_BOB), None);
+        assert_eq!(<Deposit<Test>>::get(nft), Some(300 * DOLLARS));
+        assert_eq!(Balances::free_balance(&meta.pot), 300 * DOLLARS);
+
+        assert_noop!(
+            Nft::withdraw_backing(Origin::signed(BOB), nft),
+            Error::<Test>::NotExists
+        );
+    });
+}
+
+#[test]
+fn should_mint_with_backing_of_each_nft() {
+    new_test_ext().execute_with(|| {
+        // both NFTs of the KOL share a pot
+        assert_eq!(Nft::meta(0).unwrap().pot, Nft::meta(1).unwrap().pot);
+
+        let pot = Nft::meta(0).unwrap().pot;
+
+        assert_ok!(Nft::back(Origin::signed(BOB), 0, 2000 * DOLLARS));
+        assert_ok!(Nft::back(Origin::signed(CHARLIE), 1, 500 * DOLLARS));
+
+        assert_noop!(
+            Nft::mint(
+                Origin::signed(ALICE),
+                1,
+                b"Test Token".to_vec(),
+                b"XTT".to_vec()
+            ),
+            Error::<Test>::InsufficientBalance
+        );
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            0,
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        // the backing of the other NFT stays in the pot
+        assert_eq!(Balances::free_balance(&pot), 500 * DOLLARS);
+    });
+}
+
 #[test]
 fn should_fail_when_self() {
     new_test_ext().execute_with(|| {
@@ -270,6 +503,147 @@ fn should_fail_when_minted() {
     });
 }
 
+#[test]
+fn should_validate_names_and_symbols() {
+    assert!(validation::is_valid_name(b"Test Token1", 46));
+    assert!(!validation::is_valid_name(b"", 46));
+    assert!(!validation::is_valid_name(b"1Token", 46));
+    assert!(!validation::is_valid_name(b"Test Token ", 46));
+    assert!(!validation::is_valid_name(b"Test\tToken", 46));
+    assert!(!validation::is_valid_name(&[b'x'; 47], 46));
+
+    assert!(validation::is_valid_symbol(b"XTT1", 46));
+    assert!(!validation::is_valid_symbol(b"", 46));
+    assert!(!validation::is_valid_symbol(b"1XTT", 46));
+    assert!(!validation::is_valid_symbol(b"X TT", 46));
+    assert!(!validation::is_valid_symbol(&[b'x'; 47], 46));
+
+    assert_eq!(validation::normalize_symbol(b"xTt1"), b"XTT1".to_vec());
+}
+
+#[test]
+fn should_fail_when_bad_metadata() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+
+        assert_ok!(Nft::back(Origin::signed(BOB), nft, 1000 * DOLLARS));
+
+        assert_noop!(
+            Nft::mint(Origin::signed(ALICE), nft, b"Test Token".to_vec(), vec![]),
+            Error::<Test>::BadMetadata
+        );
+
+        // symbol is checked against its own length
+        assert_noop!(
+            Nft::mint(
+                Origin::signed(ALICE),
+                nft,
+                b"Test Token".to_vec(),
+                vec![b'X'; 47]
+            ),
+            Error::<Test>::BadMetadata
+        );
+
+        assert_noop!(
+            Nft::mint(
+                Origin::signed(ALICE),
+                nft,
+                b"Test Token".to_vec(),
+                b"X TT".to_vec()
+            ),
+            Error::<Test>::BadMetadata
+        );
+    });
+}
+
+#[test]
+fn should_fail_when_symbol_taken() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Nft::back(Origin::signed(BOB), 0, 1000 * DOLLARS));
+        assert_ok!(Nft::back(Origin::signed(BOB), 1, 1000 * DOLLARS));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            0,
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        assert_eq!(Nft::symbol_of(b"xtt"), Some(0));
+        assert!(!Nft::is_symbol_available(b"Xtt", &DID_ALICE));
+
+        assert_noop!(
+            Nft::mint(
+                Origin::signed(ALICE),
+                1,
+                b"Test Token".to_vec(),
+                b"xtt".to_vec()
+            ),
+            Error::<Test>::SymbolTaken
+        );
+
+        assert_noop!(
+            Nft::set_reserved_symbol(Origin::root(), b"XTT".to_vec(), Some(DID_BOB)),
+            Error::<Test>::SymbolTaken
+        );
+    });
+}
+
+#[test]
+fn should_set_reserved_symbol() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+
+        assert_ok!(Nft::back(Origin::signed(BOB), nft, 1000 * DOLLARS));
+
+        assert_noop!(
+            Nft::set_reserved_symbol(Origin::signed(ALICE), b"BRAND".to_vec(), Some(DID_ALICE)),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(Nft::set_reserved_symbol(
+            Origin::root(),
+            b"brand".to_vec(),
+            Some(DID_BOB)
+        ));
+        System::assert_last_event(Event::Nft(crate::Event::SymbolReserved(
+            b"BRAND".to_vec(),
+            DID_BOB,
+        )));
+
+        assert!(!Nft::is_symbol_available(b"Brand", &DID_ALICE));
+        assert!(Nft::is_symbol_available(b"Brand", &DID_BOB));
+
+        assert_noop!(
+            Nft::mint(
+                Origin::signed(ALICE),
+                nft,
+                b"Test Token".to_vec(),
+                b"BRAND".to_vec()
+            ),
+            Error::<Test>::SymbolReserved
+        );
+
+        assert_ok!(Nft::set_reserved_symbol(
+            Origin::root(),
+            b"BRAND".to_vec(),
+            Some(DID_ALICE)
+        ));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            nft,
+            b"Test Token".to_vec(),
+            b"Brand".to_vec()
+        ));
+
+        assert_eq!(Nft::symbol_of(b"BRAND"), Some(nft));
+        assert_eq!(<ReservedSymbols<Test>>::get(b"BRAND".to_vec()), None);
+    });
+}
+
 #[test]
 fn should_fail_when_insufficient() {
     new_test_ext().execute_with(|| {
@@ -299,12 +673,17 @@ fn should_claim() {
             b"XTT".to_vec()
         ));
 
+        System::set_block_number(10);
+
         assert_ok!(Nft::claim(Origin::signed(BOB), nft));
         assert_ok!(Nft::claim(Origin::signed(CHARLIE), nft));
 
         assert_eq!(Assets::balance(nft, &BOB), 666666666666666666666666);
         assert_eq!(Assets::balance(nft, &CHARLIE), 333333333333333333333333);
 
+        assert!(<FractionsOf<Test>>::contains_key(&BOB, nft));
+        assert!(<FractionsOf<Test>>::contains_key(&CHARLIE, nft));
+
         assert_eq!(<Deposits<Test>>::get(nft, &DID_BOB), None);
         assert_eq!(<Deposits<Test>>::get(nft, &DID_CHARLIE), None);
 
@@ -313,8 +692,6 @@ fn should_claim() {
             Error::<Test>::NotExists
         );
 
-        System::set_block_number(5);
-
         assert_ok!(Nft::claim(Origin::signed(ALICE), nft));
 
         assert_eq!(Assets::balance(nft, &ALICE), 1_000_000 * DOLLARS);
@@ -322,37 +699,308 @@ fn should_claim() {
     });
 }
 
-fn mock_validate_request(
-    ether_endpoint: &str,
-    body: String,
-    response: &str,
-) -> testing::PendingRequest {
-    testing::PendingRequest {
-        method: "POST".into(),
-        uri: ether_endpoint.into(),
-        sent: true,
-        headers: vec![(
-            "User-Agent".into(),
-            "GoogleBot (compatible; ParamiWorker/1.0; +http://parami.io/worker/)".into(),
-        )],
-        body: body.into(),
-        response: Some(response.into()),
-        ..Default::default()
-    }
-}
+#[test]
+fn should_query_crowdfunding() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
 
-fn offchain_execute(
-    mock_requests: Vec<testing::PendingRequest>,
-    test_executable: impl FnOnce(Arc<RwLock<PoolState>>) -> (),
-) {
-    let (offchain, state) = testing::TestOffchainExt::new();
-    let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+        assert_ok!(Nft::back(Origin::signed(BOB), nft, 2000 * DOLLARS));
+        assert_ok!(Nft::back(Origin::signed(CHARLIE), nft, 1000 * DOLLARS));
 
-    let mut t = new_test_ext();
-    t.register_extension(OffchainWorkerExt::new(offchain));
-    t.register_extension(TransactionPoolExt::new(pool));
+        let meta = Nft::meta(nft).unwrap();
+        let pot = Balances::free_balance(&meta.pot);
 
-    {
+        assert_eq!(
+            Nft::crowdfunding(nft),
+            Ok((pot, 3000 * DOLLARS, 1000 * DOLLARS))
+        );
+
+        let mut shares = Nft::shares_of(nft, None, 10).unwrap();
+        shares.sort();
+        assert_eq!(
+            shares,
+            vec![
+                (DID_CHARLIE, 1000 * DOLLARS, 0, 0),
+                (DID_BOB, 2000 * DOLLARS, 0, 0),
+            ]
+        );
+
+        assert_eq!(Nft::lockup_of(nft), Err(Error::<Test>::NotExists.into()));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            nft,
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        System::set_block_number(4);
+
+        let mut shares = Nft::shares_of(nft, None, 10).unwrap();
+        shares.sort();
+        assert_eq!(
+            shares,
+            vec![
+                (
+                    DID_CHARLIE,
+                    1000 * DOLLARS,
+                    333333333333333333333333,
+                    133333333333333333333333
+                ),
+                (
+                    DID_BOB,
+                    2000 * DOLLARS,
+                    666666666666666666666666,
+                    266666666666666666666666
+                ),
+                (DID_ALICE, pot, 1_000_000 * DOLLARS, 0),
+            ]
+        );
+
+        assert_eq!(Nft::lockup_of(nft), Ok(5));
+
+        assert_eq!(Nft::porting_of(&DID_BOB, None, 10), vec![]);
+
+        let _result = Linker::insert_link(DID_BOB, Network::Ethereum, "something".into(), DID_BOB);
+
+        assert_ok!(Nft::port(
+            Origin::signed(BOB),
+            Network::Ethereum,
+            NAMESPACE.to_vec(),
+            vec![0x22],
+            None,
+            None
+        ));
+
+        assert_eq!(
+            Nft::porting_of(&DID_BOB, None, 10),
+            vec![(Network::Ethereum, NAMESPACE.to_vec(), vec![0x22], 9)]
+        );
+    });
+}
+
+#[test]
+fn should_paginate_queries() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+
+        assert_ok!(Nft::back(Origin::signed(BOB), nft, 2000 * DOLLARS));
+        assert_ok!(Nft::back(Origin::signed(CHARLIE), nft, 1000 * DOLLARS));
+
+        let first = Nft::shares_of(nft, None, 1).unwrap();
+        assert_eq!(first.len(), 1);
+
+        let second = Nft::shares_of(nft, Some(first[0].0), 10).unwrap();
+        assert_eq!(second.len(), 1);
+        assert_ne!(first[0].0, second[0].0);
+
+        assert_eq!(Nft::shares_of(nft, Some(second[0].0), 10), Ok(vec![]));
+
+        let _result = Linker::insert_link(DID_BOB, Network::Ethereum, "something".into(), DID_BOB);
+
+        for token in [0x22, 0x23] {
+            assert_ok!(Nft::port(
+                Origin::signed(BOB),
+                Network::Ethereum,
+                NAMESPACE.to_vec(),
+                vec![token],
+                None,
+                None
+            ));
+        }
+
+        let first = Nft::porting_of(&DID_BOB, None, 1);
+        assert_eq!(first.len(), 1);
+
+        let (network, namespace, token, _) = first[0].clone();
+        let second = Nft::porting_of(&DID_BOB, Some((network, namespace, token.clone())), 10);
+        assert_eq!(second.len(), 1);
+        assert_ne!(token, second[0].2);
+
+        assert_eq!(Nft::porting_of(&DID_CHARLIE, None, 10), vec![]);
+    });
+}
+
+#[test]
+fn should_claim_vested() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+
+        assert_ok!(Nft::back(Origin::signed(BOB), nft, 2000 * DOLLARS));
+        assert_ok!(Nft::back(Origin::signed(CHARLIE), nft, 1000 * DOLLARS));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            nft,
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        System::set_block_number(1);
+
+        assert_eq!(Nft::claimable(nft, &DID_BOB), Ok(0));
+        assert_noop!(
+            Nft::claim(Origin::signed(BOB), nft),
+            Error::<Test>::NotVested
+        );
+
+        System::set_block_number(4);
+
+        assert_eq!(Nft::claimable(nft, &DID_BOB), Ok(266666666666666666666666));
+
+        assert_ok!(Nft::claim(Origin::signed(BOB), nft));
+
+        assert_eq!(Assets::balance(nft, &BOB), 266666666666666666666666);
+        assert_eq!(
+            <Claimed<Test>>::get(nft, &DID_BOB),
+            266666666666666666666666
+        );
+        assert_eq!(Nft::claimable(nft, &DID_BOB), Ok(0));
+
+        assert_noop!(
+            Nft::claim(Origin::signed(BOB), nft),
+            Error::<Test>::NotVested
+        );
+        assert_noop!(
+            Nft::claim(Origin::signed(ALICE), nft),
+            Error::<Test>::NotVested
+        );
+
+        System::set_block_number(5);
+
+        assert_ok!(Nft::claim(Origin::signed(ALICE), nft));
+
+        assert_eq!(Assets::balance(nft, &ALICE), 500_000 * DOLLARS);
+
+        System::set_block_number(10);
+
+        assert_ok!(Nft::claim(Origin::signed(BOB), nft));
+
+        System::assert_last_event(Event::Nft(crate::Event::Claimed(
+            DID_BOB,
+            nft,
+            400000000000000000000000,
+        )));
+
+        assert_eq!(Assets::balance(nft, &BOB), 666666666666666666666666);
+        assert_eq!(<Deposits<Test>>::get(nft, &DID_BOB), None);
+        assert_eq!(<Claimed<Test>>::get(nft, &DID_BOB), 0);
+        assert_eq!(
+            Nft::claimable(nft, &DID_BOB),
+            Err(Error::<Test>::NotExists.into())
+        );
+    });
+}
+
+#[test]
+fn should_mint_with_preset() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let profile = types::Profile {
+            supply: 10_000_000 * DOLLARS,
+            decimals: 12,
+            swap: 2,
+            backers: 5,
+            kol: 2,
+            treasury: 1,
+        };
+
+        assert_noop!(
+            Nft::set_preset(Origin::signed(ALICE), 1, Some(profile.clone())),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Nft::set_preset(
+                Origin::root(),
+                1,
+                Some(types::Profile {
+                    swap: 0,
+                    ..profile.clone()
+                })
+            ),
+            Error::<Test>::BadProfile
+        );
+
+        assert_ok!(Nft::set_preset(Origin::root(), 1, Some(profile.clone())));
+        System::assert_last_event(Event::Nft(crate::Event::PresetSet(1)));
+        assert_eq!(<Presets<Test>>::get(1), Some(profile.clone()));
+
+        assert_noop!(
+            Nft::kick(Origin::signed(BOB), Some(2), None),
+            Error::<Test>::NotExists
+        );
+        assert_ok!(Nft::kick(Origin::signed(BOB), Some(1), None));
+
+        let nft = Nft::preferred(DID_BOB).unwrap();
+        assert_eq!(<Profile<Test>>::get(nft), Some(profile));
+
+        assert_ok!(Nft::back(Origin::signed(CHARLIE), nft, 2000 * DOLLARS));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(BOB),
+            nft,
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        let meta = <Metadata<Test>>::get(nft).unwrap();
+
+        assert_eq!(Assets::total_issuance(nft), 10_000_000 * DOLLARS);
+        assert_eq!(Assets::decimals(&nft), 12);
+        assert_eq!(
+            Assets::balance(nft, &TreasuryAccount::get()),
+            1_000_000 * DOLLARS
+        );
+        assert_eq!(Assets::balance(nft, &meta.pot), 7_000_000 * DOLLARS);
+
+        System::set_block_number(11);
+
+        assert_ok!(Nft::claim(Origin::signed(CHARLIE), nft));
+        assert_ok!(Nft::claim(Origin::signed(BOB), nft));
+
+        assert_eq!(Assets::balance(nft, &CHARLIE), 5_000_000 * DOLLARS);
+        assert_eq!(Assets::balance(nft, &BOB), 2_000_000 * DOLLARS);
+        assert_eq!(Assets::balance(nft, &meta.pot), 0);
+
+        assert_ok!(Nft::set_preset(Origin::root(), 1, None));
+        System::assert_last_event(Event::Nft(crate::Event::PresetRemoved(1)));
+        assert_eq!(<Presets<Test>>::get(1), None);
+    });
+}
+
+fn mock_validate_request(
+    ether_endpoint: &str,
+    body: String,
+    response: &str,
+) -> testing::PendingRequest {
+    testing::PendingRequest {
+        method: "POST".into(),
+        uri: ether_endpoint.into(),
+        sent: true,
+        headers: vec![(
+            "User-Agent".into(),
+            "GoogleBot (compatible; ParamiWorker/1.0; +http://parami.io/worker/)".into(),
+        )],
+        body: body.into(),
+        response: Some(response.into()),
+        ..Default::default()
+    }
+}
+
+fn offchain_execute(
+    mock_requests: Vec<testing::PendingRequest>,
+    test_executable: impl FnOnce(Arc<RwLock<PoolState>>) -> (),
+) {
+    let (offchain, state) = testing::TestOffchainExt::new();
+    let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+
+    let mut t = new_test_ext();
+    t.register_extension(OffchainDbExt::new(offchain.clone()));
+    t.register_extension(OffchainWorkerExt::new(offchain));
+    t.register_extension(TransactionPoolExt::new(pool));
+
+    {
         let mut state = state.write();
         for r in mock_requests.into_iter() {
             state.expect_request(r);
@@ -465,6 +1113,8 @@ fn should_import_nft_by_ocw() {
                 Network::Ethereum,
                 namespace.clone(),
                 token.into(),
+                None,
+                None
             ));
 
             assert_ok!(Nft::ocw_begin_block(System::block_number()));
@@ -491,57 +1141,668 @@ fn should_import_nft_by_ocw() {
 }
 
 #[test]
-fn should_sumbit_porting() {
-    new_test_ext().execute_with(|| {
-        let namespace = NAMESPACE.to_vec();
-        let token = vec![0x22];
-        let did = DID_BOB;
+fn should_validate_erc1155_balance() {
+    let bsc_endpoint = "http://binance.endpoint/example";
+    let links: &[Vec<u8>] = &[
+        vec![0x11; 20],
+        vec![
+            219, 208, 68, 36, 49, 141, 30, 6, 179, 66, 89, 173, 214, 75, 241, 10, 142, 180, 90, 135,
+        ],
+    ];
+    let contract_address = b"contractaddress";
+    let token = 546u64.to_be_bytes();
 
-        let _result = Linker::insert_link(did, Network::Ethereum, "something".into(), did);
+    let reverted =
+        r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32000,"message":"execution reverted"}}"#;
+    let empty = r#"{"jsonrpc":"2.0","id":1,"result":"0x0000000000000000000000000000000000000000000000000000000000000000"}"#;
+    let owned = r#"{"jsonrpc":"2.0","id":1,"result":"0x0000000000000000000000000000000000000000000000000000000000000001"}"#;
 
-        assert_ok!(Nft::port(
-            Origin::signed(BOB),
-            Network::Ethereum,
-            namespace.clone(),
-            token.clone()
-        ));
-        assert_ok!(Nft::submit_porting(
-            frame_system::RawOrigin::None.into(),
-            DID_BOB,
-            Network::Ethereum,
-            namespace.clone(),
-            token.clone(),
-            true,
-        ));
+    offchain_execute(
+        vec![
+            mock_validate_request(
+                bsc_endpoint,
+                Nft::construct_request_body(contract_address, &token),
+                reverted,
+            ),
+            mock_validate_request(
+                bsc_endpoint,
+                Nft::construct_balance_of_body(contract_address, &links[0], &token),
+                empty,
+            ),
+            mock_validate_request(
+                bsc_endpoint,
+                Nft::construct_balance_of_body(contract_address, &links[1], &token),
+                owned,
+            ),
+        ],
+        |_| {
+            assert_ok!(Evm::<Test>::validate(
+                bsc_endpoint,
+                links,
+                contract_address,
+                &token
+            ));
+        },
+    );
+}
 
-        let token: &Vec<u8> = &token.into();
-        assert!(<Porting<Test>>::get((Network::Ethereum, &namespace, token)).is_none());
+#[test]
+fn should_import_bsc_nft_by_ocw() {
+    let bsc_endpoint = "http://binance.endpoint/example";
+    let profile: Vec<u8> = vec![
+        219, 208, 68, 36, 49, 141, 30, 6, 179, 66, 89, 173, 214, 75, 241, 10, 142, 180, 90, 135,
+    ];
+    let contract_address = b"contractaddress";
+    let token = 546u64.to_be_bytes();
 
-        assert_eq!(
-            <Ported<Test>>::get((Network::Ethereum, &namespace, token)).expect("should be ported"),
-            // genesis config creates the first token, so we got 2 here.
-            2,
-        );
+    let body = Nft::construct_request_body(contract_address, &token);
+    let res = r#"{"jsonrpc":"2.0","id":1,"result":"0x000000000000000000000000dbd04424318d1e06b34259add64bf10a8eb45a87"}"#;
 
-        let external = <External<Test>>::get(2).expect("external should have data");
-        assert_eq!(external.owner, did);
-        assert_eq!(external.network, Network::Ethereum);
-        assert_eq!(external.namespace, namespace);
-        assert_eq!(external.token, token.clone());
+    offchain_execute(
+        vec![mock_validate_request(bsc_endpoint, body, res)],
+        |pool_state| {
+            let _result = Linker::insert_link(DID_BOB, Network::Binance, profile, DID_BOB);
 
-        let subaccount_id =
-            <Test as crate::Config>::PalletId::get().into_sub_account_truncating(DID_BOB);
+            assert_ok!(Nft::port(
+                Origin::signed(BOB),
+                Network::Binance,
+                contract_address.to_vec(),
+                token.into(),
+                None,
+                None
+            ));
 
-        let metadata = <Metadata<Test>>::get(2).expect("meta should have data");
-        assert_eq!(metadata.owner, did);
-        assert_eq!(metadata.class_id, 2);
-        assert_eq!(metadata.pot, subaccount_id);
-        assert_eq!(metadata.minted, false);
-        assert_eq!(metadata.token_asset_id, 2);
+            assert_ok!(Nft::ocw_begin_block(System::block_number()));
 
-        let preferred = <Preferred<Test>>::get(DID_BOB).expect("prefered should have data");
-        assert_eq!(preferred, 2);
-    });
+            let tx = pool_state.write().transactions.pop().unwrap();
+            let tx = Extrinsic::decode(&mut &*tx).unwrap();
+
+            assert_eq!(
+                tx.call,
+                Call::Nft(crate::Call::submit_porting {
+                    did: DID_BOB,
+                    network: Network::Binance,
+                    namespace: contract_address.to_vec(),
+                    token: token.to_vec(),
+                    validated: true
+                })
+            );
+        },
+    );
+}
+
+#[test]
+fn should_validate_metaplex_token_account() {
+    let sol_endpoint = "http://solana.endpoint/example";
+    let owner = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+    let mint = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU";
+    let links: &[Vec<u8>] = &[owner.into()];
+
+    let body = Nft::construct_token_accounts_body(owner, mint);
+    let owned = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":[{"pubkey":"C2gJg6tKpQs41PRS1nC8aw3ZKNZK3HQQZGVrDFDup5nx","account":{"data":{"parsed":{"info":{"mint":"7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU","owner":"9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM","tokenAmount":{"amount":"1","decimals":0}}},"program":"spl-token"},"lamports":2039280}}]}}"#;
+    let empty = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":[]}}"#;
+
+    offchain_execute(
+        vec![
+            mock_validate_request(sol_endpoint, body.clone(), owned),
+            mock_validate_request(sol_endpoint, body, empty),
+        ],
+        |_| {
+            assert_ok!(Metaplex::<Test>::validate(
+                sol_endpoint,
+                links,
+                mint.as_bytes(),
+                &[]
+            ));
+
+            assert_noop!(
+                Metaplex::<Test>::validate(sol_endpoint, links, mint.as_bytes(), &[]),
+                Error::<Test>::NotTokenOwner
+            );
+
+            assert_noop!(
+                Metaplex::<Test>::validate(sol_endpoint, links, mint.as_bytes(), &[0x01]),
+                Error::<Test>::BadMetadata
+            );
+        },
+    );
+}
+
+#[test]
+fn should_validate_nep171_token_owner() {
+    let near_endpoint = "http://near.endpoint/example";
+    let links: &[Vec<u8>] = &[b"alice.near".to_vec()];
+    let contract = "nft.example.near";
+    let token = "42";
+
+    let nft = r#"{"token_id":"42","owner_id":"alice.near","metadata":null}"#;
+    let bytes: Vec<String> = nft.bytes().map(|b| b.to_string()).collect();
+    let res = format!(
+        r#"{{"jsonrpc":"2.0","id":1,"result":{{"result":[{}],"logs":[],"block_height":1}}}}"#,
+        bytes.join(",")
+    );
+
+    let body = Nft::construct_nft_token_body(contract, token);
+
+    offchain_execute(
+        vec![mock_validate_request(near_endpoint, body.clone(), &res)],
+        |_| {
+            assert_ok!(Nep171::<Test>::validate(
+                near_endpoint,
+                links,
+                contract.as_bytes(),
+                token.as_bytes()
+            ));
+
+            assert_noop!(
+                Nep171::<Test>::validate(
+                    near_endpoint,
+                    links,
+                    contract.as_bytes(),
+                    br#"42","x":""#
+                ),
+                Error::<Test>::BadMetadata
+            );
+        },
+    );
+
+    offchain_execute(
+        vec![mock_validate_request(near_endpoint, body, &res)],
+        |_| {
+            assert_noop!(
+                Nep171::<Test>::validate(
+                    near_endpoint,
+                    &[b"bob.near".to_vec()],
+                    contract.as_bytes(),
+                    token.as_bytes()
+                ),
+                Error::<Test>::NotTokenOwner
+            );
+        },
+    );
+}
+
+#[test]
+fn should_report_ownership_lost_by_ocw() {
+    let ether_endpoint = "http://etherum.endpoint/example";
+    let profile: Vec<u8> = vec![
+        219, 208, 68, 36, 49, 141, 30, 6, 179, 66, 89, 173, 214, 75, 241, 10, 142, 180, 90, 135,
+    ];
+
+    let body = Nft::construct_request_body(&NAMESPACE, &[0x01]);
+    let res = r#"{"jsonrpc":"2.0","id":1,"result":"0x0000000000000000000000001111111111111111111111111111111111111111"}"#;
+
+    offchain_execute(
+        vec![mock_validate_request(ether_endpoint, body, res)],
+        |pool_state| {
+            let _result = Linker::insert_link(DID_ALICE, Network::Ethereum, profile, DID_ALICE);
+
+            System::set_block_number(9);
+
+            assert_ok!(Nft::ocw_begin_block(System::block_number()));
+            assert!(pool_state.read().transactions.is_empty());
+
+            System::set_block_number(10);
+
+            assert_ok!(Nft::ocw_begin_block(System::block_number()));
+
+            let tx = pool_state.write().transactions.pop().unwrap();
+            let tx = Extrinsic::decode(&mut &*tx).unwrap();
+
+            assert_eq!(tx.signature, None);
+            assert_eq!(
+                tx.call,
+                Call::Nft(crate::Call::report_ownership_lost { nft: 1 })
+            );
+        },
+    );
+}
+
+#[test]
+fn should_register_validators() {
+    type NftValidators = <Test as crate::Config>::Validators;
+
+    assert_eq!(
+        NftValidators::networks(),
+        vec![
+            Network::Binance,
+            Network::Ethereum,
+            Network::Near,
+            Network::Solana
+        ]
+    );
+
+    assert_eq!(
+        NftValidators::validate(Network::Polkadot, "", &[], &[], &[]),
+        None
+    );
+    assert_eq!(
+        NftValidators::validate(Network::Solana, "", &[], &[], &[0x01]),
+        Some(Err(Error::<Test>::BadMetadata.into()))
+    );
+}
+
+#[test]
+fn should_reverify_in_batches() {
+    let ether_endpoint = "http://etherum.endpoint/example";
+    let profile: Vec<u8> = vec![
+        219, 208, 68, 36, 49, 141, 30, 6, 179, 66, 89, 173, 214, 75, 241, 10, 142, 180, 90, 135,
+    ];
+    let res = r#"{"jsonrpc":"2.0","id":1,"result":"0x0000000000000000000000001111111111111111111111111111111111111111"}"#;
+
+    let (offchain, state) = testing::TestOffchainExt::new();
+    let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+
+    let mut t = new_test_ext();
+    t.register_extension(OffchainDbExt::new(offchain.clone()));
+    t.register_extension(OffchainWorkerExt::new(offchain));
+    t.register_extension(TransactionPoolExt::new(pool));
+
+    t.execute_with(|| {
+        let _result = Linker::insert_link(DID_ALICE, Network::Ethereum, profile, DID_ALICE);
+
+        <External<Test>>::insert(
+            0,
+            types::External {
+                network: Network::Ethereum,
+                namespace: NAMESPACE.to_vec(),
+                token: vec![0x02],
+                owner: DID_ALICE,
+            },
+        );
+
+        // one NFT a block, in the order of storage
+        for (block, (nft, external)) in (10..).zip(<External<Test>>::iter()) {
+            let body = Nft::construct_request_body(&external.namespace, &external.token);
+            state
+                .write()
+                .expect_request(mock_validate_request(ether_endpoint, body, res));
+
+            System::set_block_number(block);
+
+            assert_ok!(Nft::ocw_begin_block(System::block_number()));
+
+            let tx = pool_state.write().transactions.pop().unwrap();
+            let tx = Extrinsic::decode(&mut &*tx).unwrap();
+
+            assert_eq!(
+                tx.call,
+                Call::Nft(crate::Call::report_ownership_lost { nft })
+            );
+            assert!(pool_state.read().transactions.is_empty());
+        }
+
+        // sweep finished until the next interval
+
+        System::set_block_number(12);
+
+        assert_ok!(Nft::ocw_begin_block(System::block_number()));
+        assert!(pool_state.read().transactions.is_empty());
+    });
+}
+
+#[test]
+fn should_freeze_and_reclaim() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let token = vec![0x01];
+
+        assert_noop!(
+            Nft::report_ownership_lost(Origin::signed(BOB), 1),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Nft::report_ownership_lost(Origin::none(), 0),
+            Error::<Test>::NotExists
+        );
+
+        assert_ok!(Nft::report_ownership_lost(Origin::none(), 1));
+        System::assert_last_event(Event::Nft(crate::Event::OwnershipLost(DID_ALICE, 1)));
+
+        assert_eq!(<Frozen<Test>>::get(1), Some(1));
+        assert!(Nft::is_frozen(1));
+
+        assert_noop!(
+            Nft::report_ownership_lost(Origin::none(), 1),
+            Error::<Test>::Frozen
+        );
+        assert_noop!(
+            Nft::back(Origin::signed(BOB), 1, 1000 * DOLLARS),
+            Error::<Test>::Frozen
+        );
+
+        let _result = Linker::insert_link(DID_BOB, Network::Ethereum, "something".into(), DID_BOB);
+
+        assert_ok!(Nft::port(
+            Origin::signed(BOB),
+            Network::Ethereum,
+            NAMESPACE.to_vec(),
+            token.clone(),
+            None,
+            None
+        ));
+        assert_ok!(Nft::submit_porting(
+            Origin::none(),
+            DID_BOB,
+            Network::Ethereum,
+            NAMESPACE.to_vec(),
+            token.clone(),
+            true,
+        ));
+        System::assert_last_event(Event::Nft(crate::Event::Reclaimed(DID_BOB, 1)));
+
+        assert!(!Nft::is_frozen(1));
+        assert_eq!(<Metadata<Test>>::get(1).unwrap().owner, DID_BOB);
+        assert_eq!(<External<Test>>::get(1).unwrap().owner, DID_BOB);
+        assert_eq!(
+            <Ported<Test>>::get((Network::Ethereum, NAMESPACE.to_vec(), token.clone())),
+            Some(1)
+        );
+        assert_eq!(Nft::preferred(DID_BOB), Some(1));
+        assert_eq!(Nft::preferred(DID_ALICE), None);
+
+        assert_noop!(
+            Nft::port(
+                Origin::signed(BOB),
+                Network::Ethereum,
+                NAMESPACE.to_vec(),
+                token,
+                None,
+                None
+            ),
+            Error::<Test>::Exists
+        );
+    });
+}
+
+#[test]
+fn should_unport() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_noop!(Nft::unport(Origin::signed(BOB), 1), Error::<Test>::NotOwned);
+        assert_noop!(
+            Nft::unport(Origin::signed(ALICE), 0),
+            Error::<Test>::NotExists
+        );
+
+        let external = <External<Test>>::get(1).unwrap();
+
+        assert_ok!(Nft::unport(Origin::signed(ALICE), 1));
+        System::assert_last_event(Event::Nft(crate::Event::Unported(DID_ALICE, 1)));
+
+        assert!(Nft::is_frozen(1));
+        assert_eq!(<External<Test>>::get(1), None);
+        assert_eq!(
+            <Ported<Test>>::get((
+                external.network,
+                external.namespace.clone(),
+                external.token.clone()
+            )),
+            None
+        );
+
+        assert_noop!(
+            Nft::unport(Origin::signed(ALICE), 1),
+            Error::<Test>::NotExists
+        );
+
+        // the token can be ported again as a new NFT
+        let _result = Linker::insert_link(DID_BOB, Network::Ethereum, "something".into(), DID_BOB);
+
+        assert_ok!(Nft::port(
+            Origin::signed(BOB),
+            external.network,
+            external.namespace.clone(),
+            external.token.clone(),
+            None,
+            None
+        ));
+        assert_ok!(Nft::submit_porting(
+            Origin::none(),
+            DID_BOB,
+            external.network,
+            external.namespace.clone(),
+            external.token.clone(),
+            true,
+        ));
+
+        assert_eq!(
+            <Ported<Test>>::get((external.network, external.namespace, external.token)),
+            Some(NEXT_INSTANCE_ID)
+        );
+        assert_eq!(<Metadata<Test>>::get(1).unwrap().owner, DID_ALICE);
+    });
+}
+
+#[test]
+fn should_transfer() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+
+        assert_ok!(Nft::back(Origin::signed(BOB), nft, 2000 * DOLLARS));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            nft,
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        System::set_block_number(1);
+
+        assert_noop!(
+            Nft::transfer(Origin::signed(BOB), nft, DID_CHARLIE),
+            Error::<Test>::NotOwned
+        );
+        assert_noop!(
+            Nft::transfer(Origin::signed(ALICE), nft, DID_ALICE),
+            Error::<Test>::YourSelf
+        );
+        assert_noop!(
+            Nft::transfer(Origin::signed(ALICE), nft, H160([0x11; 20])),
+            Error::<Test>::NotExists
+        );
+        assert_ok!(Nft::transfer(Origin::signed(ALICE), nft, DID_CHARLIE));
+        System::assert_last_event(Event::Nft(crate::Event::Transferred(
+            DID_ALICE,
+            DID_CHARLIE,
+            nft,
+        )));
+
+        assert_eq!(<Metadata<Test>>::get(nft).unwrap().owner, DID_CHARLIE);
+        assert_eq!(<External<Test>>::get(nft).unwrap().owner, DID_CHARLIE);
+        assert_eq!(Nft::preferred(DID_CHARLIE), Some(nft));
+        assert_eq!(Nft::preferred(DID_ALICE), None);
+
+        // fragments of the KOL keep vesting to the minter
+        assert_noop!(
+            Nft::claim(Origin::signed(CHARLIE), nft),
+            Error::<Test>::NotExists
+        );
+        assert_noop!(
+            Nft::claim(Origin::signed(ALICE), nft),
+            Error::<Test>::NotVested
+        );
+
+        System::set_block_number(10);
+
+        assert_ok!(Nft::claim(Origin::signed(ALICE), nft));
+        assert_eq!(Assets::balance(nft, &ALICE), 1_000_000 * DOLLARS);
+
+        // a backer can take over the NFT too
+        assert_ok!(Nft::transfer(Origin::signed(CHARLIE), nft, DID_BOB));
+        assert_eq!(<Metadata<Test>>::get(nft).unwrap().owner, DID_BOB);
+    });
+}
+
+#[test]
+fn should_set_preferred() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_noop!(
+            Nft::set_preferred(Origin::signed(BOB), 0),
+            Error::<Test>::NotOwned
+        );
+        assert_noop!(
+            Nft::set_preferred(Origin::signed(ALICE), 9),
+            Error::<Test>::NotExists
+        );
+
+        assert_ok!(Nft::set_preferred(Origin::signed(ALICE), 0));
+        System::assert_last_event(Event::Nft(crate::Event::PreferredSet(DID_ALICE, 0)));
+
+        assert_eq!(Nft::preferred(DID_ALICE), Some(0));
+    });
+}
+
+#[test]
+fn should_set_royalty() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_noop!(
+            Nft::set_royalty(Origin::signed(BOB), 1, 500),
+            Error::<Test>::NotOwned
+        );
+        assert_noop!(
+            Nft::set_royalty(Origin::signed(ALICE), 9, 500),
+            Error::<Test>::NotExists
+        );
+        assert_noop!(
+            Nft::set_royalty(Origin::signed(ALICE), 1, 1_001),
+            Error::<Test>::BadRoyalty
+        );
+
+        assert_ok!(Nft::set_royalty(Origin::signed(ALICE), 1, 500));
+        System::assert_last_event(Event::Nft(crate::Event::RoyaltySet(1, 500)));
+
+        assert_eq!(Nft::royalty(1), 500);
+
+        // not minted yet
+        assert_eq!(Nft::royalty_of(1), None);
+
+        assert_ok!(Nft::set_royalty(Origin::signed(ALICE), 1, 0));
+        assert_eq!(Nft::royalty(1), 0);
+    });
+}
+
+#[test]
+fn should_pay_royalty_on_swap() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+
+        assert_ok!(Nft::back(Origin::signed(BOB), nft, 2000 * DOLLARS));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            nft,
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        assert_ok!(Nft::set_royalty(Origin::signed(ALICE), nft, 1_000));
+        assert_eq!(Nft::royalty_of(nft), Some((ALICE, 1_000)));
+
+        // buy tokens, royalty in currency
+
+        let tokens = 1000 * DOLLARS;
+        let currency = Swap::token_out_dry(nft, tokens).unwrap();
+        let royalty = currency * 3 / 1000 / 10;
+
+        let free = Balances::free_balance(&ALICE);
+
+        assert_ok!(Swap::buy_tokens(
+            Origin::signed(CHARLIE),
+            nft,
+            tokens,
+            currency,
+            100
+        ));
+        System::assert_has_event(Event::Swap(parami_swap::Event::RoyaltyPaid(
+            nft, ALICE, 0, royalty,
+        )));
+
+        assert_eq!(Balances::free_balance(&ALICE), free + royalty);
+
+        // sell tokens, royalty in tokens
+
+        let tokens = 500 * DOLLARS;
+        let royalty = tokens * 3 / 1000 / 10;
+
+        assert_ok!(Swap::sell_tokens(
+            Origin::signed(CHARLIE),
+            nft,
+            tokens,
+            1,
+            100
+        ));
+        System::assert_has_event(Event::Swap(parami_swap::Event::RoyaltyPaid(
+            nft, ALICE, royalty, 0,
+        )));
+
+        assert_eq!(Assets::balance(nft, &ALICE), royalty);
+    });
+}
+
+#[test]
+fn should_sumbit_porting() {
+    new_test_ext().execute_with(|| {
+        let namespace = NAMESPACE.to_vec();
+        let token = vec![0x22];
+        let did = DID_BOB;
+
+        let _result = Linker::insert_link(did, Network::Ethereum, "something".into(), did);
+
+        assert_ok!(Nft::port(
+            Origin::signed(BOB),
+            Network::Ethereum,
+            namespace.clone(),
+            token.clone(),
+            None,
+            None
+        ));
+        assert_ok!(Nft::submit_porting(
+            frame_system::RawOrigin::None.into(),
+            DID_BOB,
+            Network::Ethereum,
+            namespace.clone(),
+            token.clone(),
+            true,
+        ));
+
+        let token: &Vec<u8> = &token.into();
+        assert!(<Porting<Test>>::get((Network::Ethereum, &namespace, token)).is_none());
+
+        assert_eq!(
+            <Ported<Test>>::get((Network::Ethereum, &namespace, token)).expect("should be ported"),
+            // genesis config creates the first token, so we got 2 here.
+            2,
+        );
+
+        let external = <External<Test>>::get(2).expect("external should have data");
+        assert_eq!(external.owner, did);
+        assert_eq!(external.network, Network::Ethereum);
+        assert_eq!(external.namespace, namespace);
+        assert_eq!(external.token, token.clone());
+
+        let subaccount_id =
+            <Test as crate::Config>::PalletId::get().into_sub_account_truncating(DID_BOB);
+
+        let metadata = <Metadata<Test>>::get(2).expect("meta should have data");
+        assert_eq!(metadata.owner, did);
+        assert_eq!(metadata.class_id, 2);
+        assert_eq!(metadata.pot, subaccount_id);
+        assert_eq!(metadata.minted, false);
+        assert_eq!(metadata.token_asset_id, 2);
+
+        let preferred = <Preferred<Test>>::get(DID_BOB).expect("prefered should have data");
+        assert_eq!(preferred, 2);
+    });
 }
 
 #[test]
@@ -566,16 +1827,84 @@ fn should_transfer_all_assets() {
         assert_ok!(Assets::mint_into(0, &ALICE, 500));
         assert_ok!(Assets::mint_into(1, &ALICE, 1000));
 
+        Nft::note_holder(0, &ALICE);
+        Nft::note_holder(1, &ALICE);
+
         assert_eq!(Assets::balance(0, &ALICE), 500);
         assert_eq!(Assets::balance(1, &ALICE), 1000);
         assert_eq!(Assets::balance(0, &BOB), 0);
         assert_eq!(Assets::balance(1, &BOB), 0);
 
-        assert_ok!(Nft::force_transfer_all_fractions(&ALICE, &BOB));
+        assert_noop!(Nft::migrate(&ALICE, &BOB, 1), DispatchError::Exhausted);
+
+        let migrated = Nft::migrate(&ALICE, &BOB, 2).unwrap();
+        assert_eq!(migrated.len(), 2);
+        assert!(migrated.contains(&Migrated::Fractions(0, 500)));
+        assert!(migrated.contains(&Migrated::Fractions(1, 1000)));
 
         assert_eq!(Assets::balance(0, &ALICE), 0);
         assert_eq!(Assets::balance(1, &ALICE), 0);
         assert_eq!(Assets::balance(0, &BOB), 500);
         assert_eq!(Assets::balance(1, &BOB), 1000);
+
+        assert_eq!(<FractionsOf<Test>>::iter_prefix(&ALICE).count(), 0);
+        assert!(<FractionsOf<Test>>::contains_key(&BOB, 0));
+        assert!(<FractionsOf<Test>>::contains_key(&BOB, 1));
+    });
+}
+
+#[test]
+fn should_migrate_minter() {
+    new_test_ext().execute_with(|| {
+        let nft = Nft::preferred(DID_ALICE).unwrap();
+
+        assert_ok!(Nft::back(Origin::signed(BOB), nft, 2000 * DOLLARS));
+
+        assert_ok!(Nft::mint(
+            Origin::signed(ALICE),
+            nft,
+            b"Test Token".to_vec(),
+            b"XTT".to_vec()
+        ));
+
+        // minters were not recorded before
+        <Minter<Test>>::remove(nft);
+
+        StorageVersion::new(3).put::<Nft>();
+
+        migrations::migrate::<Test>();
+
+        assert_eq!(StorageVersion::get::<Nft>(), crate::STORAGE_VERSION);
+        assert_eq!(<Minter<Test>>::get(nft), Some(DID_ALICE));
+        assert_eq!(<Minter<Test>>::get(0), None);
+    });
+}
+
+#[test]
+fn should_migrate_porting_of() {
+    new_test_ext().execute_with(|| {
+        let _result = Linker::insert_link(DID_BOB, Network::Ethereum, "something".into(), DID_BOB);
+
+        assert_ok!(Nft::port(
+            Origin::signed(BOB),
+            Network::Ethereum,
+            NAMESPACE.to_vec(),
+            vec![0x22],
+            None,
+            None
+        ));
+
+        // importing in progress was not indexed by owner before
+        <PortingOf<Test>>::remove_all(None);
+
+        StorageVersion::new(4).put::<Nft>();
+
+        migrations::migrate::<Test>();
+
+        assert_eq!(StorageVersion::get::<Nft>(), crate::STORAGE_VERSION);
+        assert_eq!(
+            Nft::porting_of(&DID_BOB, None, 10),
+            vec![(Network::Ethereum, NAMESPACE.to_vec(), vec![0x22], 9)]
+        );
     });
 }
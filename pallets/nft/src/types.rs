@@ -20,3 +20,20 @@ pub struct Metadata<Did, AccountId, NftClassId, AssetId> {
     pub minted: bool,
     pub token_asset_id: AssetId,
 }
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo, MaxEncodedLen)]
+pub struct Funding<Balance, Height> {
+    pub goal: Balance,
+    pub deadline: Height,
+}
+
+/// Token economics of an initial minting, shares are relative weights of the supply
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo, MaxEncodedLen)]
+pub struct Profile<Balance> {
+    pub supply: Balance,
+    pub decimals: u8,
+    pub swap: u32,
+    pub backers: u32,
+    pub kol: u32,
+    pub treasury: u32,
+}
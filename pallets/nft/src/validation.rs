@@ -0,0 +1,37 @@
+use sp_std::prelude::*;
+
+/// Check a name of fragments.
+///
+/// A name has 1 to `limit` characters, starts with a letter, and consists of
+/// ASCII letters, digits and spaces, without a trailing space.
+pub fn is_valid_name(name: &[u8], limit: usize) -> bool {
+    let (first, last) = match (name.first(), name.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return false,
+    };
+
+    name.len() <= limit
+        && first.is_ascii_alphabetic()
+        && *last != b' '
+        && name.iter().all(|c| c.is_ascii_alphanumeric() || *c == b' ')
+}
+
+/// Check a symbol of fragments.
+///
+/// A symbol has 1 to `limit` characters, starts with a letter, and consists of
+/// ASCII letters and digits.
+pub fn is_valid_symbol(symbol: &[u8], limit: usize) -> bool {
+    let first = match symbol.first() {
+        Some(first) => first,
+        None => return false,
+    };
+
+    symbol.len() <= limit
+        && first.is_ascii_alphabetic()
+        && symbol.iter().all(u8::is_ascii_alphanumeric)
+}
+
+/// Normalize a symbol to the key of the registry, symbols are case-insensitive.
+pub fn normalize_symbol(symbol: &[u8]) -> Vec<u8> {
+    symbol.iter().map(u8::to_ascii_uppercase).collect()
+}
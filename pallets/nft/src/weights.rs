@@ -46,9 +46,18 @@ pub trait WeightInfo {
     fn port() -> Weight;
     fn kick() -> Weight;
     fn back() -> Weight;
+    fn withdraw_backing() -> Weight;
     fn mint(n: u32, s: u32, ) -> Weight;
     fn claim() -> Weight;
     fn submit_porting() -> Weight;
+    fn set_preset() -> Weight;
+    fn report_ownership_lost() -> Weight;
+    fn unport() -> Weight;
+    fn transfer() -> Weight;
+    fn set_preferred() -> Weight;
+    fn set_royalty() -> Weight;
+    fn set_reserved_symbol() -> Weight;
+    fn migrate_account(n: u32, ) -> Weight;
 }
 
 /// Weights for parami_nft using the Substrate node and recommended hardware.
@@ -57,29 +66,50 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     // Storage: Did DidOf (r:1 w:0)
     // Storage: Nft Porting (r:1 w:1)
     // Storage: Nft Ported (r:1 w:0)
+    // Storage: Nft Frozen (r:1 w:0)
     // Storage: Linker LinksOf (r:2 w:0)
+    // Storage: Nft Presets (r:1 w:0)
+    // Storage: Nft PortingProfile (r:0 w:1)
+    // Storage: Nft PortingFunding (r:0 w:1)
+    // Storage: Nft PortingOf (r:0 w:1)
     fn port() -> Weight {
-        (24_000_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(5 as Weight))
-            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+        (29_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
     }
     // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Presets (r:1 w:0)
     // Storage: Nft NextClassId (r:1 w:1)
     // Storage: Nft Metadata (r:1 w:1)
+    // Storage: Nft Profile (r:0 w:1)
     // Storage: Nft Preferred (r:1 w:1)
+    // Storage: Nft Funding (r:0 w:1)
     fn kick() -> Weight {
-        (21_000_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(4 as Weight))
-            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+        (23_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
     }
     // Storage: Did DidOf (r:1 w:0)
     // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Nft Frozen (r:1 w:0)
     // Storage: System Account (r:1 w:1)
+    // Storage: Nft Funding (r:1 w:0)
     // Storage: Nft Deposit (r:1 w:1)
     // Storage: Nft Deposits (r:1 w:1)
     fn back() -> Weight {
-        (45_000_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+        (48_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Nft Funding (r:1 w:0)
+    // Storage: Nft Deposit (r:1 w:1)
+    // Storage: Nft Deposits (r:1 w:1)
+    // Storage: System Account (r:1 w:1)
+    fn withdraw_backing() -> Weight {
+        (46_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
             .saturating_add(T::DbWeight::get().writes(3 as Weight))
     }
     // Storage: Did DidOf (r:1 w:0)
@@ -98,34 +128,116 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     // Storage: Swap Account (r:0 w:1)
     // Storage: Swap Liquidity (r:0 w:1)
     // Storage: Nft Date (r:0 w:1)
+    // Storage: Nft Profile (r:1 w:0)
+    // Storage: Nft Symbols (r:1 w:1)
+    // Storage: Nft ReservedSymbols (r:1 w:1)
+    // Storage: Nft Deposit (r:1 w:0)
+    // Storage: Nft Funding (r:1 w:0)
+    // Storage: Nft Minter (r:0 w:1)
     fn mint(n: u32, _s: u32, ) -> Weight {
-        (148_057_000 as Weight)
+        (152_057_000 as Weight)
             // Standard Error: 0
             .saturating_add((5_000 as Weight).saturating_mul(n as Weight))
-            .saturating_add(T::DbWeight::get().reads(14 as Weight))
-            .saturating_add(T::DbWeight::get().writes(17 as Weight))
+            .saturating_add(T::DbWeight::get().reads(20 as Weight))
+            .saturating_add(T::DbWeight::get().writes(21 as Weight))
     }
     // Storage: Did DidOf (r:1 w:0)
     // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Nft Date (r:1 w:0)
     // Storage: Nft Deposit (r:1 w:0)
     // Storage: Nft Deposits (r:1 w:1)
+    // Storage: Nft Claimed (r:1 w:1)
+    // Storage: Nft Profile (r:1 w:0)
     // Storage: Assets Asset (r:1 w:1)
     // Storage: Assets Account (r:2 w:2)
+    // Storage: Nft Minter (r:1 w:0)
     fn claim() -> Weight {
-        (54_000_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(7 as Weight))
-            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+        (59_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(11 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
     }
     // Storage: Nft Porting (r:1 w:1)
+    // Storage: Nft PortingProfile (r:1 w:1)
+    // Storage: Nft Ported (r:1 w:1)
     // Storage: Nft NextClassId (r:1 w:1)
     // Storage: Nft Metadata (r:1 w:1)
+    // Storage: Nft Profile (r:0 w:1)
     // Storage: Nft Preferred (r:1 w:1)
-    // Storage: Nft Ported (r:0 w:1)
     // Storage: Nft External (r:0 w:1)
+    // Storage: Nft PortingFunding (r:1 w:1)
+    // Storage: Nft Funding (r:0 w:1)
+    // Storage: Nft PortingOf (r:0 w:1)
     fn submit_porting() -> Weight {
-        (31_000_000 as Weight)
+        (35_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(11 as Weight))
+    }
+    // Storage: Nft Presets (r:0 w:1)
+    fn set_preset() -> Weight {
+        (14_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Nft External (r:1 w:0)
+    // Storage: Nft Frozen (r:1 w:1)
+    fn report_ownership_lost() -> Weight {
+        (17_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Nft External (r:1 w:1)
+    // Storage: Nft Frozen (r:1 w:1)
+    // Storage: Nft Ported (r:0 w:1)
+    fn unport() -> Weight {
+        (21_000_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(4 as Weight))
-            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:1)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Nft Frozen (r:1 w:0)
+    // Storage: Nft External (r:1 w:1)
+    // Storage: Nft Preferred (r:2 w:2)
+    fn transfer() -> Weight {
+        (42_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Nft Preferred (r:0 w:1)
+    fn set_preferred() -> Weight {
+        (18_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Nft Royalty (r:0 w:1)
+    fn set_royalty() -> Weight {
+        (18_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Nft Symbols (r:1 w:0)
+    // Storage: Nft ReservedSymbols (r:0 w:1)
+    fn set_reserved_symbol() -> Weight {
+        (12_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Nft FractionsOf (r:1 w:2)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    fn migrate_account(n: u32, ) -> Weight {
+        (3_000_000 as Weight)
+            // Standard Error: 15_000
+            .saturating_add((38_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
     }
 }
 
@@ -134,29 +246,50 @@ impl WeightInfo for () {
     // Storage: Did DidOf (r:1 w:0)
     // Storage: Nft Porting (r:1 w:1)
     // Storage: Nft Ported (r:1 w:0)
+    // Storage: Nft Frozen (r:1 w:0)
     // Storage: Linker LinksOf (r:2 w:0)
+    // Storage: Nft Presets (r:1 w:0)
+    // Storage: Nft PortingProfile (r:0 w:1)
+    // Storage: Nft PortingFunding (r:0 w:1)
+    // Storage: Nft PortingOf (r:0 w:1)
     fn port() -> Weight {
-        (24_000_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+        (29_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
     }
     // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Presets (r:1 w:0)
     // Storage: Nft NextClassId (r:1 w:1)
     // Storage: Nft Metadata (r:1 w:1)
+    // Storage: Nft Profile (r:0 w:1)
     // Storage: Nft Preferred (r:1 w:1)
+    // Storage: Nft Funding (r:0 w:1)
     fn kick() -> Weight {
-        (21_000_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+        (23_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
     }
     // Storage: Did DidOf (r:1 w:0)
     // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Nft Frozen (r:1 w:0)
     // Storage: System Account (r:1 w:1)
+    // Storage: Nft Funding (r:1 w:0)
     // Storage: Nft Deposit (r:1 w:1)
     // Storage: Nft Deposits (r:1 w:1)
     fn back() -> Weight {
-        (45_000_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+        (48_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Nft Funding (r:1 w:0)
+    // Storage: Nft Deposit (r:1 w:1)
+    // Storage: Nft Deposits (r:1 w:1)
+    // Storage: System Account (r:1 w:1)
+    fn withdraw_backing() -> Weight {
+        (46_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
             .saturating_add(RocksDbWeight::get().writes(3 as Weight))
     }
     // Storage: Did DidOf (r:1 w:0)
@@ -175,33 +308,115 @@ impl WeightInfo for () {
     // Storage: Swap Account (r:0 w:1)
     // Storage: Swap Liquidity (r:0 w:1)
     // Storage: Nft Date (r:0 w:1)
+    // Storage: Nft Profile (r:1 w:0)
+    // Storage: Nft Symbols (r:1 w:1)
+    // Storage: Nft ReservedSymbols (r:1 w:1)
+    // Storage: Nft Deposit (r:1 w:0)
+    // Storage: Nft Funding (r:1 w:0)
+    // Storage: Nft Minter (r:0 w:1)
     fn mint(n: u32, _s: u32, ) -> Weight {
-        (148_057_000 as Weight)
+        (152_057_000 as Weight)
             // Standard Error: 0
             .saturating_add((5_000 as Weight).saturating_mul(n as Weight))
-            .saturating_add(RocksDbWeight::get().reads(14 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(17 as Weight))
+            .saturating_add(RocksDbWeight::get().reads(20 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(21 as Weight))
     }
     // Storage: Did DidOf (r:1 w:0)
     // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Nft Date (r:1 w:0)
     // Storage: Nft Deposit (r:1 w:0)
     // Storage: Nft Deposits (r:1 w:1)
+    // Storage: Nft Claimed (r:1 w:1)
+    // Storage: Nft Profile (r:1 w:0)
     // Storage: Assets Asset (r:1 w:1)
     // Storage: Assets Account (r:2 w:2)
+    // Storage: Nft Minter (r:1 w:0)
     fn claim() -> Weight {
-        (54_000_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+        (59_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(11 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
     }
     // Storage: Nft Porting (r:1 w:1)
+    // Storage: Nft PortingProfile (r:1 w:1)
+    // Storage: Nft Ported (r:1 w:1)
     // Storage: Nft NextClassId (r:1 w:1)
     // Storage: Nft Metadata (r:1 w:1)
+    // Storage: Nft Profile (r:0 w:1)
     // Storage: Nft Preferred (r:1 w:1)
-    // Storage: Nft Ported (r:0 w:1)
     // Storage: Nft External (r:0 w:1)
+    // Storage: Nft PortingFunding (r:1 w:1)
+    // Storage: Nft Funding (r:0 w:1)
+    // Storage: Nft PortingOf (r:0 w:1)
     fn submit_porting() -> Weight {
-        (31_000_000 as Weight)
+        (35_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(11 as Weight))
+    }
+    // Storage: Nft Presets (r:0 w:1)
+    fn set_preset() -> Weight {
+        (14_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Nft External (r:1 w:0)
+    // Storage: Nft Frozen (r:1 w:1)
+    fn report_ownership_lost() -> Weight {
+        (17_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Nft External (r:1 w:1)
+    // Storage: Nft Frozen (r:1 w:1)
+    // Storage: Nft Ported (r:0 w:1)
+    fn unport() -> Weight {
+        (21_000_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(4 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:1)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Nft Frozen (r:1 w:0)
+    // Storage: Nft External (r:1 w:1)
+    // Storage: Nft Preferred (r:2 w:2)
+    fn transfer() -> Weight {
+        (42_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Nft Preferred (r:0 w:1)
+    fn set_preferred() -> Weight {
+        (18_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Did DidOf (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Nft Royalty (r:0 w:1)
+    fn set_royalty() -> Weight {
+        (18_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Nft Symbols (r:1 w:0)
+    // Storage: Nft ReservedSymbols (r:0 w:1)
+    fn set_reserved_symbol() -> Weight {
+        (12_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Nft FractionsOf (r:1 w:2)
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    fn migrate_account(n: u32, ) -> Weight {
+        (3_000_000 as Weight)
+            // Standard Error: 15_000
+            .saturating_add((38_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
     }
 }
@@ -1,6 +1,6 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-pub use lite_json::JsonValue;
+pub use lite_json::{parse_json, JsonValue};
 pub use pallet::*;
 
 #[cfg(test)]
@@ -3,15 +3,18 @@
 use codec::Codec;
 use parami_primitives::BalanceWrapper;
 use sp_runtime::traits::{MaybeDisplay, MaybeFromStr};
-use sp_runtime::DispatchError;
+use sp_runtime::{DispatchError, FixedU128, Permill};
+use sp_std::vec::Vec;
 
 pub type ApiResult<T> = Result<T, DispatchError>;
 
 sp_api::decl_runtime_apis! {
-    pub trait SwapRuntimeApi<AssetId, Balance>
+    pub trait SwapRuntimeApi<AccountId, AssetId, Balance, Height>
     where
+        AccountId: Codec,
         AssetId: Codec,
         Balance: Codec + MaybeDisplay + MaybeFromStr,
+        Height: Codec,
     {
         /// Get dry-run result of add_liquidity
         ///
@@ -116,6 +119,123 @@ sp_api::decl_runtime_apis! {
             currency: BalanceWrapper<Balance>,
         ) -> ApiResult<BalanceWrapper<Balance>>;
 
+        /// Get dry-run result of swap_exact_tokens_for_tokens
+        ///
+        /// # Arguments
+        ///
+        /// * `token_in` - The Asset ID of tokens to be sold
+        /// * `token_out` - The Asset ID of tokens to be bought
+        /// * `tokens` - The amount of tokens to be sold
+        ///
+        /// # Results
+        ///
+        /// tuple of (tokens to be gained, currency routed through)
+        fn dryly_swap_tokens(
+            token_in: AssetId,
+            token_out: AssetId,
+            tokens: BalanceWrapper<Balance>,
+        ) -> ApiResult<(BalanceWrapper<Balance>, BalanceWrapper<Balance>)>;
+
+        /// Get trading fee of a swap pair
+        ///
+        /// # Arguments
+        ///
+        /// * `token_id` - The Asset ID
+        ///
+        /// # Results
+        ///
+        /// tuple of (fee, protocol share)
+        ///
+        /// * `fee` - The trading fee of the swap pair
+        /// * `protocol share` - The share of trading fee paid to the treasury
+        fn get_fee(
+            token_id: AssetId,
+        ) -> ApiResult<(Permill, Permill)>;
+
+        /// Get time-weighted average price of a fraction token
+        ///
+        /// # Arguments
+        ///
+        /// * `token_id` - The Asset ID
+        /// * `window` - The number of blocks to average over, ending at the current block
+        ///
+        /// # Results
+        ///
+        /// The average price in currency per token
+        fn twap(
+            token_id: AssetId,
+            window: Height,
+        ) -> ApiResult<FixedU128>;
+
+        /// Get reserves and spot price of a swap pair
+        ///
+        /// # Arguments
+        ///
+        /// * `token_id` - The Asset ID
+        ///
+        /// # Results
+        ///
+        /// tuple of (pot, currency, tokens, liquidity, price)
+        ///
+        /// * `pot` - The account holding the reserves
+        /// * `currency` - The currency reserve
+        /// * `tokens` - The token reserve
+        /// * `liquidity` - The total liquidity minted
+        /// * `price` - The spot price in currency per token
+        fn get_pool(
+            token_id: AssetId,
+        ) -> ApiResult<(
+            AccountId,
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+            FixedU128,
+        )>;
+
+        /// List swap pairs
+        ///
+        /// # Arguments
+        ///
+        /// * `start_after` - The Asset ID to continue after, from the last page
+        /// * `limit` - The maximum number of swap pairs to return
+        ///
+        /// # Results
+        ///
+        /// The Asset IDs of swap pairs
+        fn list_pools(
+            start_after: Option<AssetId>,
+            limit: u32,
+        ) -> ApiResult<Vec<AssetId>>;
+
+        /// List liquidity provider tokens of an account
+        ///
+        /// # Arguments
+        ///
+        /// * `who` - The provider account
+        /// * `start_after` - The LP Token ID to continue after, from the last page
+        /// * `limit` - The maximum number of LP tokens to return
+        ///
+        /// # Results
+        ///
+        /// Vec of tuple of (lp_token_id, token_id, liquidity, minted, claimed)
+        ///
+        /// * `lp_token_id` - The Liquidity Provider Token ID
+        /// * `token_id` - The Asset ID
+        /// * `liquidity` - The amount of liquidity
+        /// * `minted` - The height at which the LP token was minted
+        /// * `claimed` - The height at which reward was last claimed
+        fn list_positions(
+            who: AccountId,
+            start_after: Option<AssetId>,
+            limit: u32,
+        ) -> ApiResult<Vec<(
+            AssetId,
+            AssetId,
+            BalanceWrapper<Balance>,
+            Height,
+            Height,
+        )>>;
+
         /// Calculate staking reward
         ///
         /// # Arguments
@@ -11,11 +11,12 @@ use sp_blockchain::HeaderBackend;
 use sp_runtime::{
     generic::BlockId,
     traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
+    FixedU128, Permill,
 };
 use std::sync::Arc;
 
 #[rpc(client, server)]
-pub trait SwapApi<BlockHash, AssetId, Balance>
+pub trait SwapApi<BlockHash, AccountId, AssetId, Balance, Height>
 where
     Balance: MaybeDisplay + MaybeFromStr,
 {
@@ -140,6 +141,131 @@ where
         at: Option<BlockHash>,
     ) -> RpcResult<BalanceWrapper<Balance>>;
 
+    /// Get dry-run result of swap_exact_tokens_for_tokens
+    ///
+    /// # Arguments
+    ///
+    /// * `token_in` - The Asset ID of tokens to be sold
+    /// * `token_out` - The Asset ID of tokens to be bought
+    /// * `tokens` - The amount of tokens to be sold
+    ///
+    /// # Results
+    ///
+    /// tuple of (tokens to be gained, currency routed through)
+    #[method(name = "swap_drylySwapTokens")]
+    fn dryly_swap_tokens(
+        &self,
+        token_in: AssetId,
+        token_out: AssetId,
+        tokens: BalanceWrapper<Balance>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<(BalanceWrapper<Balance>, BalanceWrapper<Balance>)>;
+
+    /// Get trading fee of a swap pair
+    ///
+    /// # Arguments
+    ///
+    /// * `token_id` - The Asset ID
+    ///
+    /// # Results
+    ///
+    /// tuple of (fee, protocol share)
+    ///
+    /// * `fee` - The trading fee of the swap pair
+    /// * `protocol share` - The share of trading fee paid to the treasury
+    #[method(name = "swap_getFee")]
+    fn get_fee(&self, token_id: AssetId, at: Option<BlockHash>) -> RpcResult<(Permill, Permill)>;
+
+    /// Get time-weighted average price of a fraction token
+    ///
+    /// # Arguments
+    ///
+    /// * `token_id` - The Asset ID
+    /// * `window` - The number of blocks to average over, ending at the current block
+    ///
+    /// # Results
+    ///
+    /// The average price in currency per token
+    #[method(name = "swap_twap")]
+    fn twap(
+        &self,
+        token_id: AssetId,
+        window: Height,
+        at: Option<BlockHash>,
+    ) -> RpcResult<FixedU128>;
+
+    /// Get reserves and spot price of a swap pair
+    ///
+    /// # Arguments
+    ///
+    /// * `token_id` - The Asset ID
+    ///
+    /// # Results
+    ///
+    /// tuple of (pot, currency, tokens, liquidity, price)
+    ///
+    /// * `pot` - The account holding the reserves
+    /// * `currency` - The currency reserve
+    /// * `tokens` - The token reserve
+    /// * `liquidity` - The total liquidity minted
+    /// * `price` - The spot price in currency per token
+    #[method(name = "swap_getPool")]
+    fn get_pool(
+        &self,
+        token_id: AssetId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<(
+        AccountId,
+        BalanceWrapper<Balance>,
+        BalanceWrapper<Balance>,
+        BalanceWrapper<Balance>,
+        FixedU128,
+    )>;
+
+    /// List swap pairs
+    ///
+    /// # Arguments
+    ///
+    /// * `start_after` - The Asset ID to continue after, from the last page
+    /// * `limit` - The maximum number of swap pairs to return
+    ///
+    /// # Results
+    ///
+    /// The Asset IDs of swap pairs
+    #[method(name = "swap_listPools")]
+    fn list_pools(
+        &self,
+        start_after: Option<AssetId>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<AssetId>>;
+
+    /// List liquidity provider tokens of an account
+    ///
+    /// # Arguments
+    ///
+    /// * `who` - The provider account
+    /// * `start_after` - The LP Token ID to continue after, from the last page
+    /// * `limit` - The maximum number of LP tokens to return
+    ///
+    /// # Results
+    ///
+    /// Vec of tuple of (lp_token_id, token_id, liquidity, minted, claimed)
+    ///
+    /// * `lp_token_id` - The Liquidity Provider Token ID
+    /// * `token_id` - The Asset ID
+    /// * `liquidity` - The amount of liquidity
+    /// * `minted` - The height at which the LP token was minted
+    /// * `claimed` - The height at which reward was last claimed
+    #[method(name = "swap_listPositions")]
+    fn list_positions(
+        &self,
+        who: AccountId,
+        start_after: Option<AssetId>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(AssetId, AssetId, BalanceWrapper<Balance>, Height, Height)>>;
+
     /// Calculate staking reward
     ///
     /// # Arguments
@@ -157,12 +283,14 @@ where
     ) -> RpcResult<BalanceWrapper<Balance>>;
 }
 
-pub struct SwapsRpcHandler<C, Block, AssetId, Balance> {
+pub struct SwapsRpcHandler<C, Block, AccountId, AssetId, Balance, Height> {
     client: Arc<C>,
-    _marker: std::marker::PhantomData<(Block, AssetId, Balance)>,
+    _marker: std::marker::PhantomData<(Block, AccountId, AssetId, Balance, Height)>,
 }
 
-impl<C, Block, AssetId, Balance> SwapsRpcHandler<C, Block, AssetId, Balance> {
+impl<C, Block, AccountId, AssetId, Balance, Height>
+    SwapsRpcHandler<C, Block, AccountId, AssetId, Balance, Height>
+{
     pub fn new(client: Arc<C>) -> Self {
         Self {
             client,
@@ -172,14 +300,17 @@ impl<C, Block, AssetId, Balance> SwapsRpcHandler<C, Block, AssetId, Balance> {
 }
 
 #[async_trait]
-impl<C, Block, AssetId, Balance> SwapApiServer<<Block as BlockT>::Hash, AssetId, Balance>
-    for SwapsRpcHandler<C, Block, AssetId, Balance>
+impl<C, Block, AccountId, AssetId, Balance, Height>
+    SwapApiServer<<Block as BlockT>::Hash, AccountId, AssetId, Balance, Height>
+    for SwapsRpcHandler<C, Block, AccountId, AssetId, Balance, Height>
 where
     Block: BlockT,
     C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-    C::Api: SwapRuntimeApi<Block, AssetId, Balance>,
+    C::Api: SwapRuntimeApi<Block, AccountId, AssetId, Balance, Height>,
+    AccountId: Codec + Send + Sync + 'static,
     AssetId: Codec + Send + Sync + 'static,
     Balance: Codec + MaybeDisplay + MaybeFromStr + Send + Sync + 'static,
+    Height: Codec + Send + Sync + 'static,
 {
     fn dryly_add_liquidity(
         &self,
@@ -348,6 +479,172 @@ where
         })
     }
 
+    fn dryly_swap_tokens(
+        &self,
+        token_in: AssetId,
+        token_out: AssetId,
+        tokens: BalanceWrapper<Balance>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<(BalanceWrapper<Balance>, BalanceWrapper<Balance>)> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api
+            .dryly_swap_tokens(&at, token_in, token_out, tokens)
+            .map_err(|e| {
+                Error::Call(CallError::Custom(ErrorObject::owned(
+                    INTERNAL_ERROR_CODE,
+                    "Unable to dry-run token_to_token_in.",
+                    Some(format!("{:?}", e)),
+                )))
+            })?;
+
+        res.map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to dry-run token_to_token_in.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    fn get_fee(
+        &self,
+        token_id: AssetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<(Permill, Permill)> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api.get_fee(&at, token_id).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get fee.",
+                Some(format!("{:?}", e)),
+            )))
+        })?;
+
+        res.map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get fee.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    fn twap(
+        &self,
+        token_id: AssetId,
+        window: Height,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<FixedU128> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api.twap(&at, token_id, window).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get twap.",
+                Some(format!("{:?}", e)),
+            )))
+        })?;
+
+        res.map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get twap.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    fn get_pool(
+        &self,
+        token_id: AssetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<(
+        AccountId,
+        BalanceWrapper<Balance>,
+        BalanceWrapper<Balance>,
+        BalanceWrapper<Balance>,
+        FixedU128,
+    )> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api.get_pool(&at, token_id).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get pool.",
+                Some(format!("{:?}", e)),
+            )))
+        })?;
+
+        res.map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to get pool.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    fn list_pools(
+        &self,
+        start_after: Option<AssetId>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<AssetId>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api.list_pools(&at, start_after, limit).map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to list pools.",
+                Some(format!("{:?}", e)),
+            )))
+        })?;
+
+        res.map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to list pools.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    fn list_positions(
+        &self,
+        who: AccountId,
+        start_after: Option<AssetId>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(AssetId, AssetId, BalanceWrapper<Balance>, Height, Height)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api
+            .list_positions(&at, who, start_after, limit)
+            .map_err(|e| {
+                Error::Call(CallError::Custom(ErrorObject::owned(
+                    INTERNAL_ERROR_CODE,
+                    "Unable to list positions.",
+                    Some(format!("{:?}", e)),
+                )))
+            })?;
+
+        res.map_err(|e| {
+            Error::Call(CallError::Custom(ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                "Unable to list positions.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
     fn calculate_reward(
         &self,
         lp_token_id: AssetId,
@@ -2,10 +2,11 @@ use super::*;
 
 #[allow(unused)]
 use crate::Pallet as Swap;
-use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
 use frame_support::traits::tokens::fungibles::{Create, Inspect, Mutate};
 use frame_system::RawOrigin;
-use sp_runtime::traits::{Saturating, Zero};
+use parami_traits::AccountMigration;
+use sp_runtime::traits::{One, Saturating, Zero};
 
 benchmarks! {
     where_clause {
@@ -211,6 +212,163 @@ benchmarks! {
     verify {
         assert_eq!(T::Currency::free_balance(&caller), pot.saturating_mul(4u32.into()));
     }
+
+    swap_exact_tokens_for_tokens {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let min = T::Currency::minimum_balance();
+
+        let pot = min.saturating_mul(1_000_000u32.into());
+
+        let token_in = T::AssetId::min_value();
+        let token_out = token_in.saturating_add(One::one());
+        let deadline = HeightOf::<T>::max_value();
+
+        T::Currency::make_free_balance_be(&caller, pot.saturating_mul(5u32.into()));
+
+        for id in [token_in, token_out] {
+            T::Assets::create(id, caller.clone(), true, min)?;
+            T::Assets::mint_into(id, &caller, pot.saturating_mul(5u32.into()))?;
+
+            Swap::<T>::create(RawOrigin::Signed(caller.clone()).into(), id)?;
+
+            Swap::<T>::add_liquidity(
+                RawOrigin::Signed(caller.clone()).into(),
+                id,
+                pot,
+                min,
+                pot.saturating_mul(2u32.into()),
+                deadline,
+            )?;
+        }
+    }: _(RawOrigin::Signed(caller.clone()), token_in, token_out, pot, min, deadline)
+    verify {
+        assert_eq!(T::Assets::balance(token_in, &caller), pot.saturating_mul(2u32.into()));
+    }
+
+    swap_tokens_for_exact_tokens {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let max = BalanceOf::<T>::max_value();
+        let min = T::Currency::minimum_balance();
+
+        let pot = min.saturating_mul(1_000_000u32.into());
+
+        let token_in = T::AssetId::min_value();
+        let token_out = token_in.saturating_add(One::one());
+        let deadline = HeightOf::<T>::max_value();
+
+        T::Currency::make_free_balance_be(&caller, pot.saturating_mul(5u32.into()));
+
+        for id in [token_in, token_out] {
+            T::Assets::create(id, caller.clone(), true, min)?;
+            T::Assets::mint_into(id, &caller, pot.saturating_mul(5u32.into()))?;
+
+            Swap::<T>::create(RawOrigin::Signed(caller.clone()).into(), id)?;
+
+            Swap::<T>::add_liquidity(
+                RawOrigin::Signed(caller.clone()).into(),
+                id,
+                pot,
+                min,
+                pot.saturating_mul(2u32.into()),
+                deadline,
+            )?;
+        }
+    }: _(RawOrigin::Signed(caller.clone()), token_in, token_out, min, max, deadline)
+    verify {
+        assert_eq!(
+            T::Assets::balance(token_out, &caller),
+            pot.saturating_mul(3u32.into()).saturating_add(min)
+        );
+    }
+
+    set_fee {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let min = T::Currency::minimum_balance();
+
+        let id = T::AssetId::min_value();
+        let fee = T::MaxFee::get();
+
+        T::Assets::create(id, caller.clone(), true, min)?;
+
+        Swap::<T>::create(RawOrigin::Signed(caller.clone()).into(), id)?;
+    }: _(RawOrigin::Root, id, fee)
+    verify {
+        assert_eq!(<Metadata<T>>::get(id).unwrap().fee, fee);
+    }
+
+    set_default_fee {
+        let fee = T::MaxFee::get();
+    }: _(RawOrigin::Root, fee)
+    verify {
+        assert_eq!(<DefaultFee<T>>::get(), fee);
+    }
+
+    set_protocol_share {
+        let share = Permill::from_percent(50);
+    }: _(RawOrigin::Root, share)
+    verify {
+        assert_eq!(<ProtocolShare<T>>::get(), share);
+    }
+
+    set_curve {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let min = T::Currency::minimum_balance();
+
+        let id = T::AssetId::min_value();
+
+        T::Assets::create(id, caller.clone(), true, min)?;
+
+        Swap::<T>::create(RawOrigin::Signed(caller.clone()).into(), id)?;
+    }: _(RawOrigin::Root, id, Curve::Halving)
+    verify {
+        assert_eq!(Swap::<T>::curve_of(id), Curve::Halving);
+    }
+
+    migrate_account {
+        let n in 1 .. 100;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let dest: T::AccountId = account("dest", 0, 0);
+
+        let max = BalanceOf::<T>::max_value();
+        let min = T::Currency::minimum_balance();
+
+        let pot = min.saturating_mul(1_000_000u32.into());
+        let total = pot.saturating_mul((5 * n).into());
+
+        let id = T::AssetId::min_value();
+        let deadline = HeightOf::<T>::max_value();
+
+        T::Assets::create(id, caller.clone(), true, min)?;
+
+        T::Currency::make_free_balance_be(&caller, total);
+        T::Assets::mint_into(id, &caller, total)?;
+
+        Swap::<T>::create(RawOrigin::Signed(caller.clone()).into(), id)?;
+
+        Swap::<T>::add_liquidity(
+            RawOrigin::Signed(caller.clone()).into(),
+            id,
+            pot,
+            min,
+            pot.saturating_mul(2u32.into()),
+            deadline,
+        )?;
+
+        for _ in 1 .. n {
+            Swap::<T>::add_liquidity(RawOrigin::Signed(caller.clone()).into(), id, pot, min, max, deadline)?;
+        }
+    }: {
+        Swap::<T>::migrate(&caller, &dest, n)?;
+    }
+    verify {
+        assert_eq!(<Account<T>>::iter_prefix(&caller).count(), 0);
+        assert_eq!(<Account<T>>::iter_prefix(&dest).count() as u32, n);
+    }
 }
 
 impl_benchmark_test_suite!(Swap, crate::mock::new_test_ext(), crate::mock::Test);
@@ -1,12 +1,13 @@
 use crate::{
-    Account, AssetOf, BalanceOf, Config, Error, HeightOf, Liquidity, LiquidityOf, Metadata, Pallet,
+    types::Curve, Account, AssetOf, BalanceOf, Config, Curves, Error, HeightOf, Liquidity,
+    LiquidityOf, Metadata, Pallet,
 };
 
 use frame_support::traits::{tokens::fungibles::Inspect, Currency, Get};
 use sp_core::U512;
 use sp_runtime::{
-    traits::{Saturating, Zero},
-    DispatchError,
+    traits::{Saturating, UniqueSaturatedInto, Zero},
+    DispatchError, FixedPointNumber, FixedU128,
 };
 use sp_std::marker::PhantomData;
 
@@ -38,15 +39,24 @@ impl<T: Config> FarmingCurve<T> for () {
     }
 }
 
-pub struct LinearFarmingCurve<T, I, B>(PhantomData<(T, I, B)>);
-impl<T, InitialFarmingReward, InitialMintingValueBase> FarmingCurve<T>
-    for LinearFarmingCurve<T, InitialFarmingReward, InitialMintingValueBase>
+pub struct LinearFarmingCurve<T, I, B, S, P>(PhantomData<(T, I, B, S, P)>);
+impl<T, InitialFarmingReward, InitialMintingValueBase, TotalFarmingReward, FarmingPeriod>
+    FarmingCurve<T>
+    for LinearFarmingCurve<
+        T,
+        InitialFarmingReward,
+        InitialMintingValueBase,
+        TotalFarmingReward,
+        FarmingPeriod,
+    >
 where
     T: Config,
     T::BlockNumber: From<u32> + Into<U512>,
     <T::Currency as Currency<T::AccountId>>::Balance: From<u32> + Into<U512> + TryFrom<U512>,
     InitialFarmingReward: Get<BalanceOf<T>>,
     InitialMintingValueBase: Get<BalanceOf<T>>,
+    TotalFarmingReward: Get<BalanceOf<T>>,
+    FarmingPeriod: Get<HeightOf<T>>,
 {
     fn calculate_farming_reward(
         created_height: HeightOf<T>,
@@ -59,39 +69,175 @@ where
             return Zero::zero();
         }
 
-        let x_lower = staked_height - created_height;
-        let x_upper = current_height - created_height;
+        let period: U512 = FarmingPeriod::get().into();
 
-        let x_lower: U512 = x_lower.into();
-        let x_upper: U512 = x_upper.into();
+        if period.is_zero() {
+            return Zero::zero();
+        }
 
         // we use a linear curve for farming reward
-        // y = a * x + b
+        // y = -a * x + b
 
-        // we will issue 100 dollars in the first block
+        // b is issued in the first block
         let base: U512 = InitialFarmingReward::get().into();
-        // b = 100DOLLARS
-
-        // our goal is to issue 7,000,000 dollars in 3 years
-        // DAYS is the block number of a day
-        // const PERIOD: f64 = 3f64 * 365.25f64 * DAYS as f64;
-        // PERIOD = 3 * 365.25 * (60000 / 12000 * 60 * 24) = 7889400
+        let total: U512 = TotalFarmingReward::get().into();
 
         // to calculate the total supply, we use integral
-        // Y = Integrate[-ax + 100DOLLARS]
+        // Y = Integrate[-ax + b] = b * x - a * Power[x, 2] / 2
+
+        // ∵ Integrate[-ax + b, {x, 0, PERIOD}] = TOTAL
+        // ∴ a / 2 = (b * PERIOD - TOTAL) / Power[PERIOD, 2]
+        let (slope, rising) = match (base * period).checked_sub(total) {
+            Some(slope) => (slope, false),
+            None => (total - base * period, true),
+        };
+
+        // the reward stops at the end of the period,
+        // or once the reward of each block drops to zero
+        // y = 0 ⇒ x = b * Power[PERIOD, 2] / (2 * (b * PERIOD - TOTAL))
+        let end = if rising || slope.is_zero() {
+            period
+        } else {
+            period.min(base * period * period / (slope * U512::from(2u32)))
+        };
 
-        // ∵ Integrate[-ax + 100DOLLARS, {x, 0, PERIOD}] = 7_000_000DOLLARS
-        // ∴ a = 39_097_000_000_000_000_000_000 / 1556065809
-        // ∴ Y = 100DOLLARS x - 19_548_500_000_000_000_000_000 * Power[x,2] / 1_556_065_809
-        // Y ≈ 100DOLLARS x - 12_562_772_015_768 * Power[x,2]
-        let c = U512::from(12_562_772_015_768u128);
+        let issued = |x: HeightOf<T>| -> U512 {
+            let x: U512 = x.into();
+            let x = x.min(end);
+
+            let quadratic = slope * x * x / (period * period);
+
+            if rising {
+                base * x + quadratic
+            } else {
+                (base * x).saturating_sub(quadratic)
+            }
+        };
+
+        let x_lower = staked_height.saturating_sub(created_height);
+        let x_upper = current_height.saturating_sub(created_height);
 
         // reward = Integrate[-ax + b, {x, staked_height, current_height}]
         // cuz Newton-Leibniz formula
         // reward = Y(x_upper) - Y(x_lower)
+        let reward = issued(x_upper).saturating_sub(issued(x_lower));
+
+        reward.try_into().unwrap_or_default()
+    }
+}
+
+pub struct ExponentialDecayFarmingCurve<T, I, S>(PhantomData<(T, I, S)>);
+impl<T, InitialFarmingReward, TotalFarmingReward> FarmingCurve<T>
+    for ExponentialDecayFarmingCurve<T, InitialFarmingReward, TotalFarmingReward>
+where
+    T: Config,
+    T::BlockNumber: From<u32> + Into<U512>,
+    <T::Currency as Currency<T::AccountId>>::Balance: From<u32> + Into<U512> + TryFrom<U512>,
+    InitialFarmingReward: Get<BalanceOf<T>>,
+    TotalFarmingReward: Get<BalanceOf<T>>,
+{
+    fn calculate_farming_reward(
+        created_height: HeightOf<T>,
+        staked_height: HeightOf<T>,
+        current_height: HeightOf<T>,
+        _total_supply: BalanceOf<T>,
+    ) -> BalanceOf<T> {
+        let base: U512 = InitialFarmingReward::get().into();
+        let total: U512 = TotalFarmingReward::get().into();
+
+        if total.is_zero() {
+            return Zero::zero();
+        }
+
+        // the reward of each block decays by a constant ratio
+        // y = b * Power[q, x]
+
+        // to issue the total in infinite blocks, we use geometric series
+        // Sum[b * Power[q, x], {x, 0, Infinity}] = b / (1 - q) = TOTAL
+        // ∴ q = 1 - b / TOTAL
+        // ∴ Y = TOTAL * (1 - Power[q, x])
+
+        // q is rounded up, so that the first block issues b exactly
+        let accuracy = U512::from(FixedU128::accuracy());
+        let ratio = (total.saturating_sub(base) * accuracy + total - U512::one()) / total;
+        let ratio = FixedU128::from_inner(ratio.low_u128());
+
+        let x_lower: u64 = staked_height
+            .saturating_sub(created_height)
+            .unique_saturated_into();
+        let x_upper: u64 = current_height
+            .saturating_sub(created_height)
+            .unique_saturated_into();
+
+        // the reward not issued yet
+        // TOTAL - Y = TOTAL * Power[q, x]
+        let remaining = |x: u64| -> U512 {
+            let x = x.try_into().unwrap_or(usize::MAX);
+            let power: U512 = ratio.saturating_pow(x).into_inner().into();
+            total * power / accuracy
+        };
+
+        // reward = Y(x_upper) - Y(x_lower)
+        let reward = remaining(x_lower).saturating_sub(remaining(x_upper));
+
+        reward.try_into().unwrap_or_default()
+    }
+}
+
+pub struct HalvingFarmingCurve<T, S, P>(PhantomData<(T, S, P)>);
+impl<T, TotalFarmingReward, HalvingPeriod> FarmingCurve<T>
+    for HalvingFarmingCurve<T, TotalFarmingReward, HalvingPeriod>
+where
+    T: Config,
+    T::BlockNumber: From<u32> + Into<U512>,
+    <T::Currency as Currency<T::AccountId>>::Balance: From<u32> + Into<U512> + TryFrom<U512>,
+    TotalFarmingReward: Get<BalanceOf<T>>,
+    HalvingPeriod: Get<HeightOf<T>>,
+{
+    fn calculate_farming_reward(
+        created_height: HeightOf<T>,
+        staked_height: HeightOf<T>,
+        current_height: HeightOf<T>,
+        _total_supply: BalanceOf<T>,
+    ) -> BalanceOf<T> {
+        let total: U512 = TotalFarmingReward::get().into();
+        let period: U512 = HalvingPeriod::get().into();
+
+        if period.is_zero() {
+            return Zero::zero();
+        }
+
+        // the reward of each block is halved every period
+        // y = b / Power[2, Floor[x / PERIOD]]
+
+        // to issue the total in infinite blocks, we use geometric series
+        // Sum[b * PERIOD / Power[2, n], {n, 0, Infinity}] = 2 * b * PERIOD = TOTAL
+        // ∴ b = TOTAL / (2 * PERIOD)
+        let two = U512::from(2u32);
+        let base = total / (period * two);
 
-        let reward = (base * x_upper - c * x_upper.pow(U512::from(2u32)))
-            - (base * x_lower - c * x_lower.pow(U512::from(2u32)));
+        // to calculate the total supply, we sum up whole periods
+        // and the blocks elapsed in the current period
+        // Y = 2 * b * PERIOD * (1 - 1 / Power[2, n]) + b * (x - n * PERIOD) / Power[2, n]
+        // ∴ Y = b * (2 * PERIOD * (Power[2, n] - 1) + x - n * PERIOD) / Power[2, n]
+        let issued = |x: U512| -> U512 {
+            let n = x / period;
+
+            // the remaining reward is less than one unit
+            if n >= U512::from(256u32) {
+                return base * period * two;
+            }
+
+            let halvings = U512::one() << n.low_u32();
+
+            base * (period * two * (halvings - U512::one()) + x - n * period) / halvings
+        };
+
+        let x_lower = staked_height.saturating_sub(created_height);
+        let x_upper = current_height.saturating_sub(created_height);
+
+        // reward = Y(x_upper) - Y(x_lower)
+        let reward = issued(x_upper.into()).saturating_sub(issued(x_lower.into()));
 
         reward.try_into().unwrap_or_default()
     }
@@ -122,7 +268,8 @@ impl<T: Config> Pallet<T> {
         // calculate the reward from the height when
         // the liquidity was staked or last claimed
         // so that we will always have a positive reward
-        let reward = T::FarmingCurve::calculate_farming_reward(
+        let reward = Self::calculate_farming_reward(
+            liquidity.token_id,
             meta.created,
             claimed, // last claimed
             height,
@@ -139,4 +286,87 @@ impl<T: Config> Pallet<T> {
 
         Ok((liquidity, reward))
     }
+
+    /// The farming curve of a swap pair currently in effect
+    pub fn curve_of(token_id: AssetOf<T>) -> Curve {
+        <Curves<T>>::get(token_id)
+            .last()
+            .map(|(_, curve)| *curve)
+            .unwrap_or_default()
+    }
+
+    /// Calculate the farming value of a swap pair with its curves,
+    /// each span between two changes of curve is calculated with the curve then in effect
+    fn calculate_farming_reward(
+        token_id: AssetOf<T>,
+        created_height: HeightOf<T>,
+        staked_height: HeightOf<T>,
+        current_height: HeightOf<T>,
+        total_supply: BalanceOf<T>,
+    ) -> BalanceOf<T> {
+        let mut reward = BalanceOf::<T>::zero();
+
+        let mut curve = Curve::Default;
+        let mut from = staked_height;
+
+        for (changed, next) in <Curves<T>>::get(token_id) {
+            let to = changed.min(current_height);
+
+            if from < to {
+                reward.saturating_accrue(Self::calculate_with_curve(
+                    curve,
+                    created_height,
+                    from,
+                    to,
+                    total_supply,
+                ));
+
+                from = to;
+            }
+
+            curve = next;
+        }
+
+        if from < current_height {
+            reward.saturating_accrue(Self::calculate_with_curve(
+                curve,
+                created_height,
+                from,
+                current_height,
+                total_supply,
+            ));
+        }
+
+        reward
+    }
+
+    /// Calculate the farming value with a curve
+    fn calculate_with_curve(
+        curve: Curve,
+        created_height: HeightOf<T>,
+        staked_height: HeightOf<T>,
+        current_height: HeightOf<T>,
+        total_supply: BalanceOf<T>,
+    ) -> BalanceOf<T> {
+        match curve {
+            Curve::Default => T::FarmingCurve::calculate_farming_reward(
+                created_height,
+                staked_height,
+                current_height,
+                total_supply,
+            ),
+            Curve::ExponentialDecay => T::ExponentialDecayCurve::calculate_farming_reward(
+                created_height,
+                staked_height,
+                current_height,
+                total_supply,
+            ),
+            Curve::Halving => T::HalvingCurve::calculate_farming_reward(
+                created_height,
+                staked_height,
+                current_height,
+                total_supply,
+            ),
+        }
+    }
 }
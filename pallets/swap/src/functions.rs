@@ -1,12 +1,25 @@
-use crate::{AssetOf, BalanceOf, Config, Error, Metadata, Pallet, SwapOf};
+use crate::{
+    types, Account, AccountOf, Accumulator, AssetOf, BalanceOf, Config, Error, Event, Fees,
+    HeightOf, Liquidity, Metadata, ObservationOf, Observations, Pallet, ProtocolShare, SwapOf,
+    MAX_PAGE_SIZE,
+};
 
 use frame_support::{
     ensure,
-    traits::{tokens::fungibles::Inspect as FungInspect, Currency},
+    traits::{
+        tokens::fungibles::{Inspect as FungInspect, Transfer as FungTransfer},
+        Currency,
+        ExistenceRequirement::KeepAlive,
+        Get,
+    },
 };
-use parami_traits::Swaps;
+use parami_traits::{Holders, Royalties, Swaps};
 use sp_core::U512;
-use sp_runtime::{traits::Zero, DispatchError};
+use sp_runtime::{
+    traits::{SaturatedConversion, Saturating, Zero},
+    DispatchError, FixedPointNumber, FixedU128, PerThing, Permill,
+};
+use sp_std::prelude::*;
 
 impl<T: Config> Pallet<T> {
     pub(super) fn try_into<S, D>(value: S) -> Result<D, DispatchError>
@@ -85,25 +98,292 @@ impl<T: Config> Pallet<T> {
         Ok((tokens, currency, meta))
     }
 
+    /// Get trading fee of a swap pair
+    pub fn fee_of(token_id: AssetOf<T>) -> Result<Permill, DispatchError> {
+        let meta = <Metadata<T>>::get(token_id).ok_or(Error::<T>::NotExists)?;
+
+        Ok(meta.fee)
+    }
+
+    /// Reserves of a swap pair, returns pool account, currency, tokens, total liquidity and spot price.
+    pub fn pool_of(
+        token_id: AssetOf<T>,
+    ) -> Result<
+        (
+            AccountOf<T>,
+            BalanceOf<T>,
+            BalanceOf<T>,
+            BalanceOf<T>,
+            FixedU128,
+        ),
+        DispatchError,
+    > {
+        let meta = <Metadata<T>>::get(token_id).ok_or(Error::<T>::NotExists)?;
+
+        let pot = Self::get_pool_account(token_id);
+
+        let currency = T::Currency::free_balance(&pot);
+        let tokens = T::Assets::balance(token_id, &pot);
+        let price = Self::spot_price(token_id).unwrap_or_default();
+
+        Ok((pot, currency, tokens, meta.liquidity, price))
+    }
+
+    /// Swap pairs in storage order, starting after `start_after` if given.
+    pub fn pools(start_after: Option<AssetOf<T>>, limit: u32) -> Vec<AssetOf<T>> {
+        let limit = limit.min(MAX_PAGE_SIZE) as usize;
+
+        let iter = match start_after {
+            Some(token_id) => {
+                <Metadata<T>>::iter_keys_from(<Metadata<T>>::hashed_key_for(token_id))
+            }
+            None => <Metadata<T>>::iter_keys(),
+        };
+
+        iter.take(limit).collect()
+    }
+
+    /// Liquidity provider tokens of an account, starting after `start_after` if given,
+    /// returns LP token ID, token ID, liquidity, minted height and last claimed height of each.
+    pub fn positions_of(
+        who: &AccountOf<T>,
+        start_after: Option<AssetOf<T>>,
+        limit: u32,
+    ) -> Vec<(
+        AssetOf<T>,
+        AssetOf<T>,
+        BalanceOf<T>,
+        HeightOf<T>,
+        HeightOf<T>,
+    )> {
+        let limit = limit.min(MAX_PAGE_SIZE) as usize;
+
+        let iter = match start_after {
+            Some(lp_token_id) => {
+                <Account<T>>::iter_prefix_from(who, <Account<T>>::hashed_key_for(who, lp_token_id))
+            }
+            None => <Account<T>>::iter_prefix(who),
+        };
+
+        iter.filter_map(|(lp_token_id, claimed)| {
+            let liquidity = <Liquidity<T>>::get(lp_token_id)?;
+
+            Some((
+                lp_token_id,
+                liquidity.token_id,
+                liquidity.amount,
+                liquidity.minted,
+                claimed,
+            ))
+        })
+        .take(limit)
+        .collect()
+    }
+
+    /// Calculate trading fee of a trade, charged on the input
+    pub(super) fn calculate_fee(token_id: AssetOf<T>, input_amount: BalanceOf<T>) -> BalanceOf<T> {
+        match Self::fee_of(token_id) {
+            Ok(fee) => fee.mul_floor(input_amount),
+            Err(_) => Zero::zero(),
+        }
+    }
+
+    /// Calculate royalty of a trade, as a share of its trading fee
+    pub(super) fn calculate_royalty(
+        token_id: AssetOf<T>,
+        fee: BalanceOf<T>,
+    ) -> Option<(AccountOf<T>, BalanceOf<T>)> {
+        let (account, royalty) = T::Royalties::royalty_of(token_id)?;
+
+        let fee: U512 = Self::try_into(fee).ok()?;
+        let royalty = U512::from(royalty.min(10_000));
+
+        let royalty = fee * royalty / U512::from(10_000);
+
+        let royalty: BalanceOf<T> = Self::try_into(royalty).ok()?;
+
+        if royalty.is_zero() {
+            return None;
+        }
+
+        Some((account, royalty))
+    }
+
+    /// Pay royalty and protocol share of the trading fee in currency out of the pot,
+    /// the rest of the fee stays in the pot for liquidity providers
+    pub(super) fn pay_fees_in_currency(
+        token_id: AssetOf<T>,
+        currency: BalanceOf<T>,
+    ) -> Result<(), DispatchError> {
+        let fee = Self::calculate_fee(token_id, currency);
+        if fee.is_zero() {
+            return Ok(());
+        }
+
+        let pot = Self::get_pool_account(token_id);
+
+        let mut remain = fee;
+
+        if let Some((account, royalty)) = Self::calculate_royalty(token_id, fee) {
+            T::Currency::transfer(&pot, &account, royalty, KeepAlive)?;
+
+            remain.saturating_reduce(royalty);
+
+            Self::deposit_event(Event::RoyaltyPaid(token_id, account, Zero::zero(), royalty));
+        }
+
+        let protocol = <ProtocolShare<T>>::get().mul_floor(fee).min(remain);
+        if !protocol.is_zero() {
+            let treasury = T::TreasuryAccount::get();
+
+            T::Currency::transfer(&pot, &treasury, protocol, KeepAlive)?;
+
+            remain.saturating_reduce(protocol);
+
+            Self::deposit_event(Event::ProtocolFeePaid(token_id, Zero::zero(), protocol));
+        }
+
+        <Fees<T>>::mutate(token_id, |fees| {
+            fees.lp_currency.saturating_accrue(remain);
+            fees.protocol_currency.saturating_accrue(protocol);
+        });
+
+        Ok(())
+    }
+
+    /// Pay royalty and protocol share of the trading fee in tokens out of the pot,
+    /// the rest of the fee stays in the pot for liquidity providers
+    pub(super) fn pay_fees_in_tokens(
+        token_id: AssetOf<T>,
+        tokens: BalanceOf<T>,
+    ) -> Result<(), DispatchError> {
+        let fee = Self::calculate_fee(token_id, tokens);
+        if fee.is_zero() {
+            return Ok(());
+        }
+
+        let pot = Self::get_pool_account(token_id);
+
+        let mut remain = fee;
+
+        if let Some((account, royalty)) = Self::calculate_royalty(token_id, fee) {
+            T::Assets::transfer(token_id, &pot, &account, royalty, true)?;
+            T::Holders::note_holder(token_id, &account);
+
+            remain.saturating_reduce(royalty);
+
+            Self::deposit_event(Event::RoyaltyPaid(token_id, account, royalty, Zero::zero()));
+        }
+
+        let protocol = <ProtocolShare<T>>::get().mul_floor(fee).min(remain);
+        if !protocol.is_zero() {
+            let treasury = T::TreasuryAccount::get();
+
+            T::Assets::transfer(token_id, &pot, &treasury, protocol, true)?;
+
+            remain.saturating_reduce(protocol);
+
+            Self::deposit_event(Event::ProtocolFeePaid(token_id, protocol, Zero::zero()));
+        }
+
+        <Fees<T>>::mutate(token_id, |fees| {
+            fees.lp_tokens.saturating_accrue(remain);
+            fees.protocol_tokens.saturating_accrue(protocol);
+        });
+
+        Ok(())
+    }
+
+    /// Calculate spot price of a swap pair, in currency per token
+    pub(super) fn spot_price(token_id: AssetOf<T>) -> Option<FixedU128> {
+        let pot = Self::get_pool_account(token_id);
+
+        let total_quote: u128 = Self::try_into(T::Currency::free_balance(&pot)).ok()?;
+        let total_token: u128 = Self::try_into(T::Assets::balance(token_id, &pot)).ok()?;
+
+        FixedU128::checked_from_rational(total_quote, total_token)
+    }
+
+    /// Extend cumulative price of a swap pair to a given height,
+    /// assuming the spot price held since it was last observed
+    pub(super) fn extend_observation(
+        token_id: AssetOf<T>,
+        last: &ObservationOf<T>,
+        height: HeightOf<T>,
+    ) -> ObservationOf<T> {
+        let mut observation = last.clone();
+
+        if height > last.height {
+            if let Some(price) = Self::spot_price(token_id) {
+                let elapsed: u128 = height.saturating_sub(last.height).saturated_into();
+                let elapsed = FixedU128::saturating_from_integer(elapsed);
+
+                observation
+                    .cumulative
+                    .saturating_accrue(price.saturating_mul(elapsed));
+            }
+
+            observation.height = height;
+        }
+
+        observation
+    }
+
+    /// Accumulate price of a swap pair, must be called before its reserves change
+    pub(super) fn accumulate_price(token_id: AssetOf<T>) {
+        let height = <frame_system::Pallet<T>>::block_number();
+
+        let accumulator = match <Accumulator<T>>::get(token_id) {
+            Some(last) => Self::extend_observation(token_id, &last, height),
+            None => types::Observation {
+                height,
+                cumulative: Zero::zero(),
+            },
+        };
+
+        <Observations<T>>::mutate(token_id, |observations| {
+            let due = match observations.last() {
+                Some(last) => height >= last.height.saturating_add(T::ObservationPeriod::get()),
+                None => true,
+            };
+
+            if !due {
+                return;
+            }
+
+            if !observations.is_empty() && observations.len() as u32 >= T::MaxObservations::get() {
+                observations.remove(0);
+            }
+
+            let _ = observations.try_push(accumulator.clone());
+        });
+
+        <Accumulator<T>>::insert(token_id, accumulator);
+    }
+
     /// Calculate buy price in U512
     pub(self) fn calculate_price_buy(
         output_amount: U512,
         input_reserve: U512,
         output_reserve: U512,
+        fee: Permill,
     ) -> U512 {
         let ten_percent = output_reserve / 10;
 
         if output_amount > ten_percent {
-            let d = Self::calculate_price_buy(ten_percent, input_reserve, output_reserve);
+            let d = Self::calculate_price_buy(ten_percent, input_reserve, output_reserve, fee);
 
             d + Self::calculate_price_buy(
                 output_amount - ten_percent,
                 input_reserve + d,
                 output_reserve - ten_percent,
+                fee,
             )
         } else {
-            let numerator = input_reserve * output_amount * U512::from(1000);
-            let denominator = (output_reserve - output_amount) * U512::from(997);
+            let accuracy = U512::from(Permill::ACCURACY);
+            let after_fee = accuracy - U512::from(fee.deconstruct());
+            let numerator = input_reserve * output_amount * accuracy;
+            let denominator = (output_reserve - output_amount) * after_fee;
             let result = numerator / denominator + U512::from(1);
 
             result
@@ -115,21 +395,25 @@ impl<T: Config> Pallet<T> {
         input_amount: U512,
         input_reserve: U512,
         output_reserve: U512,
+        fee: Permill,
     ) -> U512 {
         let ten_percent = input_reserve / 10;
 
         if input_amount > ten_percent {
-            let d = Self::calculate_price_sell(ten_percent, input_reserve, output_reserve);
+            let d = Self::calculate_price_sell(ten_percent, input_reserve, output_reserve, fee);
 
             d + Self::calculate_price_sell(
                 input_amount - ten_percent,
                 input_reserve + ten_percent,
                 output_reserve - d,
+                fee,
             )
         } else {
-            let input_amount_with_fee = input_amount * U512::from(997);
+            let accuracy = U512::from(Permill::ACCURACY);
+            let after_fee = accuracy - U512::from(fee.deconstruct());
+            let input_amount_with_fee = input_amount * after_fee;
             let numerator = input_amount_with_fee * output_reserve;
-            let denominator = (input_reserve * U512::from(1000)) + input_amount_with_fee;
+            let denominator = (input_reserve * accuracy) + input_amount_with_fee;
             let result = numerator / denominator;
 
             result
@@ -141,6 +425,7 @@ impl<T: Config> Pallet<T> {
         output_amount: BalanceOf<T>,
         input_reserve: BalanceOf<T>,
         output_reserve: BalanceOf<T>,
+        fee: Permill,
     ) -> Result<BalanceOf<T>, DispatchError> {
         ensure!(
             output_reserve > output_amount,
@@ -151,7 +436,7 @@ impl<T: Config> Pallet<T> {
         let input_reserve = Self::try_into(input_reserve)?;
         let output_reserve = Self::try_into(output_reserve)?;
 
-        let result = Self::calculate_price_buy(output_amount, input_reserve, output_reserve);
+        let result = Self::calculate_price_buy(output_amount, input_reserve, output_reserve, fee);
 
         let result = Self::try_into(result)?;
 
@@ -163,12 +448,13 @@ impl<T: Config> Pallet<T> {
         input_amount: BalanceOf<T>,
         input_reserve: BalanceOf<T>,
         output_reserve: BalanceOf<T>,
+        fee: Permill,
     ) -> Result<BalanceOf<T>, DispatchError> {
         let input_amount = Self::try_into(input_amount)?;
         let input_reserve = Self::try_into(input_reserve)?;
         let output_reserve = Self::try_into(output_reserve)?;
 
-        let result = Self::calculate_price_sell(input_amount, input_reserve, output_reserve);
+        let result = Self::calculate_price_sell(input_amount, input_reserve, output_reserve, fee);
 
         ensure!(output_reserve > result, Error::<T>::InsufficientLiquidity);
 
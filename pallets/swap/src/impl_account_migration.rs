@@ -0,0 +1,58 @@
+use crate::{
+    weights::WeightInfo, Account, AccountOf, AssetOf, Config, Error, HeightOf, Liquidity, Pallet,
+    Provider,
+};
+
+use frame_support::{ensure, weights::Weight};
+use parami_traits::{AccountMigration, Migrated};
+use sp_runtime::{
+    traits::{SaturatedConversion, Saturating, Zero},
+    DispatchError,
+};
+use sp_std::prelude::*;
+
+impl<T: Config> AccountMigration<AccountOf<T>> for Pallet<T> {
+    fn migrate(
+        src: &AccountOf<T>,
+        dest: &AccountOf<T>,
+        items: u32,
+    ) -> Result<Vec<Migrated>, DispatchError> {
+        let mut migrated = Vec::new();
+
+        let positions: Vec<(AssetOf<T>, HeightOf<T>)> = <Account<T>>::iter_prefix(src)
+            .take((items as usize).saturating_add(1))
+            .collect();
+        ensure!(positions.len() as u32 <= items, DispatchError::Exhausted);
+
+        for (lp_token_id, claimed) in positions {
+            let liquidity = <Liquidity<T>>::try_mutate(lp_token_id, |maybe_liquidity| {
+                let liquidity = maybe_liquidity.as_mut().ok_or(Error::<T>::NotExists)?;
+
+                liquidity.owner = dest.clone();
+
+                Ok::<_, DispatchError>(liquidity.clone())
+            })?;
+
+            <Account<T>>::remove(src, lp_token_id);
+            <Account<T>>::insert(dest, lp_token_id, claimed);
+
+            let provided = <Provider<T>>::take(liquidity.token_id, src);
+            if !provided.is_zero() {
+                <Provider<T>>::mutate(liquidity.token_id, dest, |holding| {
+                    holding.saturating_accrue(provided)
+                });
+            }
+
+            migrated.push(Migrated::Liquidity(
+                lp_token_id.saturated_into(),
+                liquidity.amount.saturated_into(),
+            ));
+        }
+
+        Ok(migrated)
+    }
+
+    fn migrate_weight(items: u32) -> Weight {
+        T::WeightInfo::migrate_account(items)
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{Accumulator, AssetOf, Config, Error, HeightOf, Observations, Pallet};
+
+use frame_support::ensure;
+use parami_traits::Prices;
+use sp_runtime::{
+    traits::{CheckedDiv, SaturatedConversion, Saturating, Zero},
+    DispatchError, FixedPointNumber, FixedU128,
+};
+
+impl<T: Config> Prices<AssetOf<T>, HeightOf<T>> for Pallet<T> {
+    fn twap(token_id: AssetOf<T>, window: HeightOf<T>) -> Result<FixedU128, DispatchError> {
+        let last = <Accumulator<T>>::get(token_id).ok_or(Error::<T>::InsufficientHistory)?;
+
+        let height = <frame_system::Pallet<T>>::block_number();
+
+        let current = Self::extend_observation(token_id, &last, height);
+
+        // the latest observation which is at least `window` blocks old
+        let target = height.saturating_sub(window);
+        let observations = <Observations<T>>::get(token_id);
+        let start = observations
+            .iter()
+            .rev()
+            .find(|observation| observation.height <= target)
+            .ok_or(Error::<T>::InsufficientHistory)?;
+
+        let elapsed = height.saturating_sub(start.height);
+        ensure!(!elapsed.is_zero(), Error::<T>::InsufficientHistory);
+
+        let elapsed: u128 = elapsed.saturated_into();
+        let elapsed = FixedU128::saturating_from_integer(elapsed);
+
+        let cumulative = current.cumulative.saturating_sub(start.cumulative);
+
+        let price = cumulative
+            .checked_div(&elapsed)
+            .ok_or(Error::<T>::Overflow)?;
+
+        Ok(price)
+    }
+}
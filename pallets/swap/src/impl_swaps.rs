@@ -1,6 +1,6 @@
 use crate::{
-    types, Account, AccountOf, AssetOf, BalanceOf, Config, Error, Event, HeightOf, Liquidity,
-    Metadata, NextTokenId, Pallet, Provider,
+    types, Account, AccountOf, AssetOf, BalanceOf, Config, DefaultFee, Error, Event, HeightOf,
+    Liquidity, Metadata, NextTokenId, Pallet, Provider,
 };
 
 use frame_support::{
@@ -11,8 +11,9 @@ use frame_support::{
         ExistenceRequirement::{AllowDeath, KeepAlive},
         Get,
     },
+    transactional,
 };
-use parami_traits::Swaps;
+use parami_traits::{Holders, Swaps};
 use sp_runtime::traits::{AccountIdConversion, CheckedAdd, One, Saturating, Zero};
 use sp_std::boxed::Box;
 
@@ -51,6 +52,7 @@ impl<T: Config> Swaps<AccountOf<T>> for Pallet<T> {
             types::Swap {
                 created,
                 liquidity: Zero::zero(),
+                fee: <DefaultFee<T>>::get(),
             },
         );
 
@@ -109,6 +111,8 @@ impl<T: Config> Swaps<AccountOf<T>> for Pallet<T> {
 
         let pot = Self::get_pool_account(token_id);
 
+        Self::accumulate_price(token_id);
+
         T::Currency::transfer(
             &who,
             &pot,
@@ -201,7 +205,10 @@ impl<T: Config> Swaps<AccountOf<T>> for Pallet<T> {
 
         let pot = Self::get_pool_account(liquidity.token_id);
 
+        Self::accumulate_price(liquidity.token_id);
+
         T::Assets::transfer(liquidity.token_id, &pot, &who, tokens, false)?;
+        T::Holders::note_holder(liquidity.token_id, &who);
         T::Currency::transfer(&pot, &who, currency, AllowDeath)?;
 
         Self::deposit_event(Event::LiquidityRemoved(
@@ -223,12 +230,14 @@ impl<T: Config> Swaps<AccountOf<T>> for Pallet<T> {
 
         let total_quote = T::Currency::free_balance(&pot);
         let total_token = T::Assets::balance(token_id, &pot);
+        let fee = Self::fee_of(token_id)?;
 
-        let currency_sold = Self::price_buy(tokens, total_quote, total_token)?;
+        let currency_sold = Self::price_buy(tokens, total_quote, total_token, fee)?;
 
         Ok(currency_sold)
     }
 
+    #[transactional]
     fn token_out(
         who: AccountOf<T>,
         token_id: Self::AssetId,
@@ -243,14 +252,17 @@ impl<T: Config> Swaps<AccountOf<T>> for Pallet<T> {
 
         let total_quote = T::Currency::free_balance(&pot);
         let total_token = T::Assets::balance(token_id, &pot);
+        let fee = Self::fee_of(token_id)?;
 
-        let currency_sold = Self::price_buy(tokens, total_quote, total_token)?;
+        let currency_sold = Self::price_buy(tokens, total_quote, total_token, fee)?;
 
         ensure!(
             currency_sold <= max_currency,
             Error::<T>::TooExpensiveCurrency
         );
 
+        Self::accumulate_price(token_id);
+
         T::Currency::transfer(
             &who,
             &pot,
@@ -258,6 +270,9 @@ impl<T: Config> Swaps<AccountOf<T>> for Pallet<T> {
             if keep_alive { KeepAlive } else { AllowDeath },
         )?;
         T::Assets::transfer(token_id, &pot, &who, tokens, false)?;
+        T::Holders::note_holder(token_id, &who);
+
+        Self::pay_fees_in_currency(token_id, currency_sold)?;
 
         Self::deposit_event(Event::TokenBought(token_id, who, tokens, currency_sold));
 
@@ -272,12 +287,14 @@ impl<T: Config> Swaps<AccountOf<T>> for Pallet<T> {
 
         let total_quote = T::Currency::free_balance(&pot);
         let total_token = T::Assets::balance(token_id, &pot);
+        let fee = Self::fee_of(token_id)?;
 
-        let currency_bought = Self::price_sell(tokens, total_token, total_quote)?;
+        let currency_bought = Self::price_sell(tokens, total_token, total_quote, fee)?;
 
         Ok(currency_bought)
     }
 
+    #[transactional]
     fn token_in(
         who: AccountOf<T>,
         token_id: Self::AssetId,
@@ -292,14 +309,19 @@ impl<T: Config> Swaps<AccountOf<T>> for Pallet<T> {
 
         let total_quote = T::Currency::free_balance(&pot);
         let total_token = T::Assets::balance(token_id, &pot);
+        let fee = Self::fee_of(token_id)?;
 
-        let currency_bought = Self::price_sell(tokens, total_token, total_quote)?;
+        let currency_bought = Self::price_sell(tokens, total_token, total_quote, fee)?;
 
         ensure!(currency_bought >= min_currency, Error::<T>::TooLowCurrency);
 
+        Self::accumulate_price(token_id);
+
         T::Assets::transfer(token_id, &who, &pot, tokens, keep_alive)?;
         T::Currency::transfer(&pot, &who, currency_bought, AllowDeath)?;
 
+        Self::pay_fees_in_tokens(token_id, tokens)?;
+
         Self::deposit_event(Event::TokenSold(token_id, who, tokens, currency_bought));
 
         Ok(currency_bought)
@@ -313,12 +335,14 @@ impl<T: Config> Swaps<AccountOf<T>> for Pallet<T> {
 
         let total_quote = T::Currency::free_balance(&pot);
         let total_token = T::Assets::balance(token_id, &pot);
+        let fee = Self::fee_of(token_id)?;
 
-        let tokens_bought = Self::price_sell(currency, total_quote, total_token)?;
+        let tokens_bought = Self::price_sell(currency, total_quote, total_token, fee)?;
 
         Ok(tokens_bought)
     }
 
+    #[transactional]
     fn quote_in(
         who: AccountOf<T>,
         token_id: Self::AssetId,
@@ -333,11 +357,14 @@ impl<T: Config> Swaps<AccountOf<T>> for Pallet<T> {
 
         let total_quote = T::Currency::free_balance(&pot);
         let total_token = T::Assets::balance(token_id, &pot);
+        let fee = Self::fee_of(token_id)?;
 
-        let tokens_bought = Self::price_sell(currency, total_quote, total_token)?;
+        let tokens_bought = Self::price_sell(currency, total_quote, total_token, fee)?;
 
         ensure!(tokens_bought >= min_tokens, Error::<T>::TooExpensiveTokens);
 
+        Self::accumulate_price(token_id);
+
         T::Currency::transfer(
             &who,
             &pot,
@@ -345,6 +372,9 @@ impl<T: Config> Swaps<AccountOf<T>> for Pallet<T> {
             if keep_alive { KeepAlive } else { AllowDeath },
         )?;
         T::Assets::transfer(token_id, &pot, &who, tokens_bought, false)?;
+        T::Holders::note_holder(token_id, &who);
+
+        Self::pay_fees_in_currency(token_id, currency)?;
 
         Self::deposit_event(Event::TokenBought(token_id, who, tokens_bought, currency));
 
@@ -359,12 +389,14 @@ impl<T: Config> Swaps<AccountOf<T>> for Pallet<T> {
 
         let total_quote = T::Currency::free_balance(&pot);
         let total_token = T::Assets::balance(token_id, &pot);
+        let fee = Self::fee_of(token_id)?;
 
-        let tokens_sold = Self::price_buy(currency, total_token, total_quote)?;
+        let tokens_sold = Self::price_buy(currency, total_token, total_quote, fee)?;
 
         Ok(tokens_sold)
     }
 
+    #[transactional]
     fn quote_out(
         who: AccountOf<T>,
         token_id: Self::AssetId,
@@ -379,16 +411,109 @@ impl<T: Config> Swaps<AccountOf<T>> for Pallet<T> {
 
         let total_quote = T::Currency::free_balance(&pot);
         let total_token = T::Assets::balance(token_id, &pot);
+        let fee = Self::fee_of(token_id)?;
 
-        let tokens_sold = Self::price_buy(currency, total_token, total_quote)?;
+        let tokens_sold = Self::price_buy(currency, total_token, total_quote, fee)?;
 
         ensure!(max_tokens >= tokens_sold, Error::<T>::TooLowTokens);
 
+        Self::accumulate_price(token_id);
+
         T::Assets::transfer(token_id, &who, &pot, tokens_sold, keep_alive)?;
         T::Currency::transfer(&pot, &who, currency, AllowDeath)?;
 
+        Self::pay_fees_in_tokens(token_id, tokens_sold)?;
+
         Self::deposit_event(Event::TokenSold(token_id, who, tokens_sold, currency));
 
         Ok(tokens_sold)
     }
+
+    fn token_to_token_in_dry(
+        token_in: Self::AssetId,
+        token_out: Self::AssetId,
+        tokens: Self::TokenBalance,
+    ) -> DispatchResult<(Self::TokenBalance, Self::QuoteBalance)> {
+        ensure!(token_in != token_out, Error::<T>::SameTokens);
+
+        let currency = Self::token_in_dry(token_in, tokens)?;
+        let tokens_bought = Self::quote_in_dry(token_out, currency)?;
+
+        Ok((tokens_bought, currency))
+    }
+
+    #[transactional]
+    fn token_to_token_in(
+        who: AccountOf<T>,
+        token_in: Self::AssetId,
+        token_out: Self::AssetId,
+        tokens: Self::TokenBalance,
+        min_tokens: Self::TokenBalance,
+        keep_alive: bool,
+    ) -> DispatchResult<(Self::TokenBalance, Self::QuoteBalance)> {
+        ensure!(tokens > Zero::zero(), Error::<T>::ZeroTokens);
+        ensure!(min_tokens > Zero::zero(), Error::<T>::ZeroTokens);
+
+        let (tokens_bought, currency) = Self::token_to_token_in_dry(token_in, token_out, tokens)?;
+
+        ensure!(tokens_bought >= min_tokens, Error::<T>::TooExpensiveTokens);
+
+        // the currency gained from the first swap is spent entirely on the second one
+        Self::token_in(who.clone(), token_in, tokens, currency, keep_alive)?;
+        Self::quote_in(who.clone(), token_out, currency, tokens_bought, false)?;
+
+        Self::deposit_event(Event::TokensSwapped(
+            token_in,
+            token_out,
+            who,
+            tokens,
+            tokens_bought,
+        ));
+
+        Ok((tokens_bought, currency))
+    }
+
+    fn token_to_token_out_dry(
+        token_in: Self::AssetId,
+        token_out: Self::AssetId,
+        tokens: Self::TokenBalance,
+    ) -> DispatchResult<(Self::TokenBalance, Self::QuoteBalance)> {
+        ensure!(token_in != token_out, Error::<T>::SameTokens);
+
+        let currency = Self::token_out_dry(token_out, tokens)?;
+        let tokens_sold = Self::quote_out_dry(token_in, currency)?;
+
+        Ok((tokens_sold, currency))
+    }
+
+    #[transactional]
+    fn token_to_token_out(
+        who: AccountOf<T>,
+        token_in: Self::AssetId,
+        token_out: Self::AssetId,
+        tokens: Self::TokenBalance,
+        max_tokens: Self::TokenBalance,
+        keep_alive: bool,
+    ) -> DispatchResult<(Self::TokenBalance, Self::QuoteBalance)> {
+        ensure!(tokens > Zero::zero(), Error::<T>::ZeroTokens);
+        ensure!(max_tokens > Zero::zero(), Error::<T>::ZeroTokens);
+
+        let (tokens_sold, currency) = Self::token_to_token_out_dry(token_in, token_out, tokens)?;
+
+        ensure!(max_tokens >= tokens_sold, Error::<T>::TooLowTokens);
+
+        // the currency gained from the first swap is spent entirely on the second one
+        Self::quote_out(who.clone(), token_in, currency, tokens_sold, keep_alive)?;
+        Self::token_out(who.clone(), token_out, tokens, currency, false)?;
+
+        Self::deposit_event(Event::TokensSwapped(
+            token_in,
+            token_out,
+            who,
+            tokens_sold,
+            tokens,
+        ));
+
+        Ok((tokens_sold, currency))
+    }
 }
@@ -1,7 +1,10 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-pub use farming::{FarmingCurve, LinearFarmingCurve};
+pub use farming::{
+    ExponentialDecayFarmingCurve, FarmingCurve, HalvingFarmingCurve, LinearFarmingCurve,
+};
 pub use pallet::*;
+pub use types::Curve;
 
 #[rustfmt::skip]
 pub mod weights;
@@ -17,7 +20,10 @@ mod benchmarking;
 
 mod farming;
 mod functions;
+mod impl_account_migration;
+mod impl_prices;
 mod impl_swaps;
+mod migrations;
 mod types;
 
 use frame_support::{
@@ -27,12 +33,15 @@ use frame_support::{
         tokens::fungibles::{
             InspectMetadata as FungMeta, Mutate as FungMutate, Transfer as FungTransfer,
         },
-        Currency, Get,
+        Currency, Get, StorageVersion,
     },
     PalletId,
 };
-use parami_traits::Swaps;
-use sp_runtime::traits::{AtLeast32BitUnsigned, Bounded, Saturating, Zero};
+use parami_traits::{Holders, Royalties, Swaps};
+use sp_runtime::{
+    traits::{AtLeast32BitUnsigned, Bounded, Saturating, Zero},
+    Permill,
+};
 use sp_std::prelude::*;
 
 use weights::WeightInfo;
@@ -43,6 +52,13 @@ type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountOf<T>>>::Balance
 type HeightOf<T> = <T as frame_system::Config>::BlockNumber;
 type SwapOf<T> = types::Swap<HeightOf<T>, BalanceOf<T>>;
 type LiquidityOf<T> = types::Liquidity<AccountOf<T>, BalanceOf<T>, HeightOf<T>, AssetOf<T>>;
+type FeesOf<T> = types::Fees<BalanceOf<T>>;
+type ObservationOf<T> = types::Observation<HeightOf<T>>;
+
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+/// The maximum number of items returned by a paginated query
+const MAX_PAGE_SIZE: u32 = 100;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -76,15 +92,55 @@ pub mod pallet {
         /// The curve for seasoned orffering
         type FarmingCurve: FarmingCurve<Self>;
 
+        /// The exponential-decay curve, which may be chosen for a swap pair
+        type ExponentialDecayCurve: FarmingCurve<Self>;
+
+        /// The piecewise-halving curve, which may be chosen for a swap pair
+        type HalvingCurve: FarmingCurve<Self>;
+
+        /// The origin which may update trading fees and farming curves
+        type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+        /// The maximum number of farming curve changes kept for a swap pair
+        #[pallet::constant]
+        type MaxCurveChanges: Get<u32>;
+
+        /// The maximum trading fee of a swap pair
+        #[pallet::constant]
+        type MaxFee: Get<Permill>;
+
+        /// The maximum share of trading fees paid to the treasury
+        #[pallet::constant]
+        type MaxProtocolShare: Get<Permill>;
+
+        /// The maximum number of price observations kept for a swap pair
+        #[pallet::constant]
+        type MaxObservations: Get<u32>;
+
+        /// The minimum number of blocks between two price observations
+        #[pallet::constant]
+        type ObservationPeriod: Get<HeightOf<Self>>;
+
         /// The pallet id, used for deriving liquid accounts
         #[pallet::constant]
         type PalletId: Get<PalletId>;
 
+        /// The royalties trait, royalty of a token is paid out of its trading fees
+        type Royalties: Royalties<AccountOf<Self>, AssetOf<Self>>;
+
+        /// The holders trait, accounts receiving tokens out of a pool are noted to it
+        type Holders: Holders<AccountOf<Self>, AssetOf<Self>>;
+
+        /// The treasury account, protocol share of trading fees is paid to
+        #[pallet::constant]
+        type TreasuryAccount: Get<AccountOf<Self>>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::generate_store(pub(super) trait Store)]
     pub struct Pallet<T>(_);
 
@@ -129,6 +185,53 @@ pub mod pallet {
     #[pallet::storage]
     pub(super) type NextTokenId<T: Config> = StorageValue<_, AssetOf<T>, ValueQuery>;
 
+    #[pallet::type_value]
+    pub(super) fn DefaultFeeOnEmpty() -> Permill {
+        Permill::from_perthousand(3)
+    }
+
+    /// Trading fee of newly created swap pairs
+    #[pallet::storage]
+    #[pallet::getter(fn default_fee)]
+    pub(super) type DefaultFee<T: Config> = StorageValue<_, Permill, ValueQuery, DefaultFeeOnEmpty>;
+
+    /// Share of trading fees paid to the treasury
+    #[pallet::storage]
+    #[pallet::getter(fn protocol_share)]
+    pub(super) type ProtocolShare<T: Config> = StorageValue<_, Permill, ValueQuery>;
+
+    /// Trading fees accrued by liquidity providers and the treasury
+    #[pallet::storage]
+    #[pallet::getter(fn fees)]
+    pub(super) type Fees<T: Config> =
+        StorageMap<_, Twox64Concat, AssetOf<T>, FeesOf<T>, ValueQuery>;
+
+    /// Farming curves chosen for a swap pair with the height they took effect, oldest first,
+    /// the default curve is used before the first change
+    #[pallet::storage]
+    pub(super) type Curves<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        AssetOf<T>,
+        BoundedVec<(HeightOf<T>, Curve), T::MaxCurveChanges>,
+        ValueQuery,
+    >;
+
+    /// Cumulative price of a swap pair, as of the last time its reserves changed
+    #[pallet::storage]
+    pub(super) type Accumulator<T: Config> =
+        StorageMap<_, Twox64Concat, AssetOf<T>, ObservationOf<T>>;
+
+    /// Periodic snapshots of the cumulative price of a swap pair, oldest first
+    #[pallet::storage]
+    pub(super) type Observations<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        AssetOf<T>,
+        BoundedVec<ObservationOf<T>, T::MaxObservations>,
+        ValueQuery,
+    >;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub fn deposit_event)]
     pub enum Event<T: Config> {
@@ -154,26 +257,55 @@ pub mod pallet {
         TokenBought(AssetOf<T>, AccountOf<T>, BalanceOf<T>, BalanceOf<T>),
         /// Tokens sold \[id, account, tokens, currency\]
         TokenSold(AssetOf<T>, AccountOf<T>, BalanceOf<T>, BalanceOf<T>),
+        /// Royalty paid out of trading fees \[id, account, tokens, currency\]
+        RoyaltyPaid(AssetOf<T>, AccountOf<T>, BalanceOf<T>, BalanceOf<T>),
+        /// Protocol share of trading fees paid to the treasury \[id, tokens, currency\]
+        ProtocolFeePaid(AssetOf<T>, BalanceOf<T>, BalanceOf<T>),
+        /// Trading fee of a swap pair updated \[id, fee\]
+        FeeUpdated(AssetOf<T>, Permill),
+        /// Farming curve of a swap pair updated \[id, curve\]
+        CurveUpdated(AssetOf<T>, Curve),
+        /// Trading fee of newly created swap pairs updated \[fee\]
+        DefaultFeeUpdated(Permill),
+        /// Protocol share of trading fees updated \[share\]
+        ProtocolShareUpdated(Permill),
+        /// Tokens swapped for other tokens \[from, to, account, tokens sold, tokens bought\]
+        TokensSwapped(
+            AssetOf<T>,
+            AssetOf<T>,
+            AccountOf<T>,
+            BalanceOf<T>,
+            BalanceOf<T>,
+        ),
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            migrations::migrate::<T>()
+        }
+    }
 
     #[pallet::error]
     pub enum Error<T> {
         Deadline,
         Exists,
+        FeeTooHigh,
+        ProtocolShareTooHigh,
         InsufficientCurrency,
+        InsufficientHistory,
         InsufficientLiquidity,
         InsufficientTokens,
         NoLiquidity,
         NotExists,
         Overflow,
+        SameTokens,
         TooExpensiveCurrency,
         TooExpensiveTokens,
         TooLowCurrency,
         TooLowLiquidity,
         TooLowTokens,
+        TooManyCurveChanges,
         ZeroCurrency,
         ZeroLiquidity,
         ZeroTokens,
@@ -354,6 +486,58 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Swap exact tokens for other tokens, routed through the currency
+        ///
+        /// * `token_in` - The Asset ID of tokens to be sold
+        /// * `token_out` - The Asset ID of tokens to be bought
+        /// * `tokens` - The amount of tokens to be sold
+        /// * `min_tokens` - The minimum amount of tokens to be gained
+        /// * `deadline` - The block number at which the swap should be invalidated
+        #[pallet::weight(T::WeightInfo::swap_exact_tokens_for_tokens())]
+        pub fn swap_exact_tokens_for_tokens(
+            origin: OriginFor<T>,
+            #[pallet::compact] token_in: AssetOf<T>,
+            #[pallet::compact] token_out: AssetOf<T>,
+            #[pallet::compact] tokens: BalanceOf<T>,
+            #[pallet::compact] min_tokens: BalanceOf<T>,
+            deadline: HeightOf<T>,
+        ) -> DispatchResult {
+            let height = <frame_system::Pallet<T>>::block_number();
+            ensure!(deadline > height, Error::<T>::Deadline);
+
+            let who = ensure_signed(origin)?;
+
+            let _ = Self::token_to_token_in(who, token_in, token_out, tokens, min_tokens, false)?;
+
+            Ok(())
+        }
+
+        /// Swap other tokens for exact tokens, routed through the currency
+        ///
+        /// * `token_in` - The Asset ID of tokens to be sold
+        /// * `token_out` - The Asset ID of tokens to be bought
+        /// * `tokens` - The amount of tokens to be bought
+        /// * `max_tokens` - The maximum amount of tokens to be spent
+        /// * `deadline` - The block number at which the swap should be invalidated
+        #[pallet::weight(T::WeightInfo::swap_tokens_for_exact_tokens())]
+        pub fn swap_tokens_for_exact_tokens(
+            origin: OriginFor<T>,
+            #[pallet::compact] token_in: AssetOf<T>,
+            #[pallet::compact] token_out: AssetOf<T>,
+            #[pallet::compact] tokens: BalanceOf<T>,
+            #[pallet::compact] max_tokens: BalanceOf<T>,
+            deadline: HeightOf<T>,
+        ) -> DispatchResult {
+            let height = <frame_system::Pallet<T>>::block_number();
+            ensure!(deadline > height, Error::<T>::Deadline);
+
+            let who = ensure_signed(origin)?;
+
+            let _ = Self::token_to_token_out(who, token_in, token_out, tokens, max_tokens, false)?;
+
+            Ok(())
+        }
+
         /// Acquire Liquidity
         ///
         /// * `lp_token_id` - The Liquidity Provider Token ID
@@ -368,12 +552,110 @@ pub mod pallet {
             ensure!(liquidity.owner == who, Error::<T>::NotExists);
 
             T::Assets::mint_into(liquidity.token_id, &who, reward)?;
+            T::Holders::note_holder(liquidity.token_id, &who);
 
             let claimed = <frame_system::Pallet<T>>::block_number();
             <Account<T>>::insert(&who, lp_token_id, claimed);
 
             Ok(())
         }
+
+        /// Update trading fee of a swap pair
+        ///
+        /// * `token_id` - The Asset ID
+        /// * `fee` - The trading fee
+        #[pallet::weight(T::WeightInfo::set_fee())]
+        pub fn set_fee(
+            origin: OriginFor<T>,
+            #[pallet::compact] token_id: AssetOf<T>,
+            fee: Permill,
+        ) -> DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+
+            ensure!(fee <= T::MaxFee::get(), Error::<T>::FeeTooHigh);
+
+            <Metadata<T>>::try_mutate(token_id, |maybe| -> DispatchResult {
+                let meta = maybe.as_mut().ok_or(Error::<T>::NotExists)?;
+
+                meta.fee = fee;
+
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::FeeUpdated(token_id, fee));
+
+            Ok(())
+        }
+
+        /// Update trading fee of newly created swap pairs,
+        /// existing swap pairs are not affected
+        ///
+        /// * `fee` - The trading fee
+        #[pallet::weight(T::WeightInfo::set_default_fee())]
+        pub fn set_default_fee(origin: OriginFor<T>, fee: Permill) -> DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+
+            ensure!(fee <= T::MaxFee::get(), Error::<T>::FeeTooHigh);
+
+            <DefaultFee<T>>::put(fee);
+
+            Self::deposit_event(Event::DefaultFeeUpdated(fee));
+
+            Ok(())
+        }
+
+        /// Update share of trading fees paid to the treasury
+        ///
+        /// * `share` - The protocol share
+        #[pallet::weight(T::WeightInfo::set_protocol_share())]
+        pub fn set_protocol_share(origin: OriginFor<T>, share: Permill) -> DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                share <= T::MaxProtocolShare::get(),
+                Error::<T>::ProtocolShareTooHigh
+            );
+
+            <ProtocolShare<T>>::put(share);
+
+            Self::deposit_event(Event::ProtocolShareUpdated(share));
+
+            Ok(())
+        }
+
+        /// Choose farming curve of a swap pair, rewards earned before are kept
+        ///
+        /// * `token_id` - The Asset ID
+        /// * `curve` - The farming curve, `Default` to use the curve of all swap pairs
+        #[pallet::weight(T::WeightInfo::set_curve())]
+        pub fn set_curve(
+            origin: OriginFor<T>,
+            #[pallet::compact] token_id: AssetOf<T>,
+            curve: Curve,
+        ) -> DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+
+            ensure!(<Metadata<T>>::contains_key(token_id), Error::<T>::NotExists);
+
+            let height = <frame_system::Pallet<T>>::block_number();
+
+            <Curves<T>>::try_mutate(token_id, |curves| -> DispatchResult {
+                // a change in the same block replaces the last one
+                if matches!(curves.last(), Some((changed, _)) if *changed == height) {
+                    curves.remove(curves.len() - 1);
+                }
+
+                curves
+                    .try_push((height, curve))
+                    .map_err(|_| Error::<T>::TooManyCurveChanges)?;
+
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::CurveUpdated(token_id, curve));
+
+            Ok(())
+        }
     }
 
     #[pallet::genesis_config]
@@ -427,6 +709,7 @@ pub mod pallet {
                     } else {
                         *maybe = Some(types::Swap {
                             liquidity: amount,
+                            fee: <DefaultFee<T>>::get(),
                             ..Default::default()
                         });
                     }
@@ -0,0 +1,49 @@
+use crate::{Config, Pallet};
+use frame_support::{traits::Get, weights::Weight};
+use sp_runtime::traits::Saturating;
+
+pub fn migrate<T: Config>() -> Weight {
+    use frame_support::traits::StorageVersion;
+
+    let version = StorageVersion::get::<Pallet<T>>();
+    let mut weight: Weight = 0;
+
+    if version < 1 {
+        weight.saturating_accrue(v1::migrate::<T>());
+        StorageVersion::new(1).put::<Pallet<T>>();
+    }
+
+    weight
+}
+
+mod v1 {
+    use super::*;
+    use crate::{types, BalanceOf, HeightOf, Metadata, SwapOf};
+    use codec::{Decode, Encode};
+    use sp_runtime::{Permill, RuntimeDebug};
+
+    #[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug)]
+    pub struct OldSwap<N, B> {
+        pub created: N,
+        pub liquidity: B,
+    }
+
+    type OldSwapOf<T> = OldSwap<HeightOf<T>, BalanceOf<T>>;
+
+    pub fn migrate<T: Config>() -> Weight {
+        let mut count: Weight = 0;
+
+        // swap pairs used to charge a fixed fee of 0.3%
+        <Metadata<T>>::translate_values(|old: OldSwapOf<T>| -> Option<SwapOf<T>> {
+            count.saturating_inc();
+
+            Some(types::Swap {
+                created: old.created,
+                liquidity: old.liquidity,
+                fee: Permill::from_perthousand(3),
+            })
+        });
+
+        T::DbWeight::get().reads_writes(count, count)
+    }
+}
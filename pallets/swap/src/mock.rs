@@ -6,12 +6,15 @@ use sp_core::{sr25519, H256};
 use sp_runtime::{
     testing::Header,
     traits::{BlakeTwo256, IdentityLookup},
+    Permill,
 };
 
 type UncheckedExtrinsic = system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = system::mocking::MockBlock<Test>;
 
 pub const ALICE: sr25519::Public = sr25519::Public([1; 32]);
+pub const BOB: sr25519::Public = sr25519::Public([2; 32]);
+pub const TREASURY: sr25519::Public = sr25519::Public([0xaa; 32]);
 
 frame_support::construct_runtime!(
     pub enum Test where
@@ -107,7 +110,18 @@ impl pallet_balances::Config for Test {
 }
 
 parameter_types! {
+    pub static InitialFarmingReward: Balance = 100;
+    pub static TotalFarmingReward: Balance = 1_000_000;
+    pub static HalvingPeriod: BlockNumber = 100;
+    pub static InitialMintingValueBase: Balance = 1_000_000;
+    pub static LinearFarmingPeriod: BlockNumber = 1_000;
+    pub const SwapMaxCurveChanges: u32 = 4;
+    pub const SwapMaxFee: Permill = Permill::from_percent(10);
+    pub const SwapMaxProtocolShare: Permill = Permill::from_percent(50);
+    pub const SwapMaxObservations: u32 = 4;
+    pub const SwapObservationPeriod: BlockNumber = 10;
     pub const SwapPalletId: PalletId = PalletId(*b"prm/swap");
+    pub const TreasuryAccount: sr25519::Public = TREASURY;
 }
 
 pub struct FarmingCurve;
@@ -128,7 +142,19 @@ impl parami_swap::Config for Test {
     type Assets = Assets;
     type Currency = Balances;
     type FarmingCurve = FarmingCurve;
+    type ExponentialDecayCurve =
+        parami_swap::ExponentialDecayFarmingCurve<Test, InitialFarmingReward, TotalFarmingReward>;
+    type HalvingCurve = parami_swap::HalvingFarmingCurve<Test, TotalFarmingReward, HalvingPeriod>;
+    type ForceOrigin = EnsureRoot<Self::AccountId>;
+    type MaxCurveChanges = SwapMaxCurveChanges;
+    type MaxFee = SwapMaxFee;
+    type MaxProtocolShare = SwapMaxProtocolShare;
+    type MaxObservations = SwapMaxObservations;
+    type ObservationPeriod = SwapObservationPeriod;
     type PalletId = SwapPalletId;
+    type Royalties = ();
+    type Holders = ();
+    type TreasuryAccount = TreasuryAccount;
     type WeightInfo = ();
 }
 
@@ -144,9 +170,12 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
     .unwrap();
 
     pallet_assets::GenesisConfig::<Test> {
-        assets: vec![(1, ALICE, false, 1)],
-        metadata: vec![(1, b"Test Token".to_vec(), b"XTT".to_vec(), 18)],
-        accounts: vec![(1, ALICE, 44)],
+        assets: vec![(1, ALICE, false, 1), (2, ALICE, false, 1)],
+        metadata: vec![
+            (1, b"Test Token".to_vec(), b"XTT".to_vec(), 18),
+            (2, b"Test Token 2".to_vec(), b"XTT2".to_vec(), 18),
+        ],
+        accounts: vec![(1, ALICE, 44), (2, ALICE, 44)],
     }
     .assimilate_storage(&mut t)
     .unwrap();
@@ -1,9 +1,13 @@
-use crate::{mock::*, Account, Error, Liquidity, Metadata, Provider};
+use crate::{
+    mock::*, Account, Curve, Curves, Error, ExponentialDecayFarmingCurve, FarmingCurve, Fees,
+    HalvingFarmingCurve, LinearFarmingCurve, Liquidity, Metadata, Observations, Provider,
+};
 use frame_support::{
     assert_noop, assert_ok,
     traits::{tokens::fungibles::Mutate as FungMutate, Currency},
 };
-use parami_traits::Swaps;
+use parami_traits::{AccountMigration, Migrated, Prices, Swaps};
+use sp_runtime::{traits::Zero, DispatchError, FixedPointNumber, FixedU128, Permill};
 
 #[test]
 fn should_create() {
@@ -102,6 +106,37 @@ fn should_add_liquidity() {
     });
 }
 
+#[test]
+fn should_migrate_liquidity() {
+    new_test_ext().execute_with(|| {
+        let token = 1;
+
+        assert_ok!(Swap::create(Origin::signed(ALICE), token));
+
+        assert_ok!(Swap::add_liquidity(
+            Origin::signed(ALICE),
+            token,
+            200,
+            200,
+            20,
+            100,
+        ));
+
+        assert_noop!(Swap::migrate(&ALICE, &BOB, 0), DispatchError::Exhausted);
+
+        assert_eq!(
+            Swap::migrate(&ALICE, &BOB, 1),
+            Ok(vec![Migrated::Liquidity(0, 200)])
+        );
+
+        assert_eq!(<Provider<Test>>::get(token, &ALICE), 0);
+        assert_eq!(<Provider<Test>>::get(token, &BOB), 200);
+        assert_eq!(<Account<Test>>::get(&ALICE, 0), None);
+        assert_eq!(<Account<Test>>::get(&BOB, 0), Some(0));
+        assert_eq!(<Liquidity<Test>>::get(0).unwrap().owner, BOB);
+    });
+}
+
 #[test]
 fn should_remove_liquidity() {
     new_test_ext().execute_with(|| {
@@ -313,18 +348,766 @@ fn should_buy_currency() {
     });
 }
 
+#[test]
+fn should_swap_exact_tokens_for_tokens() {
+    new_test_ext().execute_with(|| {
+        let token_in = 1;
+        let token_out = 2;
+
+        for token in [token_in, token_out] {
+            assert_ok!(Swap::create(Origin::signed(ALICE), token));
+
+            assert_ok!(Swap::add_liquidity(
+                Origin::signed(ALICE),
+                token,
+                420,
+                420,
+                42,
+                100,
+            ));
+        }
+
+        let pot_in = Swap::get_pool_account(token_in);
+        let pot_out = Swap::get_pool_account(token_out);
+
+        assert_ok!(Assets::mint_into(token_in, &ALICE, 42));
+
+        assert_eq!(
+            Swap::token_to_token_in_dry(token_in, token_out, 20),
+            Ok((8, 133))
+        );
+
+        assert_noop!(
+            Swap::swap_exact_tokens_for_tokens(
+                Origin::signed(ALICE),
+                token_in,
+                token_in,
+                20,
+                1,
+                100
+            ),
+            Error::<Test>::SameTokens,
+        );
+
+        assert_noop!(
+            Swap::swap_exact_tokens_for_tokens(
+                Origin::signed(ALICE),
+                token_in,
+                token_out,
+                20,
+                9,
+                100
+            ),
+            Error::<Test>::TooExpensiveTokens,
+        );
+
+        assert_ok!(Swap::swap_exact_tokens_for_tokens(
+            Origin::signed(ALICE),
+            token_in,
+            token_out,
+            20,
+            8,
+            100
+        ));
+
+        assert_eq!(Balances::free_balance(&pot_in), 420 - 133);
+        assert_eq!(Assets::balance(token_in, &pot_in), 42 + 20);
+
+        assert_eq!(Balances::free_balance(&pot_out), 420 + 133);
+        assert_eq!(Assets::balance(token_out, &pot_out), 42 - 8);
+
+        assert_eq!(Balances::free_balance(&ALICE), 10000 - 420 * 2);
+        assert_eq!(Assets::balance(token_in, &ALICE), 44 - 42 + 42 - 20);
+        assert_eq!(Assets::balance(token_out, &ALICE), 44 - 42 + 8);
+    });
+}
+
+#[test]
+fn should_swap_tokens_for_exact_tokens() {
+    new_test_ext().execute_with(|| {
+        let token_in = 1;
+        let token_out = 2;
+
+        for token in [token_in, token_out] {
+            assert_ok!(Swap::create(Origin::signed(ALICE), token));
+
+            assert_ok!(Swap::add_liquidity(
+                Origin::signed(ALICE),
+                token,
+                420,
+                420,
+                42,
+                100,
+            ));
+        }
+
+        let pot_in = Swap::get_pool_account(token_in);
+        let pot_out = Swap::get_pool_account(token_out);
+
+        assert_ok!(Assets::mint_into(token_in, &ALICE, 42));
+
+        assert_eq!(
+            Swap::token_to_token_out_dry(token_in, token_out, 5),
+            Ok((8, 58))
+        );
+
+        assert_noop!(
+            Swap::swap_tokens_for_exact_tokens(
+                Origin::signed(ALICE),
+                token_out,
+                token_out,
+                5,
+                1000,
+                100
+            ),
+            Error::<Test>::SameTokens,
+        );
+
+        assert_noop!(
+            Swap::swap_tokens_for_exact_tokens(
+                Origin::signed(ALICE),
+                token_in,
+                token_out,
+                5,
+                7,
+                100
+            ),
+            Error::<Test>::TooLowTokens,
+        );
+
+        assert_ok!(Swap::swap_tokens_for_exact_tokens(
+            Origin::signed(ALICE),
+            token_in,
+            token_out,
+            5,
+            8,
+            100
+        ));
+
+        assert_eq!(Balances::free_balance(&pot_in), 420 - 58);
+        assert_eq!(Assets::balance(token_in, &pot_in), 42 + 8);
+
+        assert_eq!(Balances::free_balance(&pot_out), 420 + 58);
+        assert_eq!(Assets::balance(token_out, &pot_out), 42 - 5);
+
+        assert_eq!(Balances::free_balance(&ALICE), 10000 - 420 * 2);
+        assert_eq!(Assets::balance(token_in, &ALICE), 44 - 42 + 42 - 8);
+        assert_eq!(Assets::balance(token_out, &ALICE), 44 - 42 + 5);
+    });
+}
+
+#[test]
+fn should_set_fee() {
+    new_test_ext().execute_with(|| {
+        let token = 1;
+
+        assert_ok!(Swap::create(Origin::signed(ALICE), token));
+
+        assert_ok!(Swap::add_liquidity(
+            Origin::signed(ALICE),
+            token,
+            420,
+            420,
+            42,
+            100,
+        ));
+
+        assert_eq!(
+            <Metadata<Test>>::get(token).unwrap().fee,
+            Permill::from_perthousand(3)
+        );
+
+        assert_noop!(
+            Swap::set_fee(Origin::signed(ALICE), token, Permill::from_percent(1)),
+            DispatchError::BadOrigin
+        );
+
+        assert_noop!(
+            Swap::set_fee(Origin::root(), token, Permill::from_percent(11)),
+            Error::<Test>::FeeTooHigh
+        );
+
+        assert_noop!(
+            Swap::set_fee(Origin::root(), 9, Permill::from_percent(1)),
+            Error::<Test>::NotExists
+        );
+
+        assert_ok!(Swap::set_fee(
+            Origin::root(),
+            token,
+            Permill::from_percent(10)
+        ));
+
+        assert_eq!(
+            <Metadata<Test>>::get(token).unwrap().fee,
+            Permill::from_percent(10)
+        );
+
+        assert_ok!(Assets::mint_into(token, &ALICE, 42));
+
+        assert_ok!(Swap::sell_tokens(Origin::signed(ALICE), token, 20, 1, 100));
+
+        let pot = Swap::get_pool_account(token);
+        assert_eq!(Balances::free_balance(&pot), 420 - 122);
+        assert_eq!(Assets::balance(token, &pot), 42 + 20);
+
+        let fees = <Fees<Test>>::get(token);
+        assert_eq!(fees.lp_tokens, 2);
+        assert_eq!(fees.protocol_tokens, 0);
+    });
+}
+
+#[test]
+fn should_set_default_fee() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Swap::set_default_fee(Origin::signed(ALICE), Permill::from_percent(1)),
+            DispatchError::BadOrigin
+        );
+
+        assert_noop!(
+            Swap::set_default_fee(Origin::root(), Permill::from_percent(11)),
+            Error::<Test>::FeeTooHigh
+        );
+
+        assert_ok!(Swap::set_default_fee(
+            Origin::root(),
+            Permill::from_percent(1)
+        ));
+
+        assert_eq!(Swap::default_fee(), Permill::from_percent(1));
+
+        assert_ok!(Swap::create(Origin::signed(ALICE), 1));
+
+        assert_eq!(
+            <Metadata<Test>>::get(1).unwrap().fee,
+            Permill::from_percent(1)
+        );
+    });
+}
+
+#[test]
+fn should_pay_protocol_share() {
+    new_test_ext().execute_with(|| {
+        let token = 1;
+
+        assert_ok!(Swap::create(Origin::signed(ALICE), token));
+
+        assert_ok!(Swap::add_liquidity(
+            Origin::signed(ALICE),
+            token,
+            420,
+            420,
+            42,
+            100,
+        ));
+
+        assert_noop!(
+            Swap::set_protocol_share(Origin::signed(ALICE), Permill::from_percent(50)),
+            DispatchError::BadOrigin
+        );
+
+        assert_noop!(
+            Swap::set_protocol_share(Origin::root(), Permill::from_percent(60)),
+            Error::<Test>::ProtocolShareTooHigh
+        );
+
+        assert_ok!(Swap::set_fee(
+            Origin::root(),
+            token,
+            Permill::from_percent(10)
+        ));
+        assert_ok!(Swap::set_protocol_share(
+            Origin::root(),
+            Permill::from_percent(50)
+        ));
+
+        assert_ok!(Swap::sell_currency(
+            Origin::signed(ALICE),
+            token,
+            300,
+            1,
+            100
+        ));
+
+        let pot = Swap::get_pool_account(token);
+        assert_eq!(Balances::free_balance(&pot), 420 + 300 - 15);
+        assert_eq!(Assets::balance(token, &pot), 42 - 13);
+
+        assert_eq!(Balances::free_balance(&TREASURY), 15);
+
+        let fees = <Fees<Test>>::get(token);
+        assert_eq!(fees.lp_currency, 15);
+        assert_eq!(fees.protocol_currency, 15);
+
+        assert_ok!(Assets::mint_into(token, &ALICE, 42));
+
+        assert_ok!(Swap::sell_tokens(Origin::signed(ALICE), token, 20, 1, 100));
+
+        assert_eq!(Assets::balance(token, &pot), 42 - 13 + 20 - 1);
+
+        assert_eq!(Assets::balance(token, &TREASURY), 1);
+
+        let fees = <Fees<Test>>::get(token);
+        assert_eq!(fees.lp_tokens, 1);
+        assert_eq!(fees.protocol_tokens, 1);
+    });
+}
+
+#[test]
+fn should_calculate_twap() {
+    new_test_ext().execute_with(|| {
+        let token = 1;
+
+        System::set_block_number(1);
+
+        assert_ok!(Swap::create(Origin::signed(ALICE), token));
+
+        assert_ok!(Swap::add_liquidity(
+            Origin::signed(ALICE),
+            token,
+            420,
+            420,
+            42,
+            100,
+        ));
+
+        assert_noop!(Swap::twap(token, 5), Error::<Test>::InsufficientHistory);
+        assert_noop!(Swap::twap(token, 0), Error::<Test>::InsufficientHistory);
+        assert_noop!(Swap::twap(9, 5), Error::<Test>::InsufficientHistory);
+
+        System::set_block_number(11);
+
+        assert_eq!(
+            Swap::twap(token, 10),
+            Ok(FixedU128::saturating_from_integer(10u128))
+        );
+
+        assert_ok!(Swap::sell_currency(
+            Origin::signed(ALICE),
+            token,
+            300,
+            1,
+            100
+        ));
+
+        System::set_block_number(21);
+
+        let spot = FixedU128::saturating_from_rational(420u128 + 300, 42u128 - 14);
+
+        assert_eq!(Swap::twap(token, 10), Ok(spot));
+
+        let cumulative = FixedU128::saturating_from_integer(100u128)
+            + spot * FixedU128::saturating_from_integer(10u128);
+        assert_eq!(
+            Swap::twap(token, 20),
+            Ok(cumulative / FixedU128::saturating_from_integer(20u128))
+        );
+    });
+}
+
+#[test]
+fn should_bound_observations() {
+    new_test_ext().execute_with(|| {
+        let token = 1;
+
+        System::set_block_number(1);
+
+        assert_ok!(Swap::create(Origin::signed(ALICE), token));
+
+        assert_ok!(Swap::add_liquidity(
+            Origin::signed(ALICE),
+            token,
+            420,
+            420,
+            42,
+            100,
+        ));
+
+        for height in [5, 11, 21, 31, 41] {
+            System::set_block_number(height);
+
+            assert_ok!(Swap::sell_currency(
+                Origin::signed(ALICE),
+                token,
+                50,
+                1,
+                100
+            ));
+        }
+
+        let heights: Vec<_> = <Observations<Test>>::get(token)
+            .iter()
+            .map(|observation| observation.height)
+            .collect();
+        assert_eq!(heights, vec![11, 21, 31, 41]);
+
+        assert_noop!(Swap::twap(token, 40), Error::<Test>::InsufficientHistory);
+        assert!(Swap::twap(token, 30).is_ok());
+    });
+}
+
+#[test]
+fn should_query_pools() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(Swap::pool_of(1), Error::<Test>::NotExists);
+
+        for token in [1, 2] {
+            assert_ok!(Swap::create(Origin::signed(ALICE), token));
+        }
+
+        assert_ok!(Swap::add_liquidity(
+            Origin::signed(ALICE),
+            1,
+            420,
+            420,
+            42,
+            100,
+        ));
+
+        let pot = Swap::get_pool_account(1);
+        assert_eq!(
+            Swap::pool_of(1),
+            Ok((
+                pot,
+                420,
+                42,
+                420,
+                FixedU128::saturating_from_integer(10u128)
+            ))
+        );
+
+        let pot = Swap::get_pool_account(2);
+        assert_eq!(Swap::pool_of(2), Ok((pot, 0, 0, 0, FixedU128::zero())));
+
+        let first = Swap::pools(None, 1);
+        assert_eq!(first.len(), 1);
+
+        let rest = Swap::pools(Some(first[0]), 10);
+        assert_eq!(rest.len(), 1);
+
+        let mut pools = [first, rest].concat();
+        pools.sort();
+        assert_eq!(pools, vec![1, 2]);
+
+        assert_eq!(Swap::pools(None, 0), vec![]);
+    });
+}
+
+#[test]
+fn should_query_positions() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        for token in [1, 2] {
+            assert_ok!(Swap::create(Origin::signed(ALICE), token));
+
+            assert_ok!(Swap::add_liquidity(
+                Origin::signed(ALICE),
+                token,
+                420,
+                420,
+                42,
+                100,
+            ));
+        }
+
+        assert_eq!(Swap::positions_of(&BOB, None, 10), vec![]);
+
+        let first = Swap::positions_of(&ALICE, None, 1);
+        assert_eq!(first.len(), 1);
+
+        let rest = Swap::positions_of(&ALICE, Some(first[0].0), 10);
+        assert_eq!(rest.len(), 1);
+
+        let mut positions = [first, rest].concat();
+        positions.sort();
+
+        let lp_token_id = positions[0].0;
+        assert_eq!(
+            positions,
+            vec![(lp_token_id, 1, 420, 1, 0), (lp_token_id + 1, 2, 420, 1, 0)]
+        );
+    });
+}
+
+#[test]
+fn should_set_curve() {
+    new_test_ext().execute_with(|| {
+        let token = 1;
+
+        System::set_block_number(1);
+
+        assert_ok!(Swap::create(Origin::signed(ALICE), token));
+
+        assert_ok!(Swap::add_liquidity(
+            Origin::signed(ALICE),
+            token,
+            420,
+            420,
+            42,
+            100,
+        ));
+
+        System::set_block_number(11);
+
+        assert_eq!(Swap::calculate_reward(0).unwrap().1, 100);
+
+        assert_noop!(
+            Swap::set_curve(Origin::signed(ALICE), token, Curve::Halving),
+            DispatchError::BadOrigin
+        );
+
+        assert_noop!(
+            Swap::set_curve(Origin::root(), 9, Curve::Halving),
+            Error::<Test>::NotExists
+        );
+
+        assert_ok!(Swap::set_curve(Origin::root(), token, Curve::Halving));
+
+        assert_eq!(Swap::curve_of(token), Curve::Halving);
+
+        // rewards earned before are kept
+        assert_eq!(Swap::calculate_reward(0).unwrap().1, 100);
+
+        System::set_block_number(21);
+
+        // 1_000_000 / (2 * 100) per block in the first period
+        let halving = 5000 * 10;
+        assert_eq!(Swap::calculate_reward(0).unwrap().1, 100 + halving);
+
+        // a change in the same block replaces the last one
+        assert_ok!(Swap::set_curve(Origin::root(), token, Curve::Default));
+        assert_ok!(Swap::set_curve(
+            Origin::root(),
+            token,
+            Curve::ExponentialDecay
+        ));
+
+        assert_eq!(
+            <Curves<Test>>::get(token).into_inner(),
+            vec![(11, Curve::Halving), (21, Curve::ExponentialDecay)]
+        );
+
+        System::set_block_number(31);
+
+        let decay = ExponentialDecay::calculate_farming_reward(1, 21, 31, 0);
+        assert!(decay > 0);
+        assert_eq!(Swap::calculate_reward(0).unwrap().1, 100 + halving + decay);
+
+        assert_ok!(Swap::set_curve(Origin::root(), token, Curve::Default));
+
+        assert_eq!(Swap::curve_of(token), Curve::Default);
+
+        System::set_block_number(41);
+
+        assert_eq!(
+            Swap::calculate_reward(0).unwrap().1,
+            100 + halving + decay + 100
+        );
+
+        System::set_block_number(51);
+
+        assert_ok!(Swap::set_curve(Origin::root(), token, Curve::Halving));
+
+        System::set_block_number(61);
+
+        assert_noop!(
+            Swap::set_curve(Origin::root(), token, Curve::Default),
+            Error::<Test>::TooManyCurveChanges
+        );
+    });
+}
+
+type ExponentialDecay =
+    ExponentialDecayFarmingCurve<Test, InitialFarmingReward, TotalFarmingReward>;
+type Halving = HalvingFarmingCurve<Test, TotalFarmingReward, HalvingPeriod>;
+type Linear = LinearFarmingCurve<
+    Test,
+    InitialFarmingReward,
+    InitialMintingValueBase,
+    TotalFarmingReward,
+    LinearFarmingPeriod,
+>;
+
+/// xorshift, to sample parameters and heights reproducibly
+fn next_random(seed: &mut u64) -> u64 {
+    *seed ^= *seed << 13;
+    *seed ^= *seed >> 7;
+    *seed ^= *seed << 17;
+    *seed
+}
+
+/// Check a curve is issued block by block consistently,
+/// the reward of a span should be the sum of rewards of its sub-spans,
+/// and the reward of each block should not increase
+fn check_curve<C: FarmingCurve<Test>>(seed: &mut u64, created: u64, end: u64) {
+    let whole = C::calculate_farming_reward(created, created, created + end, 0);
+
+    let mut height = created;
+    let mut sum = 0u128;
+    while height < created + end {
+        let next = (height + 1 + next_random(seed) % (end / 8 + 1)).min(created + end);
+        sum += C::calculate_farming_reward(created, height, next, 0);
+        height = next;
+    }
+    assert_eq!(sum, whole);
+
+    let first = C::calculate_farming_reward(created, created, created + 1, 0);
+    for _ in 0..64 {
+        let x = next_random(seed) % end;
+        let reward = C::calculate_farming_reward(created, created + x, created + x + 1, 0);
+        let later = C::calculate_farming_reward(created, created + x + 1, created + x + 2, 0);
+        assert!(reward <= first + 1);
+        assert!(later <= reward + 1);
+    }
+}
+
+#[test]
+fn should_issue_total_with_exponential_decay_curve() {
+    let mut seed = 42;
+
+    for _ in 0..32 {
+        let total = 1_000 + (next_random(&mut seed) as u128) % 1_000_000_000_000;
+        let base = 1 + total / 1_000_000 + (next_random(&mut seed) as u128) % (total / 1_000);
+        let created = next_random(&mut seed) % 1_000;
+
+        InitialFarmingReward::set(base);
+        TotalFarmingReward::set(total);
+
+        // Sum[b * Power[q, x], {x, 0, Infinity}] = TOTAL
+        assert_eq!(
+            ExponentialDecay::calculate_farming_reward(created, created, u32::MAX as u64, 0),
+            total
+        );
+
+        // the first block issues b
+        assert_eq!(
+            ExponentialDecay::calculate_farming_reward(created, created, created + 1, 0),
+            base
+        );
+
+        let half_life = (total / base) as u64 * 7 / 10;
+        check_curve::<ExponentialDecay>(&mut seed, created, half_life * 4 + 1);
+    }
+}
+
+#[test]
+fn should_issue_total_with_halving_curve() {
+    let mut seed = 42;
+
+    for _ in 0..32 {
+        let total = (next_random(&mut seed) as u128) % 1_000_000_000_000;
+        let period = 1 + next_random(&mut seed) % 10_000;
+        let created = next_random(&mut seed) % 1_000;
+
+        TotalFarmingReward::set(total);
+        HalvingPeriod::set(period);
+
+        let base = total / (2 * period as u128);
+
+        // Sum[b * PERIOD / Power[2, n], {n, 0, Infinity}] = TOTAL
+        let issued = Halving::calculate_farming_reward(created, created, u64::MAX, 0);
+        assert_eq!(issued, base * 2 * period as u128);
+        assert!(total - issued < 2 * period as u128);
+
+        // a half of the total is issued in the first period
+        assert_eq!(
+            Halving::calculate_farming_reward(created, created, created + period, 0),
+            base * period as u128
+        );
+
+        // and a quarter in the second period
+        assert_eq!(
+            Halving::calculate_farming_reward(created, created + period, created + period * 2, 0),
+            base * period as u128 / 2
+        );
+
+        check_curve::<Halving>(&mut seed, created, period * 8);
+    }
+}
+
+#[test]
+fn should_issue_total_with_linear_curve() {
+    let mut seed = 42;
+
+    for _ in 0..32 {
+        let base = 1 + (next_random(&mut seed) as u128) % 1_000_000;
+        let period = 100 + next_random(&mut seed) % 10_000;
+        let created = next_random(&mut seed) % 1_000;
+
+        let full = base * period as u128;
+
+        // the reward of each block lasts for the whole period
+        let total = full / 2 + (next_random(&mut seed) as u128) % (full / 2);
+
+        InitialFarmingReward::set(base);
+        TotalFarmingReward::set(total);
+        LinearFarmingPeriod::set(period);
+
+        // Integrate[-ax + b, {x, 0, PERIOD}] = TOTAL
+        assert_eq!(
+            Linear::calculate_farming_reward(created, created, created + period, 0),
+            total
+        );
+
+        // nothing is issued after the period
+        assert_eq!(
+            Linear::calculate_farming_reward(created, created, u64::MAX, 0),
+            total
+        );
+        assert_eq!(
+            Linear::calculate_farming_reward(created, created + period, created + period * 2, 0),
+            0
+        );
+
+        check_curve::<Linear>(&mut seed, created, period * 2);
+
+        // the reward of each block drops to zero at 2 / 3 of the period
+        TotalFarmingReward::set(full / 4);
+
+        let issued = Linear::calculate_farming_reward(created, created, created + period, 0);
+        assert!(issued > full / 4);
+        assert_eq!(
+            Linear::calculate_farming_reward(created, created, u64::MAX, 0),
+            issued
+        );
+        assert_eq!(
+            Linear::calculate_farming_reward(
+                created,
+                created + period * 3 / 4,
+                created + period * 2,
+                0
+            ),
+            0
+        );
+
+        check_curve::<Linear>(&mut seed, created, period * 2);
+
+        // the reward of each block increases if the total is more than b * PERIOD
+        TotalFarmingReward::set(full * 2);
+
+        assert_eq!(
+            Linear::calculate_farming_reward(created, created, created + period, 0),
+            full * 2
+        );
+    }
+}
+
 #[test]
 fn should_swap_in_piecewisely() {
+    let fee = Permill::from_perthousand(3);
+
     let mut input_reserve = 1_000_000_000_000_000_000_000_000u128;
     let mut output_reserve = 1_000_000_000_000_000_000_000_000u128;
 
     let input = 300_000_000_000_000_000_000_000u128;
-    let obtain_once = Swap::price_sell(input, input_reserve, output_reserve).unwrap();
+    let obtain_once = Swap::price_sell(input, input_reserve, output_reserve, fee).unwrap();
 
     let mut obtain_quintuple = 0;
     {
         let input = 100_000_000_000_000_000_000_000u128;
-        let obtain = Swap::price_sell(input, input_reserve, output_reserve).unwrap();
+        let obtain = Swap::price_sell(input, input_reserve, output_reserve, fee).unwrap();
 
         input_reserve += input;
         output_reserve -= obtain;
@@ -333,7 +1116,7 @@ fn should_swap_in_piecewisely() {
     }
     {
         let input = 110_000_000_000_000_000_000_000u128;
-        let obtain = Swap::price_sell(input, input_reserve, output_reserve).unwrap();
+        let obtain = Swap::price_sell(input, input_reserve, output_reserve, fee).unwrap();
 
         input_reserve += input;
         output_reserve -= obtain;
@@ -342,7 +1125,7 @@ fn should_swap_in_piecewisely() {
     }
     {
         let input = 90_000_000_000_000_000_000_000u128;
-        let obtain = Swap::price_sell(input, input_reserve, output_reserve).unwrap();
+        let obtain = Swap::price_sell(input, input_reserve, output_reserve, fee).unwrap();
 
         obtain_quintuple += obtain;
     }
@@ -352,16 +1135,18 @@ fn should_swap_in_piecewisely() {
 
 #[test]
 fn should_swap_out_piecewisely() {
+    let fee = Permill::from_perthousand(3);
+
     let mut input_reserve = 1_000_000_000_000_000_000_000_000u128;
     let mut output_reserve = 1_000_000_000_000_000_000_000_000u128;
 
     let output = 300_000_000_000_000_000_000_000u128;
-    let coast_once = Swap::price_buy(output, input_reserve, output_reserve).unwrap();
+    let coast_once = Swap::price_buy(output, input_reserve, output_reserve, fee).unwrap();
 
     let mut coast_quintuple = 0;
     {
         let output = 100_000_000_000_000_000_000_000u128;
-        let coast = Swap::price_buy(output, input_reserve, output_reserve).unwrap();
+        let coast = Swap::price_buy(output, input_reserve, output_reserve, fee).unwrap();
 
         input_reserve += coast;
         output_reserve -= output;
@@ -370,7 +1155,7 @@ fn should_swap_out_piecewisely() {
     }
     {
         let output = 90_000_000_000_000_000_000_000u128;
-        let coast = Swap::price_buy(output, input_reserve, output_reserve).unwrap();
+        let coast = Swap::price_buy(output, input_reserve, output_reserve, fee).unwrap();
 
         input_reserve += coast;
         output_reserve -= output;
@@ -379,7 +1164,7 @@ fn should_swap_out_piecewisely() {
     }
     {
         let output = 81_000_000_000_000_000_000_000u128;
-        let coast = Swap::price_buy(output, input_reserve, output_reserve).unwrap();
+        let coast = Swap::price_buy(output, input_reserve, output_reserve, fee).unwrap();
 
         input_reserve += coast;
         output_reserve -= output;
@@ -388,7 +1173,7 @@ fn should_swap_out_piecewisely() {
     }
     {
         let output = 29_000_000_000_000_000_000_000u128;
-        let coast = Swap::price_buy(output, input_reserve, output_reserve).unwrap();
+        let coast = Swap::price_buy(output, input_reserve, output_reserve, fee).unwrap();
 
         coast_quintuple += coast;
     }
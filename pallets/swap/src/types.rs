@@ -2,13 +2,14 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{FixedU128, Permill, RuntimeDebug};
 
 #[derive(Clone, Decode, Default, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Swap<N, B> {
     pub created: N,
     pub liquidity: B,
+    pub fee: Permill,
 }
 
 #[derive(Clone, Decode, Default, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
@@ -20,3 +21,37 @@ pub struct Liquidity<A, B: MaxEncodedLen, N, T> {
     pub amount: B,
     pub minted: N,
 }
+
+#[derive(Clone, Decode, Default, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Fees<B> {
+    pub lp_tokens: B,
+    pub lp_currency: B,
+    pub protocol_tokens: B,
+    pub protocol_currency: B,
+}
+
+#[derive(Clone, Decode, Default, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Observation<N> {
+    pub height: N,
+    pub cumulative: FixedU128,
+}
+
+/// The farming curve of a swap pair
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Curve {
+    /// The curve configured for all swap pairs
+    Default,
+    /// The exponential-decay curve
+    ExponentialDecay,
+    /// The piecewise-halving curve
+    Halving,
+}
+
+impl Default for Curve {
+    fn default() -> Self {
+        Curve::Default
+    }
+}
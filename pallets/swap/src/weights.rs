@@ -50,15 +50,23 @@ pub trait WeightInfo {
     fn sell_tokens() -> Weight;
     fn sell_currency() -> Weight;
     fn buy_currency() -> Weight;
+    fn swap_exact_tokens_for_tokens() -> Weight;
+    fn swap_tokens_for_exact_tokens() -> Weight;
+    fn set_fee() -> Weight;
+    fn set_default_fee() -> Weight;
+    fn set_protocol_share() -> Weight;
+    fn set_curve() -> Weight;
+    fn migrate_account(n: u32, ) -> Weight;
 }
 
 /// Weights for parami_swap using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     // Storage: Swap Metadata (r:1 w:1)
+    // Storage: Swap DefaultFee (r:1 w:0)
     fn create() -> Weight {
         (12_000_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
             .saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
     // Storage: Swap Metadata (r:1 w:1)
@@ -69,10 +77,12 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     // Storage: Swap Provider (r:1 w:1)
     // Storage: Swap Account (r:0 w:1)
     // Storage: Swap Liquidity (r:0 w:1)
+    // Storage: Swap Accumulator (r:1 w:1)
+    // Storage: Swap Observations (r:1 w:1)
     fn add_liquidity() -> Weight {
         (75_000_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(7 as Weight))
-            .saturating_add(T::DbWeight::get().writes(9 as Weight))
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(11 as Weight))
     }
     // Storage: Swap Liquidity (r:1 w:1)
     // Storage: Swap Metadata (r:1 w:1)
@@ -81,51 +91,157 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     // Storage: Swap Provider (r:1 w:1)
     // Storage: Assets Asset (r:1 w:1)
     // Storage: Swap Account (r:0 w:1)
+    // Storage: Swap Accumulator (r:1 w:1)
+    // Storage: Swap Observations (r:1 w:1)
     fn remove_liquidity() -> Weight {
         (76_000_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(7 as Weight))
-            .saturating_add(T::DbWeight::get().writes(8 as Weight))
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(10 as Weight))
     }
-    // Storage: System Account (r:1 w:1)
+    // Storage: System Account (r:2 w:2)
     // Storage: Assets Account (r:2 w:2)
     // Storage: Assets Asset (r:1 w:1)
+    // Storage: Nft Royalty (r:1 w:0)
+    // Storage: Nft Frozen (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Swap Metadata (r:1 w:0)
+    // Storage: Swap ProtocolShare (r:1 w:0)
+    // Storage: Swap Fees (r:1 w:1)
+    // Storage: Swap Accumulator (r:1 w:1)
+    // Storage: Swap Observations (r:1 w:1)
     fn buy_tokens() -> Weight {
         (68_000_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(4 as Weight))
-            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads(15 as Weight))
+            .saturating_add(T::DbWeight::get().writes(9 as Weight))
     }
     // Storage: System Account (r:1 w:1)
-    // Storage: Assets Account (r:2 w:2)
+    // Storage: Assets Account (r:3 w:3)
     // Storage: Assets Asset (r:1 w:1)
+    // Storage: Nft Royalty (r:1 w:0)
+    // Storage: Nft Frozen (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Swap Metadata (r:1 w:0)
+    // Storage: Swap ProtocolShare (r:1 w:0)
+    // Storage: Swap Fees (r:1 w:1)
+    // Storage: Swap Accumulator (r:1 w:1)
+    // Storage: Swap Observations (r:1 w:1)
     fn sell_tokens() -> Weight {
         (68_000_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(4 as Weight))
-            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads(15 as Weight))
+            .saturating_add(T::DbWeight::get().writes(9 as Weight))
     }
-    // Storage: System Account (r:1 w:1)
+    // Storage: System Account (r:2 w:2)
     // Storage: Assets Account (r:2 w:2)
     // Storage: Assets Asset (r:1 w:1)
+    // Storage: Nft Royalty (r:1 w:0)
+    // Storage: Nft Frozen (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Swap Metadata (r:1 w:0)
+    // Storage: Swap ProtocolShare (r:1 w:0)
+    // Storage: Swap Fees (r:1 w:1)
+    // Storage: Swap Accumulator (r:1 w:1)
+    // Storage: Swap Observations (r:1 w:1)
     fn sell_currency() -> Weight {
         (63_000_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(4 as Weight))
-            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads(15 as Weight))
+            .saturating_add(T::DbWeight::get().writes(9 as Weight))
     }
     // Storage: System Account (r:1 w:1)
-    // Storage: Assets Account (r:2 w:2)
+    // Storage: Assets Account (r:3 w:3)
     // Storage: Assets Asset (r:1 w:1)
+    // Storage: Nft Royalty (r:1 w:0)
+    // Storage: Nft Frozen (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Swap Metadata (r:1 w:0)
+    // Storage: Swap ProtocolShare (r:1 w:0)
+    // Storage: Swap Fees (r:1 w:1)
+    // Storage: Swap Accumulator (r:1 w:1)
+    // Storage: Swap Observations (r:1 w:1)
     fn buy_currency() -> Weight {
         (66_000_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(4 as Weight))
-            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads(15 as Weight))
+            .saturating_add(T::DbWeight::get().writes(9 as Weight))
+    }
+    // Storage: System Account (r:4 w:4)
+    // Storage: Assets Account (r:5 w:5)
+    // Storage: Assets Asset (r:2 w:2)
+    // Storage: Nft Royalty (r:2 w:0)
+    // Storage: Nft Frozen (r:2 w:0)
+    // Storage: Nft Metadata (r:2 w:0)
+    // Storage: Did Metadata (r:2 w:0)
+    // Storage: Swap Metadata (r:2 w:0)
+    // Storage: Swap ProtocolShare (r:2 w:0)
+    // Storage: Swap Fees (r:2 w:2)
+    // Storage: Swap Accumulator (r:2 w:2)
+    // Storage: Swap Observations (r:2 w:2)
+    fn swap_exact_tokens_for_tokens() -> Weight {
+        (132_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(29 as Weight))
+            .saturating_add(T::DbWeight::get().writes(17 as Weight))
+    }
+    // Storage: System Account (r:4 w:4)
+    // Storage: Assets Account (r:5 w:5)
+    // Storage: Assets Asset (r:2 w:2)
+    // Storage: Nft Royalty (r:2 w:0)
+    // Storage: Nft Frozen (r:2 w:0)
+    // Storage: Nft Metadata (r:2 w:0)
+    // Storage: Did Metadata (r:2 w:0)
+    // Storage: Swap Metadata (r:2 w:0)
+    // Storage: Swap ProtocolShare (r:2 w:0)
+    // Storage: Swap Fees (r:2 w:2)
+    // Storage: Swap Accumulator (r:2 w:2)
+    // Storage: Swap Observations (r:2 w:2)
+    fn swap_tokens_for_exact_tokens() -> Weight {
+        (132_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(29 as Weight))
+            .saturating_add(T::DbWeight::get().writes(17 as Weight))
+    }
+    // Storage: Swap Metadata (r:1 w:1)
+    fn set_fee() -> Weight {
+        (14_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Swap DefaultFee (r:0 w:1)
+    fn set_default_fee() -> Weight {
+        (9_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Swap ProtocolShare (r:0 w:1)
+    fn set_protocol_share() -> Weight {
+        (9_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Swap Metadata (r:1 w:0)
+    // Storage: Swap Curves (r:1 w:1)
+    fn set_curve() -> Weight {
+        (13_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Swap Account (r:1 w:2)
+    // Storage: Swap Liquidity (r:1 w:1)
+    // Storage: Swap Provider (r:2 w:2)
+    fn migrate_account(n: u32, ) -> Weight {
+        (3_000_000 as Weight)
+            // Standard Error: 9_000
+            .saturating_add((24_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
     }
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
     // Storage: Swap Metadata (r:1 w:1)
+    // Storage: Swap DefaultFee (r:1 w:0)
     fn create() -> Weight {
         (12_000_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
             .saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
     // Storage: Swap Metadata (r:1 w:1)
@@ -136,10 +252,12 @@ impl WeightInfo for () {
     // Storage: Swap Provider (r:1 w:1)
     // Storage: Swap Account (r:0 w:1)
     // Storage: Swap Liquidity (r:0 w:1)
+    // Storage: Swap Accumulator (r:1 w:1)
+    // Storage: Swap Observations (r:1 w:1)
     fn add_liquidity() -> Weight {
         (75_000_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(9 as Weight))
+            .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(11 as Weight))
     }
     // Storage: Swap Liquidity (r:1 w:1)
     // Storage: Swap Metadata (r:1 w:1)
@@ -148,41 +266,146 @@ impl WeightInfo for () {
     // Storage: Swap Provider (r:1 w:1)
     // Storage: Assets Asset (r:1 w:1)
     // Storage: Swap Account (r:0 w:1)
+    // Storage: Swap Accumulator (r:1 w:1)
+    // Storage: Swap Observations (r:1 w:1)
     fn remove_liquidity() -> Weight {
         (76_000_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(8 as Weight))
+            .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(10 as Weight))
     }
-    // Storage: System Account (r:1 w:1)
+    // Storage: System Account (r:2 w:2)
     // Storage: Assets Account (r:2 w:2)
     // Storage: Assets Asset (r:1 w:1)
+    // Storage: Nft Royalty (r:1 w:0)
+    // Storage: Nft Frozen (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Swap Metadata (r:1 w:0)
+    // Storage: Swap ProtocolShare (r:1 w:0)
+    // Storage: Swap Fees (r:1 w:1)
+    // Storage: Swap Accumulator (r:1 w:1)
+    // Storage: Swap Observations (r:1 w:1)
     fn buy_tokens() -> Weight {
         (68_000_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads(15 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(9 as Weight))
     }
     // Storage: System Account (r:1 w:1)
-    // Storage: Assets Account (r:2 w:2)
+    // Storage: Assets Account (r:3 w:3)
     // Storage: Assets Asset (r:1 w:1)
+    // Storage: Nft Royalty (r:1 w:0)
+    // Storage: Nft Frozen (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Swap Metadata (r:1 w:0)
+    // Storage: Swap ProtocolShare (r:1 w:0)
+    // Storage: Swap Fees (r:1 w:1)
+    // Storage: Swap Accumulator (r:1 w:1)
+    // Storage: Swap Observations (r:1 w:1)
     fn sell_tokens() -> Weight {
         (68_000_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads(15 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(9 as Weight))
     }
-    // Storage: System Account (r:1 w:1)
+    // Storage: System Account (r:2 w:2)
     // Storage: Assets Account (r:2 w:2)
     // Storage: Assets Asset (r:1 w:1)
+    // Storage: Nft Royalty (r:1 w:0)
+    // Storage: Nft Frozen (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Swap Metadata (r:1 w:0)
+    // Storage: Swap ProtocolShare (r:1 w:0)
+    // Storage: Swap Fees (r:1 w:1)
+    // Storage: Swap Accumulator (r:1 w:1)
+    // Storage: Swap Observations (r:1 w:1)
     fn sell_currency() -> Weight {
         (63_000_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads(15 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(9 as Weight))
     }
     // Storage: System Account (r:1 w:1)
-    // Storage: Assets Account (r:2 w:2)
+    // Storage: Assets Account (r:3 w:3)
     // Storage: Assets Asset (r:1 w:1)
+    // Storage: Nft Royalty (r:1 w:0)
+    // Storage: Nft Frozen (r:1 w:0)
+    // Storage: Nft Metadata (r:1 w:0)
+    // Storage: Did Metadata (r:1 w:0)
+    // Storage: Swap Metadata (r:1 w:0)
+    // Storage: Swap ProtocolShare (r:1 w:0)
+    // Storage: Swap Fees (r:1 w:1)
+    // Storage: Swap Accumulator (r:1 w:1)
+    // Storage: Swap Observations (r:1 w:1)
     fn buy_currency() -> Weight {
         (66_000_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads(15 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(9 as Weight))
+    }
+    // Storage: System Account (r:4 w:4)
+    // Storage: Assets Account (r:5 w:5)
+    // Storage: Assets Asset (r:2 w:2)
+    // Storage: Nft Royalty (r:2 w:0)
+    // Storage: Nft Frozen (r:2 w:0)
+    // Storage: Nft Metadata (r:2 w:0)
+    // Storage: Did Metadata (r:2 w:0)
+    // Storage: Swap Metadata (r:2 w:0)
+    // Storage: Swap ProtocolShare (r:2 w:0)
+    // Storage: Swap Fees (r:2 w:2)
+    // Storage: Swap Accumulator (r:2 w:2)
+    // Storage: Swap Observations (r:2 w:2)
+    fn swap_exact_tokens_for_tokens() -> Weight {
+        (132_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(29 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(17 as Weight))
+    }
+    // Storage: System Account (r:4 w:4)
+    // Storage: Assets Account (r:5 w:5)
+    // Storage: Assets Asset (r:2 w:2)
+    // Storage: Nft Royalty (r:2 w:0)
+    // Storage: Nft Frozen (r:2 w:0)
+    // Storage: Nft Metadata (r:2 w:0)
+    // Storage: Did Metadata (r:2 w:0)
+    // Storage: Swap Metadata (r:2 w:0)
+    // Storage: Swap ProtocolShare (r:2 w:0)
+    // Storage: Swap Fees (r:2 w:2)
+    // Storage: Swap Accumulator (r:2 w:2)
+    // Storage: Swap Observations (r:2 w:2)
+    fn swap_tokens_for_exact_tokens() -> Weight {
+        (132_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(29 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(17 as Weight))
+    }
+    // Storage: Swap Metadata (r:1 w:1)
+    fn set_fee() -> Weight {
+        (14_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Swap DefaultFee (r:0 w:1)
+    fn set_default_fee() -> Weight {
+        (9_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Swap ProtocolShare (r:0 w:1)
+    fn set_protocol_share() -> Weight {
+        (9_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Swap Metadata (r:1 w:0)
+    // Storage: Swap Curves (r:1 w:1)
+    fn set_curve() -> Weight {
+        (13_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    // Storage: Swap Account (r:1 w:2)
+    // Storage: Swap Liquidity (r:1 w:1)
+    // Storage: Swap Provider (r:2 w:2)
+    fn migrate_account(n: u32, ) -> Weight {
+        (3_000_000 as Weight)
+            // Standard Error: 9_000
+            .saturating_add((24_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
     }
 }
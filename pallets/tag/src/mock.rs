@@ -84,7 +84,7 @@ impl parami_did::Config for Test {
     type DecentralizedId = sp_core::H160;
     type Hashing = Keccak256;
     type WeightInfo = ();
-    type Nfts = ();
+    type Migrations = ();
 }
 
 parameter_types! {
@@ -0,0 +1,131 @@
+use codec::{Decode, Encode};
+use frame_support::{
+    traits::{BalanceStatus, Get, NamedReservableCurrency},
+    weights::{RuntimeDbWeight, Weight},
+    PalletId,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+    traits::{SaturatedConversion, Saturating, Zero},
+    DispatchError, RuntimeDebug,
+};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// An asset or position moved from one account to another
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum Migrated {
+    /// Free balance of native currency \[amount\]
+    Currency(u128),
+    /// Named reserve of native currency \[reserve id, amount\]
+    Reserved([u8; 8], u128),
+    /// Fractions of a NFT \[asset id, amount\]
+    Fractions(u128, u128),
+    /// Liquidity position \[lp token id, liquidity\]
+    Liquidity(u128, u128),
+    /// Sealed bid of an auction \[nft id, deposit\]
+    SealedBid(u128, u128),
+}
+
+pub trait AccountMigration<AccountId> {
+    /// Move all assets and positions held by an account to another account
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - The account to move from
+    /// * `dest` - The account to move to
+    /// * `items` - The maximum number of storage items to visit
+    ///
+    /// # Returns
+    ///
+    /// list of items moved, or `DispatchError::Exhausted` if more items are to be visited
+    fn migrate(
+        src: &AccountId,
+        dest: &AccountId,
+        items: u32,
+    ) -> Result<Vec<Migrated>, DispatchError>;
+
+    /// Weight of a migration visiting at most `items` storage items
+    fn migrate_weight(items: u32) -> Weight;
+}
+
+impl<AccountId> AccountMigration<AccountId> for () {
+    fn migrate(
+        _src: &AccountId,
+        _dest: &AccountId,
+        _items: u32,
+    ) -> Result<Vec<Migrated>, DispatchError> {
+        Ok(Vec::new())
+    }
+
+    fn migrate_weight(_items: u32) -> Weight {
+        0
+    }
+}
+
+/// Moves the native currency reserved under the id of a pallet
+pub struct NamedReserveMigration<Currency, Id, DbWeight>(PhantomData<(Currency, Id, DbWeight)>);
+
+impl<AccountId, C, I, W> AccountMigration<AccountId> for NamedReserveMigration<C, I, W>
+where
+    C: NamedReservableCurrency<AccountId, ReserveIdentifier = [u8; 8]>,
+    I: Get<PalletId>,
+    W: Get<RuntimeDbWeight>,
+{
+    fn migrate(
+        src: &AccountId,
+        dest: &AccountId,
+        _items: u32,
+    ) -> Result<Vec<Migrated>, DispatchError> {
+        let id = I::get();
+
+        let reserved = C::reserved_balance_named(&id.0, src);
+        if reserved.is_zero() {
+            return Ok(Vec::new());
+        }
+
+        let remaining =
+            C::repatriate_reserved_named(&id.0, src, dest, reserved, BalanceStatus::Reserved)?;
+
+        let moved = reserved.saturating_sub(remaining);
+
+        Ok(vec![Migrated::Reserved(id.0, moved.saturated_into())])
+    }
+
+    fn migrate_weight(_items: u32) -> Weight {
+        // Balances Reserves and System Account of both accounts
+        W::get().reads_writes(4, 4)
+    }
+}
+
+macro_rules! impl_account_migration_for_tuples {
+    ($($name:ident),+) => {
+        impl<AccountId, $($name: AccountMigration<AccountId>),+> AccountMigration<AccountId>
+            for ($($name,)+)
+        {
+            fn migrate(
+                src: &AccountId,
+                dest: &AccountId,
+                items: u32,
+            ) -> Result<Vec<Migrated>, DispatchError> {
+                let mut migrated = Vec::new();
+                $( migrated.extend($name::migrate(src, dest, items)?); )+
+                Ok(migrated)
+            }
+
+            fn migrate_weight(items: u32) -> Weight {
+                let mut weight: Weight = 0;
+                $( weight = weight.saturating_add($name::migrate_weight(items)); )+
+                weight
+            }
+        }
+    };
+}
+
+impl_account_migration_for_tuples!(A);
+impl_account_migration_for_tuples!(A, B);
+impl_account_migration_for_tuples!(A, B, C);
+impl_account_migration_for_tuples!(A, B, C, D);
+impl_account_migration_for_tuples!(A, B, C, D, E);
+impl_account_migration_for_tuples!(A, B, C, D, E, F);
+impl_account_migration_for_tuples!(A, B, C, D, E, F, G);
+impl_account_migration_for_tuples!(A, B, C, D, E, F, G, H);
@@ -0,0 +1,13 @@
+pub trait Holders<AccountId, AssetId> {
+    /// Note an account as a holder of a fraction token
+    ///
+    /// # Arguments
+    ///
+    /// * `token_id` - The Asset ID
+    /// * `who` - The account receiving the token
+    fn note_holder(token_id: AssetId, who: &AccountId);
+}
+
+impl<AccountId, AssetId> Holders<AccountId, AssetId> for () {
+    fn note_holder(_token_id: AssetId, _who: &AccountId) {}
+}
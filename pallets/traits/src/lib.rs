@@ -1,16 +1,25 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod accounts;
+pub use accounts::{AccountMigration, Migrated, NamedReserveMigration};
+
+mod holders;
+pub use holders::Holders;
+
 mod links;
 pub use links::Links;
 
+mod prices;
+pub use prices::Prices;
+
 mod swaps;
 pub use swaps::Swaps;
 
 mod tags;
 pub use tags::Tags;
 
-mod nfts;
-pub use nfts::Nfts;
+mod royalties;
+pub use royalties::Royalties;
 
 pub mod types {
     pub use parami_primitives::{Network, Task};
@@ -0,0 +1,22 @@
+use sp_runtime::{DispatchError, FixedU128};
+
+pub trait Prices<AssetId, Height> {
+    /// Get time-weighted average price of a fraction token
+    ///
+    /// # Arguments
+    ///
+    /// * `token_id` - The Asset ID
+    /// * `window` - The number of blocks to average over, ending at the current block
+    ///
+    /// # Returns
+    ///
+    /// The average price in currency per token,
+    /// averaged over at least `window` blocks
+    fn twap(token_id: AssetId, window: Height) -> Result<FixedU128, DispatchError>;
+}
+
+impl<AssetId, Height> Prices<AssetId, Height> for () {
+    fn twap(_token_id: AssetId, _window: Height) -> Result<FixedU128, DispatchError> {
+        Err(DispatchError::Other("No price oracle"))
+    }
+}
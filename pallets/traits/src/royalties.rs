@@ -0,0 +1,21 @@
+pub trait Royalties<AccountId, AssetId> {
+    /// Get royalty of a fraction token
+    ///
+    /// # Arguments
+    ///
+    /// * `token_id` - The Asset ID
+    ///
+    /// # Returns
+    ///
+    /// tuple of (account, royalty)
+    ///
+    /// * `account` - The account of the NFT owner, to receive the royalty
+    /// * `royalty` - The royalty in basis points
+    fn royalty_of(token_id: AssetId) -> Option<(AccountId, u32)>;
+}
+
+impl<AccountId, AssetId> Royalties<AccountId, AssetId> for () {
+    fn royalty_of(_token_id: AssetId) -> Option<(AccountId, u32)> {
+        None
+    }
+}
@@ -319,4 +319,92 @@ pub trait Swaps<AccountId> {
         max_tokens: Self::TokenBalance,
         keep_alive: bool,
     ) -> Result<Self::TokenBalance, DispatchError>;
+
+    /// Get dry-run result of token_to_token_in
+    ///
+    /// # Arguments
+    ///
+    /// * `token_in` - The Asset ID of tokens to be sold
+    /// * `token_out` - The Asset ID of tokens to be bought
+    /// * `tokens` - The amount of tokens to be sold
+    ///
+    /// # Returns
+    ///
+    /// tuple of (tokens, currency)
+    ///
+    /// * `tokens` - The amount of tokens to be gained
+    /// * `currency` - The currency routed between the swaps
+    fn token_to_token_in_dry(
+        token_in: Self::AssetId,
+        token_out: Self::AssetId,
+        tokens: Self::TokenBalance,
+    ) -> Result<(Self::TokenBalance, Self::QuoteBalance), DispatchError>;
+
+    /// Sell tokens for other tokens, routed through the currency
+    ///
+    /// * `who` - The account ID of the operator
+    /// * `token_in` - The Asset ID of tokens to be sold
+    /// * `token_out` - The Asset ID of tokens to be bought
+    /// * `tokens` - The amount of tokens to be sold
+    /// * `min_tokens` - The minimum amount of tokens to be gained
+    /// * `keep_alive` - Whether to keep the account alive
+    ///
+    /// # Returns
+    ///
+    /// tuple of (tokens, currency)
+    ///
+    /// * `tokens` - The amount of tokens gained
+    /// * `currency` - The currency routed between the swaps
+    fn token_to_token_in(
+        who: AccountId,
+        token_in: Self::AssetId,
+        token_out: Self::AssetId,
+        tokens: Self::TokenBalance,
+        min_tokens: Self::TokenBalance,
+        keep_alive: bool,
+    ) -> Result<(Self::TokenBalance, Self::QuoteBalance), DispatchError>;
+
+    /// Get dry-run result of token_to_token_out
+    ///
+    /// # Arguments
+    ///
+    /// * `token_in` - The Asset ID of tokens to be sold
+    /// * `token_out` - The Asset ID of tokens to be bought
+    /// * `tokens` - The amount of tokens to be bought
+    ///
+    /// # Returns
+    ///
+    /// tuple of (tokens, currency)
+    ///
+    /// * `tokens` - The amount of tokens needed
+    /// * `currency` - The currency routed between the swaps
+    fn token_to_token_out_dry(
+        token_in: Self::AssetId,
+        token_out: Self::AssetId,
+        tokens: Self::TokenBalance,
+    ) -> Result<(Self::TokenBalance, Self::QuoteBalance), DispatchError>;
+
+    /// Buy tokens with other tokens, routed through the currency
+    ///
+    /// * `who` - The account ID of the operator
+    /// * `token_in` - The Asset ID of tokens to be sold
+    /// * `token_out` - The Asset ID of tokens to be bought
+    /// * `tokens` - The amount of tokens to be bought
+    /// * `max_tokens` - The maximum amount of tokens to be spent
+    /// * `keep_alive` - Whether to keep the account alive
+    ///
+    /// # Returns
+    ///
+    /// tuple of (tokens, currency)
+    ///
+    /// * `tokens` - The amount of tokens spent
+    /// * `currency` - The currency routed between the swaps
+    fn token_to_token_out(
+        who: AccountId,
+        token_in: Self::AssetId,
+        token_out: Self::AssetId,
+        tokens: Self::TokenBalance,
+        max_tokens: Self::TokenBalance,
+        keep_alive: bool,
+    ) -> Result<(Self::TokenBalance, Self::QuoteBalance), DispatchError>;
 }
@@ -1,8 +1,11 @@
-use crate::VERSION;
+use crate::{Nft, Runtime, VERSION};
+use codec::Decode;
 use frame_support::storage::migration::{remove_storage_prefix, storage_key_iter};
 use frame_support::storage::PrefixIterator;
-use frame_support::traits::OnRuntimeUpgrade;
+use frame_support::traits::{Get, OnRuntimeUpgrade};
 use frame_support::weights::Weight;
+use parami_primitives::{AccountId, AssetId};
+use parami_traits::Holders;
 use sp_std::prelude::*;
 
 const DEPRECATED_PALLETS: &'static [&'static [u8]] = &[
@@ -86,3 +89,64 @@ pub fn pallet_key_count(module: &[u8]) -> usize {
     let closure = |_raw_key_without_prefix: &[u8], mut _raw_value: &[u8]| Ok(());
     PrefixIterator::<()>::new(prefix, previous_key, closure).count()
 }
+
+/// Index the holders of NFT fraction tokens, fractions received before
+/// the index existed would be left behind by an account migration
+pub struct IndexFractionHolders;
+
+impl OnRuntimeUpgrade for IndexFractionHolders {
+    fn on_runtime_upgrade() -> Weight {
+        if VERSION.spec_version > 338 {
+            return 0;
+        }
+
+        let mut reads: Weight = 0;
+        let mut writes: Weight = 0;
+
+        for (token_id, who) in asset_accounts() {
+            reads += 2;
+
+            if Nft::meta(token_id).is_some() {
+                Nft::note_holder(token_id, &who);
+
+                writes += 1;
+            }
+        }
+
+        log::info!("indexed {:?} fraction holders", writes);
+
+        <Runtime as frame_system::Config>::DbWeight::get().reads_writes(reads, writes)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade() -> Result<(), &'static str> {
+        if VERSION.spec_version > 338 {
+            return Ok(());
+        }
+
+        for (token_id, who) in asset_accounts() {
+            if Nft::meta(token_id).is_some() {
+                assert!(Nft::fractions_of(&who, token_id).is_some());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Iterate over the (asset, account) keys of `Assets::Account`,
+/// both hashed with Blake2_128Concat
+fn asset_accounts() -> impl Iterator<Item = (AssetId, AccountId)> {
+    let mut prefix = Vec::new();
+    prefix.extend_from_slice(&sp_io::hashing::twox_128(b"Assets"));
+    prefix.extend_from_slice(&sp_io::hashing::twox_128(b"Account"));
+
+    let previous_key = prefix.clone();
+    let closure = |raw_key_without_prefix: &[u8], _raw_value: &[u8]| {
+        let mut key = &raw_key_without_prefix[16..];
+        let token_id = AssetId::decode(&mut key)?;
+        let mut key = &key[16..];
+        let who = AccountId::decode(&mut key)?;
+        Ok((token_id, who))
+    };
+    PrefixIterator::<(AssetId, AccountId)>::new(prefix, previous_key, closure)
+}
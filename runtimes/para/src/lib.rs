@@ -18,8 +18,8 @@ use sp_runtime::{
         BlakeTwo256, Block as BlockT, ConvertInto, Extrinsic, Keccak256, StaticLookup, Verify,
     },
     transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
-    ApplyExtrinsicResult, DispatchError, FixedPointNumber, Perbill, Percent, Permill, Perquintill,
-    SaturatedConversion,
+    ApplyExtrinsicResult, DispatchError, FixedPointNumber, FixedU128, Perbill, Percent, Permill,
+    Perquintill, SaturatedConversion,
 };
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
@@ -53,9 +53,10 @@ pub use parami_primitives::{
     deposit, names, AccountId, Address, AssetId, Balance, BalanceWrapper, BlockNumber,
     DecentralizedId, Hash, Header, Index, Moment, Signature,
 };
-use parami_swap::LinearFarmingCurve;
-use parami_traits::Swaps;
+use parami_swap::{ExponentialDecayFarmingCurve, HalvingFarmingCurve, LinearFarmingCurve};
+use parami_traits::{Prices, Swaps};
 
+mod migrations;
 mod voter_bags;
 
 #[cfg(any(feature = "std", test))]
@@ -126,6 +127,7 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllPalletsWithSystem,
+    (crate::migrations::IndexFractionHolders),
 >;
 
 /// Era type as expected by this runtime.
@@ -217,10 +219,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("parami"),
     impl_name: create_runtime_str!("parami-node"),
     authoring_version: 20,
-    spec_version: 332,
+    spec_version: 334,
     impl_version: 0,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 2,
+    transaction_version: 3,
 };
 
 /// The version information used to identify this runtime when compiled natively.
@@ -1460,6 +1462,16 @@ parameter_types! {
     pub const AdPalletId: PalletId = PalletId(*names::AD);
     pub const AdvertiserMinimumFee: Balance = 50 * MILLICENTS;
     pub const SlotLifetime: BlockNumber = 3 * DAYS;
+    pub const AdMaxSlotsPerNft: u32 = 5;
+    pub const AdMaxSlotsPerAd: u32 = 100;
+    pub const AdOutbidIncrement: Percent = Percent::from_percent(20);
+    pub const AdAuctionCommitPeriod: BlockNumber = 1 * DAYS;
+    pub const AdAuctionRevealPeriod: BlockNumber = 1 * DAYS;
+    pub const AdMaxSealedBids: u32 = 50;
+    pub const AdMaxReferralDepth: u32 = 5;
+    pub const AdMaxPayBatch: u32 = 100;
+    pub const AdMaxExpiriesPerBlock: u32 = 100;
+    pub const AdMaxPurgesPerBlock: u32 = 500;
 }
 
 impl parami_ad::Config for Runtime {
@@ -1467,10 +1479,21 @@ impl parami_ad::Config for Runtime {
     type MinimumFeeBalance = AdvertiserMinimumFee;
     type PalletId = AdPalletId;
     type SlotLifetime = SlotLifetime;
+    type MaxSlotsPerNft = AdMaxSlotsPerNft;
+    type MaxSlotsPerAd = AdMaxSlotsPerAd;
+    type OutbidIncrement = AdOutbidIncrement;
+    type AuctionCommitPeriod = AdAuctionCommitPeriod;
+    type AuctionRevealPeriod = AdAuctionRevealPeriod;
+    type MaxSealedBids = AdMaxSealedBids;
+    type MaxReferralDepth = AdMaxReferralDepth;
+    type MaxPayBatch = AdMaxPayBatch;
+    type MaxExpiriesPerBlock = AdMaxExpiriesPerBlock;
+    type MaxPurgesPerBlock = AdMaxPurgesPerBlock;
     type Tags = Tag;
+    type Links = Linker;
     type CallOrigin = parami_advertiser::EnsureAdvertiser<Self>;
     type ForceOrigin = EnsureRootOrHalfCouncil;
-    type WeightInfo = ();
+    type WeightInfo = parami_ad::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
@@ -1532,7 +1555,7 @@ impl parami_did::Config for Runtime {
     type DecentralizedId = DecentralizedId;
     type Hashing = Keccak256;
     type WeightInfo = parami_did::weights::SubstrateWeight<Runtime>;
-    type Nfts = Nft;
+    type Migrations = (Nft, Swap, Ad, Linker, Advertiser);
 }
 
 parameter_types! {
@@ -1562,9 +1585,19 @@ impl parami_magic::Config for Runtime {
 parameter_types! {
     pub const InitialMintingDeposit: Balance = 1_000 * DOLLARS;
     pub const InitialMintingLockupPeriod: BlockNumber = 6 * 30 * DAYS;
+    pub const InitialMintingVestingCliff: BlockNumber = 0;
+    pub const InitialMintingVestingPeriod: BlockNumber = 6 * 30 * DAYS;
+    pub const NftMaxRoyalty: u32 = 1_000;
     pub const InitialMintingValueBase: Balance = 1_000_000 * DOLLARS;
     pub const NftPendingLifetime: BlockNumber = 5;
+    pub const NftReverifyInterval: BlockNumber = 1 * DAYS;
+    pub const NftMaxReverifyPerBlock: u32 = 20;
+    pub const BinanceNetwork: parami_primitives::Network = parami_primitives::Network::Binance;
+    pub const EthereumNetwork: parami_primitives::Network = parami_primitives::Network::Ethereum;
+    pub const NearNetwork: parami_primitives::Network = parami_primitives::Network::Near;
+    pub const SolanaNetwork: parami_primitives::Network = parami_primitives::Network::Solana;
     pub const NftPalletId: PalletId = PalletId(*names::NFT);
+    pub NftTreasuryAccount: AccountId = Treasury::account_id();
 }
 
 impl parami_nft::Config for Runtime {
@@ -1573,13 +1606,25 @@ impl parami_nft::Config for Runtime {
     type Assets = Assets;
     type InitialMintingDeposit = InitialMintingDeposit;
     type InitialMintingLockupPeriod = InitialMintingLockupPeriod;
+    type InitialMintingVestingCliff = InitialMintingVestingCliff;
+    type InitialMintingVestingPeriod = InitialMintingVestingPeriod;
     type InitialMintingValueBase = InitialMintingValueBase;
     type Links = Linker;
     type Nft = Uniques;
+    type MaxRoyalty = NftMaxRoyalty;
     type PalletId = NftPalletId;
     type PendingLifetime = NftPendingLifetime;
+    type ReverifyInterval = NftReverifyInterval;
+    type MaxReverifyPerBlock = NftMaxReverifyPerBlock;
+    type Validators = (
+        parami_nft::Validator<BinanceNetwork, parami_nft::Evm<Runtime>>,
+        parami_nft::Validator<EthereumNetwork, parami_nft::Evm<Runtime>>,
+        parami_nft::Validator<NearNetwork, parami_nft::Nep171<Runtime>>,
+        parami_nft::Validator<SolanaNetwork, parami_nft::Metaplex<Runtime>>,
+    );
     type StringLimit = StringLimit;
     type Swaps = Swap;
+    type TreasuryAccount = NftTreasuryAccount;
     type WeightInfo = parami_nft::weights::SubstrateWeight<Runtime>;
     type UnsignedPriority = UnsignedPriority;
 }
@@ -1588,7 +1633,18 @@ impl parami_ocw::Config for Runtime {}
 
 parameter_types! {
     pub const InitialFarmingReward: Balance = 100 * DOLLARS;
+    // halves about every year with the total farming reward
+    pub const DecayingFarmingReward: Balance = 185 * CENTS;
+    pub const TotalFarmingReward: Balance = 7_000_000 * DOLLARS;
+    pub const FarmingHalvingPeriod: BlockNumber = 365 * DAYS;
+    pub const LinearFarmingPeriod: BlockNumber = 1095 * DAYS + 18 * HOURS;
+    pub const SwapMaxCurveChanges: u32 = 16;
+    pub const SwapMaxFee: Permill = Permill::from_percent(10);
+    pub const SwapMaxProtocolShare: Permill = Permill::from_percent(50);
+    pub const SwapMaxObservations: u32 = 168;
+    pub const SwapObservationPeriod: BlockNumber = 1 * HOURS;
     pub const SwapPalletId: PalletId = PalletId(*names::SWAP);
+    pub SwapTreasuryAccount: AccountId = Treasury::account_id();
 }
 
 impl parami_swap::Config for Runtime {
@@ -1596,8 +1652,26 @@ impl parami_swap::Config for Runtime {
     type AssetId = AssetId;
     type Assets = Assets;
     type Currency = Balances;
-    type FarmingCurve = LinearFarmingCurve<Runtime, InitialFarmingReward, InitialMintingValueBase>;
+    type FarmingCurve = LinearFarmingCurve<
+        Runtime,
+        InitialFarmingReward,
+        InitialMintingValueBase,
+        TotalFarmingReward,
+        LinearFarmingPeriod,
+    >;
+    type ExponentialDecayCurve =
+        ExponentialDecayFarmingCurve<Runtime, DecayingFarmingReward, TotalFarmingReward>;
+    type HalvingCurve = HalvingFarmingCurve<Runtime, TotalFarmingReward, FarmingHalvingPeriod>;
+    type ForceOrigin = EnsureRootOrHalfCouncil;
+    type MaxCurveChanges = SwapMaxCurveChanges;
+    type MaxFee = SwapMaxFee;
+    type MaxProtocolShare = SwapMaxProtocolShare;
+    type MaxObservations = SwapMaxObservations;
+    type ObservationPeriod = SwapObservationPeriod;
     type PalletId = SwapPalletId;
+    type Royalties = Nft;
+    type Holders = Nft;
+    type TreasuryAccount = SwapTreasuryAccount;
     type WeightInfo = parami_swap::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1906,7 +1980,98 @@ impl_runtime_apis! {
         }
     }
 
-    impl parami_swap_rpc_runtime_api::SwapRuntimeApi<Block, AssetId, Balance> for Runtime {
+    impl parami_ad_rpc_runtime_api::AdRuntimeApi<Block, AssetId, Balance, DecentralizedId, Hash, BlockNumber> for Runtime {
+        fn slots_of(nft_id: AssetId) -> Result<Vec<(Hash, BlockNumber)>, DispatchError> {
+            Ok(Ad::slots_current(nft_id))
+        }
+
+        fn slot_budget(nft_id: AssetId, ad_id: Hash) -> Result<(
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+        ), DispatchError> {
+            Ad::slot_budget(nft_id, ad_id)
+                .map(|(fractions, fungibles)| (fractions.into(), fungibles.into()))
+        }
+
+        fn ads_of(did: DecentralizedId) -> Result<Vec<Hash>, DispatchError> {
+            Ok(Ad::ads_of(did).unwrap_or_default())
+        }
+
+        fn is_paid(ad_id: Hash, did: DecentralizedId) -> Result<bool, DispatchError> {
+            Ok(Ad::is_paid(ad_id, did))
+        }
+
+        fn dryly_pay(
+            ad_id: Hash,
+            nft_id: AssetId,
+            visitor: DecentralizedId,
+            referrer: Option<DecentralizedId>,
+        ) -> Result<
+            (
+                u32,
+                BalanceWrapper<Balance>,
+                BalanceWrapper<Balance>,
+                BalanceWrapper<Balance>,
+            ),
+            DispatchError,
+        > {
+            let (score, reward, award, fungibles) =
+                Ad::dryly_pay(ad_id, nft_id, visitor, referrer)?;
+            Ok((score, reward.into(), award.into(), fungibles.into()))
+        }
+    }
+
+    impl parami_nft_rpc_runtime_api::NftRuntimeApi<Block, AssetId, Balance, DecentralizedId, BlockNumber> for Runtime {
+        fn crowdfunding_of(nft_id: AssetId) -> Result<(
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+        ), DispatchError> {
+            Nft::crowdfunding(nft_id)
+                .map(|(pot, deposit, required)| (pot.into(), deposit.into(), required.into()))
+        }
+
+        fn shares_of(
+            nft_id: AssetId,
+            start_after: Option<DecentralizedId>,
+            limit: u32,
+        ) -> Result<Vec<(
+            DecentralizedId,
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+        )>, DispatchError> {
+            Nft::shares_of(nft_id, start_after, limit).map(|shares| {
+                shares
+                    .into_iter()
+                    .map(|(did, deposit, tokens, claimable)| {
+                        (did, deposit.into(), tokens.into(), claimable.into())
+                    })
+                    .collect()
+            })
+        }
+
+        fn lockup_of(nft_id: AssetId) -> Result<BlockNumber, DispatchError> {
+            Nft::lockup_of(nft_id)
+        }
+
+        fn porting_of(
+            did: DecentralizedId,
+            start_after: Option<(parami_primitives::Network, Vec<u8>, Vec<u8>)>,
+            limit: u32,
+        ) -> Result<
+            Vec<(parami_primitives::Network, Vec<u8>, Vec<u8>, BlockNumber)>,
+            DispatchError,
+        > {
+            Ok(Nft::porting_of(&did, start_after, limit))
+        }
+
+        fn symbol_of(symbol: Vec<u8>) -> Result<Option<AssetId>, DispatchError> {
+            Ok(Nft::symbol_of(&symbol))
+        }
+    }
+
+    impl parami_swap_rpc_runtime_api::SwapRuntimeApi<Block, AccountId, AssetId, Balance, BlockNumber> for Runtime {
         fn dryly_add_liquidity(
             token_id: AssetId,
             currency: BalanceWrapper<Balance>,
@@ -1962,6 +2127,68 @@ impl_runtime_apis! {
                 .map(|tokens| tokens.into())
         }
 
+        fn dryly_swap_tokens(
+            token_in: AssetId,
+            token_out: AssetId,
+            tokens: BalanceWrapper<Balance>,
+        ) -> Result<(BalanceWrapper<Balance>, BalanceWrapper<Balance>), DispatchError> {
+            Swap::token_to_token_in_dry(token_in, token_out, tokens.into())
+                .map(|(tokens, currency)| (tokens.into(), currency.into()))
+        }
+
+        fn get_fee(
+            token_id: AssetId,
+        ) -> Result<(Permill, Permill), DispatchError> {
+            Swap::fee_of(token_id).map(|fee| (fee, Swap::protocol_share()))
+        }
+
+        fn twap(
+            token_id: AssetId,
+            window: BlockNumber,
+        ) -> Result<FixedU128, DispatchError> {
+            Swap::twap(token_id, window)
+        }
+
+        fn get_pool(
+            token_id: AssetId,
+        ) -> Result<(
+            AccountId,
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+            BalanceWrapper<Balance>,
+            FixedU128,
+        ), DispatchError> {
+            Swap::pool_of(token_id).map(|(pot, currency, tokens, liquidity, price)| {
+                (pot, currency.into(), tokens.into(), liquidity.into(), price)
+            })
+        }
+
+        fn list_pools(
+            start_after: Option<AssetId>,
+            limit: u32,
+        ) -> Result<Vec<AssetId>, DispatchError> {
+            Ok(Swap::pools(start_after, limit))
+        }
+
+        fn list_positions(
+            who: AccountId,
+            start_after: Option<AssetId>,
+            limit: u32,
+        ) -> Result<Vec<(
+            AssetId,
+            AssetId,
+            BalanceWrapper<Balance>,
+            BlockNumber,
+            BlockNumber,
+        )>, DispatchError> {
+            Ok(Swap::positions_of(&who, start_after, limit)
+                .into_iter()
+                .map(|(lp_token_id, token_id, liquidity, minted, claimed)| {
+                    (lp_token_id, token_id, liquidity.into(), minted, claimed)
+                })
+                .collect())
+        }
+
         fn calculate_reward(
             lp_token_id: AssetId,
         ) -> Result<BalanceWrapper<Balance>, DispatchError> {